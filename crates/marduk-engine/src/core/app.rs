@@ -18,6 +18,10 @@ pub trait App {
         AppControl::Continue
     }
 
+    /// Called when a [`Proxy`](crate::window::Proxy) wakes the event loop,
+    /// before the frames it triggers.
+    fn on_wake(&mut self) {}
+
     /// Called once per rendered frame per window.
     fn on_frame(&mut self, ctx: &mut FrameCtx<'_, '_>) -> AppControl;
 }
\ No newline at end of file
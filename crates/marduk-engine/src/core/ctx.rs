@@ -32,6 +32,15 @@ impl<'a> WindowCtx<'a> {
     pub fn set_cursor(&self, cursor: CursorIcon) {
         self.window.set_cursor(cursor);
     }
+
+    /// Schedules another frame for this window.
+    ///
+    /// The runtime sleeps until an input event arrives; call this from
+    /// `on_frame` while something on screen is still changing on its own
+    /// (e.g. an animation) to keep frames coming.
+    pub fn request_redraw(&self) {
+        self.window.request_redraw();
+    }
 }
 
 /// Per-frame context passed to `core::App::on_frame`.
@@ -92,31 +101,6 @@ impl<'a, 'w> FrameCtx<'a, 'w> {
             }
         };
 
-        // Clear pass — dropped before the encoder is moved into submit().
-        {
-            let _rpass = frame.encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                label: Some("marduk clear"),
-                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view:           &frame.view,
-                    resolve_target: None,
-                    ops: wgpu::Operations {
-                        load:  wgpu::LoadOp::Clear(wgpu::Color {
-                            r: clear.r as f64,
-                            g: clear.g as f64,
-                            b: clear.b as f64,
-                            a: clear.a as f64,
-                        }),
-                        store: wgpu::StoreOp::Store,
-                    },
-                    depth_slice: None,
-                })],
-                depth_stencil_attachment: None,
-                timestamp_writes:         None,
-                occlusion_query_set:      None,
-                multiview_mask:           None,
-            });
-        }
-
         // Zoom is applied by shrinking the logical viewport and scaling up
         // scale_factor by the same amount.  Renderers upload `viewport` to
         // their viewport UBO (used in NDC conversion) and use `scale_factor`
@@ -132,6 +116,7 @@ impl<'a, 'w> FrameCtx<'a, 'w> {
         // RenderTarget borrows frame.encoder; dropped before submit() takes frame.
         {
             let mut target = RenderTarget::new(&mut frame.encoder, &frame.view);
+            target.clear(clear);
             draw(&rctx, &mut target);
         }
 
@@ -51,6 +51,7 @@ impl<'w> Gpu<'w> {
             required_features,
             required_limits,
             desired_maximum_frame_latency,
+            force_fallback_adapter,
         } = init;
 
         let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
@@ -66,7 +67,7 @@ impl<'w> Gpu<'w> {
             .request_adapter(&wgpu::RequestAdapterOptions {
                 power_preference: wgpu::PowerPreference::HighPerformance,
                 compatible_surface: Some(&surface),
-                force_fallback_adapter: false,
+                force_fallback_adapter,
             })
             .await
             .context("failed to find a suitable GPU adapter")?;
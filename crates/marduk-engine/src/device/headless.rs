@@ -0,0 +1,112 @@
+use anyhow::{Context, Result};
+
+use crate::paint::Color;
+use crate::render::{RenderCtx, RenderTarget};
+
+use super::{GpuInit, OffscreenTarget};
+
+/// GPU context with no window or surface.
+///
+/// The headless counterpart of [`Gpu`](super::Gpu): owns the
+/// Instance/Adapter/Device/Queue and renders only into [`OffscreenTarget`]s.
+/// Set [`GpuInit::force_fallback_adapter`] to run on a software adapter
+/// (lavapipe/llvmpipe) on machines with no GPU.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let gpu = pollster::block_on(HeadlessGpu::new(GpuInit::default()))?;
+/// let target = gpu.create_target(800, 600);
+/// gpu.render(&target, 1.0, clear, |rctx, t| {
+///     renderer.render(rctx, t, &mut draw_list, &fonts, &images);
+/// });
+/// let rgba = gpu.read_pixels(&target)?;
+/// ```
+pub struct HeadlessGpu {
+    /// Command queue.
+    queue: wgpu::Queue,
+
+    /// Logical device.
+    device: wgpu::Device,
+
+    /// Backend/driver description of the chosen adapter.
+    adapter_info: wgpu::AdapterInfo,
+
+    /// Declared last so it is dropped last (see `Gpu::instance`).
+    #[allow(dead_code)]
+    instance: wgpu::Instance,
+}
+
+impl HeadlessGpu {
+    /// Creates a device without a surface.
+    ///
+    /// Only the adapter/device fields of `init` apply; surface settings
+    /// (format preference, present and alpha mode, frame latency) are ignored.
+    pub async fn new(init: GpuInit) -> Result<Self> {
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
+            backends: wgpu::Backends::all(),
+            ..Default::default()
+        });
+
+        let adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::HighPerformance,
+                compatible_surface: None,
+                force_fallback_adapter: init.force_fallback_adapter,
+            })
+            .await
+            .context("failed to find a suitable GPU adapter")?;
+
+        let (device, queue) = adapter
+            .request_device(&wgpu::DeviceDescriptor {
+                label: Some("marduk-engine headless device"),
+                required_features: init.required_features,
+                required_limits: init.required_limits,
+                experimental_features: wgpu::ExperimentalFeatures::disabled(),
+                memory_hints: wgpu::MemoryHints::Performance,
+                trace: wgpu::Trace::Off,
+            })
+            .await
+            .context("failed to create wgpu device/queue")?;
+
+        Ok(Self {
+            instance,
+            adapter_info: adapter.get_info(),
+            device,
+            queue,
+        })
+    }
+
+    /// Returns a reference to the logical device.
+    pub fn device(&self) -> &wgpu::Device {
+        &self.device
+    }
+
+    /// Returns a reference to the command queue.
+    pub fn queue(&self) -> &wgpu::Queue {
+        &self.queue
+    }
+
+    /// Describes the adapter in use (name, backend, `DeviceType::Cpu` for software).
+    pub fn adapter_info(&self) -> &wgpu::AdapterInfo {
+        &self.adapter_info
+    }
+
+    /// Creates an [`OffscreenTarget`] on this device in the default format.
+    pub fn create_target(&self, width: u32, height: u32) -> OffscreenTarget {
+        OffscreenTarget::new(&self.device, width, height)
+    }
+
+    /// Clears `target`, calls `draw`, and submits. See [`OffscreenTarget::render`].
+    pub fn render<F>(&self, target: &OffscreenTarget, scale_factor: f32, clear: Color, draw: F)
+    where
+        F: FnOnce(&RenderCtx<'_>, &mut RenderTarget<'_>),
+    {
+        target.render(&self.device, &self.queue, scale_factor, clear, draw);
+    }
+
+    /// Reads `target` back to the CPU. See [`OffscreenTarget::read_pixels`].
+    pub fn read_pixels(&self, target: &OffscreenTarget) -> Result<Vec<u8>> {
+        target.read_pixels(&self.device, &self.queue)
+    }
+}
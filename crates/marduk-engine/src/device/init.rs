@@ -31,6 +31,11 @@ pub struct GpuInit {
     ///
     /// This value is a hint; support depends on platform/backend.
     pub desired_maximum_frame_latency: u32,
+
+    /// Request the platform's software adapter (lavapipe, llvmpipe, WARP).
+    ///
+    /// Needed for headless rendering on machines without a GPU, such as CI.
+    pub force_fallback_adapter: bool,
 }
 
 impl Default for GpuInit {
@@ -42,6 +47,7 @@ impl Default for GpuInit {
             required_features: wgpu::Features::empty(),
             required_limits: wgpu::Limits::default(),
             desired_maximum_frame_latency: 2,
+            force_fallback_adapter: false,
         }
     }
 }
\ No newline at end of file
@@ -4,14 +4,19 @@
 //! - wgpu instance/adapter/device/queue creation
 //! - surface creation and configuration
 //! - frame acquisition and command submission
+//! - surfaceless (headless) devices and offscreen render targets
 
 mod context;
 mod error;
 mod frame;
+mod headless;
 mod init;
+mod offscreen;
 mod surface;
 
 pub use context::Gpu;
 pub use error::SurfaceErrorAction;
 pub use frame::GpuFrame;
-pub use init::GpuInit;
\ No newline at end of file
+pub use headless::HeadlessGpu;
+pub use init::GpuInit;
+pub use offscreen::OffscreenTarget;
\ No newline at end of file
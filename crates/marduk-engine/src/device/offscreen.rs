@@ -0,0 +1,238 @@
+use anyhow::{Context, Result};
+use winit::dpi::PhysicalSize;
+
+use crate::coords::Viewport;
+use crate::paint::Color;
+use crate::render::{RenderCtx, RenderTarget};
+
+/// A texture the application owns and renders into instead of a swapchain.
+///
+/// Works with any device: the window-bound [`Gpu`](super::Gpu) for thumbnails
+/// or "export panel to PNG", or a [`HeadlessGpu`](super::HeadlessGpu) when no
+/// window exists. Renderers only see a [`RenderCtx`] + [`RenderTarget`], so
+/// the same `BatchRenderer` draws here unchanged.
+///
+/// Renderer resources belong to the device that created them; use separate
+/// renderer instances per device.
+pub struct OffscreenTarget {
+    texture: wgpu::Texture,
+    view: wgpu::TextureView,
+    format: wgpu::TextureFormat,
+    size: PhysicalSize<u32>,
+}
+
+impl OffscreenTarget {
+    /// Format used by [`new`](Self::new): sRGB like the preferred surface
+    /// formats, in RGBA byte order so readback is directly PNG-compatible.
+    pub const DEFAULT_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8UnormSrgb;
+
+    /// Creates a `width × height` (physical pixels) target in [`DEFAULT_FORMAT`](Self::DEFAULT_FORMAT).
+    pub fn new(device: &wgpu::Device, width: u32, height: u32) -> Self {
+        Self::with_format(device, width, height, Self::DEFAULT_FORMAT)
+    }
+
+    /// Creates a target with an explicit color format.
+    ///
+    /// `format` must be renderable and have a fixed per-texel size to support
+    /// [`read_pixels`](Self::read_pixels).
+    pub fn with_format(
+        device: &wgpu::Device,
+        width: u32,
+        height: u32,
+        format: wgpu::TextureFormat,
+    ) -> Self {
+        let size = PhysicalSize::new(width.max(1), height.max(1));
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("marduk offscreen target"),
+            size: wgpu::Extent3d {
+                width: size.width,
+                height: size.height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                | wgpu::TextureUsages::COPY_SRC
+                | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        Self { texture, view, format, size }
+    }
+
+    /// Returns the target size in physical pixels.
+    pub fn size(&self) -> PhysicalSize<u32> {
+        self.size
+    }
+
+    pub fn format(&self) -> wgpu::TextureFormat {
+        self.format
+    }
+
+    /// The underlying texture (e.g. to sample it as an image elsewhere).
+    pub fn texture(&self) -> &wgpu::Texture {
+        &self.texture
+    }
+
+    pub fn view(&self) -> &wgpu::TextureView {
+        &self.view
+    }
+
+    /// Clears the target with `clear`, calls `draw`, and submits the work.
+    ///
+    /// The logical viewport is `size / scale_factor`, so a draw list laid out
+    /// in logical pixels fills the texture at `scale_factor` physical pixels
+    /// per logical pixel — the offscreen counterpart of `FrameCtx::render_scaled`.
+    pub fn render<F>(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        scale_factor: f32,
+        clear: Color,
+        draw: F,
+    ) where
+        F: FnOnce(&RenderCtx<'_>, &mut RenderTarget<'_>),
+    {
+        let scale_factor = scale_factor.max(f32::EPSILON);
+        let rctx = RenderCtx::new(
+            device,
+            queue,
+            self.format,
+            Viewport::new(
+                self.size.width as f32 / scale_factor,
+                self.size.height as f32 / scale_factor,
+            ),
+            scale_factor,
+        );
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("marduk offscreen encoder"),
+        });
+        {
+            let mut target = RenderTarget::new(&mut encoder, &self.view);
+            target.clear(clear);
+            draw(&rctx, &mut target);
+        }
+        queue.submit(std::iter::once(encoder.finish()));
+    }
+
+    /// Copies the texture back to the CPU, blocking until the GPU is done.
+    ///
+    /// Rows are tightly packed (`width × bytes_per_texel`, top row first) in
+    /// the texture's own layout. For the default format that is sRGB RGBA8
+    /// with premultiplied alpha, the same bytes as `Pixmap::to_rgba8_premul`.
+    pub fn read_pixels(&self, device: &wgpu::Device, queue: &wgpu::Queue) -> Result<Vec<u8>> {
+        let texel = self
+            .format
+            .block_copy_size(None)
+            .with_context(|| format!("format {:?} cannot be read back", self.format))?;
+        let (width, height) = (self.size.width, self.size.height);
+        let row_bytes = width * texel;
+        let padded_row = row_bytes.div_ceil(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT)
+            * wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("marduk offscreen readback"),
+            size: padded_row as u64 * height as u64,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("marduk offscreen readback encoder"),
+        });
+        encoder.copy_texture_to_buffer(
+            wgpu::TexelCopyTextureInfo {
+                texture: &self.texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            wgpu::TexelCopyBufferInfo {
+                buffer: &buffer,
+                layout: wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_row),
+                    rows_per_image: Some(height),
+                },
+            },
+            wgpu::Extent3d { width, height, depth_or_array_layers: 1 },
+        );
+        queue.submit(std::iter::once(encoder.finish()));
+
+        let slice = buffer.slice(..);
+        let (tx, rx) = std::sync::mpsc::channel();
+        slice.map_async(wgpu::MapMode::Read, move |r| {
+            let _ = tx.send(r);
+        });
+        device
+            .poll(wgpu::PollType::wait_indefinitely())
+            .context("failed to wait for offscreen readback")?;
+        rx.recv()
+            .context("readback callback was dropped")?
+            .context("failed to map readback buffer")?;
+
+        let mut out = Vec::with_capacity((row_bytes * height) as usize);
+        {
+            let data = slice.get_mapped_range();
+            for row in data.chunks_exact(padded_row as usize) {
+                out.extend_from_slice(&row[..row_bytes as usize]);
+            }
+        }
+        buffer.unmap();
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coords::Rect;
+    use crate::device::{GpuInit, HeadlessGpu};
+    use crate::image::ImageStore;
+    use crate::render::BatchRenderer;
+    use crate::render::software::{Pixmap, SoftwareRenderer};
+    use crate::scene::{DrawList, ZIndex};
+    use crate::text::FontSystem;
+
+    /// Any adapter will do; returns `None` on machines with no GPU or
+    /// software Vulkan driver, where the test is skipped.
+    fn headless() -> Option<HeadlessGpu> {
+        let init = GpuInit { force_fallback_adapter: true, ..GpuInit::default() };
+        pollster::block_on(HeadlessGpu::new(init))
+            .or_else(|_| pollster::block_on(HeadlessGpu::new(GpuInit::default())))
+            .map_err(|e| eprintln!("skipping GPU readback test: {e:#}"))
+            .ok()
+    }
+
+    #[test]
+    fn readback_matches_software_renderer() {
+        let Some(gpu) = headless() else { return };
+
+        let clear = Color::from_straight(0.1, 0.1, 0.1, 1.0);
+        let mut dl = DrawList::new();
+        dl.push_solid_rect(ZIndex(0), Rect::new(2.0, 2.0, 8.0, 4.0), Color::from_straight(1.0, 0.0, 0.0, 1.0));
+        dl.push_solid_rect(ZIndex(1), Rect::new(6.0, 3.0, 8.0, 8.0), Color::from_straight(0.0, 0.0, 1.0, 0.5));
+        let (fonts, images) = (FontSystem::new(), ImageStore::new());
+
+        // Width 70 × 4 bytes forces row padding in the copy.
+        let target = gpu.create_target(70, 20);
+        let mut renderer = BatchRenderer::new();
+        gpu.render(&target, 2.0, clear, |rctx, t| {
+            renderer.render(rctx, t, &mut dl, &fonts, &images);
+        });
+        let gpu_bytes = gpu.read_pixels(&target).unwrap();
+
+        let mut pixmap = Pixmap::new(70, 20);
+        pixmap.fill(clear);
+        SoftwareRenderer::new().render(&mut pixmap, 2.0, &mut dl, &fonts, &images);
+        let cpu_bytes = pixmap.to_rgba8_premul();
+
+        assert_eq!(gpu_bytes.len(), cpu_bytes.len());
+        let worst = gpu_bytes.iter().zip(&cpu_bytes).map(|(a, b)| a.abs_diff(*b)).max();
+        assert!(worst <= Some(2), "max channel difference {worst:?}");
+    }
+}
@@ -92,16 +92,16 @@ pub fn translate_window_event(
             // Skip text when control shortcuts are active (Ctrl+C, Alt+F, …).
             // Filter out control characters (backspace = \x08, etc.) — those are
             // handled as Key events and must not also arrive as text input.
-            if event.state == ElementState::Pressed {
-                if let Some(text) = &event.text {
-                    let printable: String = text.chars().filter(|c| !c.is_control()).collect();
-                    if !printable.is_empty()
-                        && !modifiers.ctrl
-                        && !modifiers.alt
-                        && !modifiers.meta
-                    {
-                        events.push(InputEvent::Text(TextEvent { text: printable }));
-                    }
+            if event.state == ElementState::Pressed
+                && let Some(text) = &event.text
+            {
+                let printable: String = text.chars().filter(|c| !c.is_control()).collect();
+                if !printable.is_empty()
+                    && !modifiers.ctrl
+                    && !modifiers.alt
+                    && !modifiers.meta
+                {
+                    events.push(InputEvent::Text(TextEvent { text: printable }));
                 }
             }
 
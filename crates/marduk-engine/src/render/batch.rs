@@ -0,0 +1,160 @@
+//! Unified renderer that draws a whole `DrawList` in true paint order.
+
+use std::mem::{discriminant, Discriminant};
+
+use crate::image::{ImageId, ImageStore};
+use crate::render::shapes::circle::CircleRenderer;
+use crate::render::shapes::image::ImageRenderer;
+use crate::render::shapes::rect::RectRenderer;
+use crate::render::shapes::rounded_rect::RoundedRectRenderer;
+use crate::render::shapes::text::TextRenderer;
+use crate::render::{RenderCtx, RenderTarget};
+use crate::scene::{DrawCmd, DrawList};
+use crate::text::FontSystem;
+
+use super::shapes::common::{begin_load_pass, logical_clip_to_scissor, push_run, Run};
+
+// ── batch kind ────────────────────────────────────────────────────────────
+
+/// Which shape renderer (and, for images, which texture) a run is drawn with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BatchKind {
+    Rect,
+    RoundedRect,
+    Circle,
+    Image(ImageId),
+    Text,
+}
+
+// ── BatchRenderer ─────────────────────────────────────────────────────────
+
+/// Draws every command in a `DrawList` in a single render pass, in true paint
+/// order (z-index, then insertion order).
+///
+/// The draw list is walked once. Consecutive items that share a command type
+/// and clip rect (and texture, for images) are merged into one instanced draw
+/// call; whenever the type or clip changes a new draw is started, so a `Rect`
+/// pushed after a `Text` always paints over it.
+///
+/// Owns one of each shape renderer, so GPU resources (pipelines, the glyph
+/// atlas, uploaded image textures) persist across frames.
+#[derive(Default)]
+pub struct BatchRenderer {
+    rect: RectRenderer,
+    rounded_rect: RoundedRectRenderer,
+    circle: CircleRenderer,
+    image: ImageRenderer,
+    text: TextRenderer,
+
+    /// Per-frame draw runs, kept to reuse the allocation.
+    runs: Vec<Run<BatchKind>>,
+}
+
+impl BatchRenderer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Renders all commands in `draw_list` into `target`.
+    ///
+    /// `font_system` resolves `DrawCmd::Text` fonts and `images` supplies the
+    /// pixels for `DrawCmd::Image` (uploaded on first use).
+    pub fn render(
+        &mut self,
+        ctx: &RenderCtx<'_>,
+        target: &mut RenderTarget<'_>,
+        draw_list: &mut DrawList,
+        font_system: &FontSystem,
+        images: &ImageStore,
+    ) {
+        self.rect.begin(ctx);
+        self.rounded_rect.begin(ctx);
+        self.circle.begin(ctx);
+        self.image.begin(ctx);
+        self.text.begin(ctx);
+        self.runs.clear();
+
+        // ── stage instances and record runs ────────────────────────────────
+        for item in draw_list.iter_in_paint_order() {
+            let (kind, staged) = match &item.cmd {
+                DrawCmd::Rect(cmd) => {
+                    let start = self.rect.staged();
+                    self.rect.push(cmd);
+                    (BatchKind::Rect, start..self.rect.staged())
+                }
+                DrawCmd::RoundedRect(cmd) => {
+                    let start = self.rounded_rect.staged();
+                    self.rounded_rect.push(cmd);
+                    (BatchKind::RoundedRect, start..self.rounded_rect.staged())
+                }
+                DrawCmd::Circle(cmd) => {
+                    let start = self.circle.staged();
+                    self.circle.push(cmd);
+                    (BatchKind::Circle, start..self.circle.staged())
+                }
+                DrawCmd::Image(cmd) => {
+                    let start = self.image.staged();
+                    self.image.push(ctx, cmd, images);
+                    (BatchKind::Image(cmd.image_id), start..self.image.staged())
+                }
+                DrawCmd::Text(cmd) => {
+                    let start = self.text.staged();
+                    self.text.push(ctx, cmd, font_system);
+                    (BatchKind::Text, start..self.text.staged())
+                }
+            };
+            push_run(&mut self.runs, kind, item.clip_rect, staged);
+        }
+
+        if self.runs.is_empty() {
+            return;
+        }
+
+        self.rect.upload(ctx);
+        self.rounded_rect.upload(ctx);
+        self.circle.upload(ctx);
+        self.image.upload(ctx);
+        self.text.upload(ctx);
+
+        // ── draw runs in order ─────────────────────────────────────────────
+        let mut rpass = begin_load_pass(target.encoder, target.color_view, "marduk batch pass");
+
+        // Pipeline/buffers are only rebound when the renderer changes.
+        let mut bound: Option<(Discriminant<BatchKind>, bool)> = None;
+
+        for run in &self.runs {
+            let Some((sx, sy, sw, sh)) =
+                logical_clip_to_scissor(run.clip, ctx.viewport, ctx.scale_factor)
+            else {
+                continue;
+            };
+
+            let d = discriminant(&run.kind);
+            let ready = match bound {
+                Some((bd, ready)) if bd == d => ready,
+                _ => {
+                    let ready = match run.kind {
+                        BatchKind::Rect => self.rect.bind(&mut rpass),
+                        BatchKind::RoundedRect => self.rounded_rect.bind(&mut rpass),
+                        BatchKind::Circle => self.circle.bind(&mut rpass),
+                        BatchKind::Image(_) => self.image.bind(&mut rpass),
+                        BatchKind::Text => self.text.bind(&mut rpass),
+                    };
+                    bound = Some((d, ready));
+                    ready
+                }
+            };
+            if !ready {
+                continue;
+            }
+            if let BatchKind::Image(id) = run.kind
+                && !self.image.bind_image(&mut rpass, id)
+            {
+                continue;
+            }
+
+            rpass.set_scissor_rect(sx, sy, sw, sh);
+            rpass.draw_indexed(0..6, 0, run.instances.clone());
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use crate::coords::Viewport;
+use crate::paint::Color;
 
 /// Renderer-facing context (device/queue + surface format + viewport + scale factor).
 ///
@@ -44,4 +45,31 @@ impl<'a> RenderTarget<'a> {
     pub fn new(encoder: &'a mut wgpu::CommandEncoder, color_view: &'a wgpu::TextureView) -> Self {
         Self { encoder, color_view }
     }
+
+    /// Records a pass that clears the whole target to `color`.
+    ///
+    /// `color` is linear premultiplied, like every other engine colour.
+    pub fn clear(&mut self, color: Color) {
+        let _rpass = self.encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("marduk clear"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view:           self.color_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load:  wgpu::LoadOp::Clear(wgpu::Color {
+                        r: color.r as f64,
+                        g: color.g as f64,
+                        b: color.b as f64,
+                        a: color.a as f64,
+                    }),
+                    store: wgpu::StoreOp::Store,
+                },
+                depth_slice: None,
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes:         None,
+            occlusion_query_set:      None,
+            multiview_mask:           None,
+        });
+    }
 }
@@ -1,13 +1,21 @@
-//! GPU rendering subsystem.
+//! Rendering subsystem.
 //!
 //! Renderers consume `scene` draw streams and issue GPU commands via wgpu.
 //! Each renderer is responsible for its own GPU resources (pipelines, buffers).
+//! [`software`] rasterizes the same streams on the CPU, without a GPU.
 //!
 //! Convention:
 //! - CPU geometry is in logical pixels (top-left origin, +Y down).
 //! - Vertex shader converts to NDC using a viewport uniform.
+//!
+//! [`BatchRenderer`] draws a whole draw list in paint order and is what most
+//! applications want; the per-shape renderers in [`shapes`] remain available
+//! for drawing a single command type in isolation.
 
+mod batch;
 mod ctx;
 pub mod shapes;
+pub mod software;
 
-pub use ctx::{RenderCtx, RenderTarget};
\ No newline at end of file
+pub use batch::BatchRenderer;
+pub use ctx::{RenderCtx, RenderTarget};
@@ -1,5 +1,7 @@
 //! Shared GPU types and utilities used by all shape renderers.
 
+use std::ops::Range;
+
 use bytemuck::{Pod, Zeroable};
 
 use crate::coords::{Rect, Viewport};
@@ -7,7 +9,7 @@ use crate::paint::Paint;
 
 // ── blend ─────────────────────────────────────────────────────────────────
 
-pub(super) fn premul_alpha_blend() -> wgpu::BlendState {
+pub(in crate::render) fn premul_alpha_blend() -> wgpu::BlendState {
     wgpu::BlendState {
         color: wgpu::BlendComponent {
             src_factor: wgpu::BlendFactor::One,
@@ -22,11 +24,76 @@ pub(super) fn premul_alpha_blend() -> wgpu::BlendState {
     }
 }
 
+// ── render pass ───────────────────────────────────────────────────────────
+
+/// Begins a render pass that loads (rather than clears) the existing contents
+/// of `color_view`.
+pub(in crate::render) fn begin_load_pass<'e>(
+    encoder: &'e mut wgpu::CommandEncoder,
+    color_view: &wgpu::TextureView,
+    label: &'static str,
+) -> wgpu::RenderPass<'e> {
+    encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        label: Some(label),
+        color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+            view: color_view,
+            resolve_target: None,
+            ops: wgpu::Operations {
+                load: wgpu::LoadOp::Load,
+                store: wgpu::StoreOp::Store,
+            },
+            depth_slice: None,
+        })],
+        depth_stencil_attachment: None,
+        timestamp_writes: None,
+        occlusion_query_set: None,
+        multiview_mask: None,
+    })
+}
+
+// ── instance runs ─────────────────────────────────────────────────────────
+
+/// A contiguous range of staged instances drawn with one instanced call.
+///
+/// `kind` identifies the pipeline/bindings needed for the draw; runs are only
+/// merged when both `kind` and `clip` match.
+#[derive(Debug, Clone, PartialEq)]
+pub(in crate::render) struct Run<K> {
+    pub kind: K,
+    pub clip: Option<Rect>,
+    pub instances: Range<u32>,
+}
+
+/// Appends `instances` to `runs`, extending the last run when it has the same
+/// kind and clip and ends exactly where `instances` begins.
+///
+/// Empty ranges are dropped so that commands producing no geometry (e.g. a
+/// blank text string) don't split an otherwise mergeable run.
+pub(in crate::render) fn push_run<K: PartialEq>(
+    runs: &mut Vec<Run<K>>,
+    kind: K,
+    clip: Option<Rect>,
+    instances: Range<u32>,
+) {
+    if instances.is_empty() {
+        return;
+    }
+    if let Some(last) = runs.last_mut()
+        && last.kind == kind
+        && last.clip == clip
+        && last.instances.end == instances.start
+    {
+        last.instances.end = instances.end;
+        return;
+    }
+    runs.push(Run { kind, clip, instances });
+}
+
 // ── viewport uniform ──────────────────────────────────────────────────────
 
 #[repr(C)]
 #[derive(Debug, Copy, Clone, Pod, Zeroable)]
-pub(super) struct ViewportUniform {
+pub(in crate::render) struct ViewportUniform {
     pub viewport: [f32; 2],
     pub _pad: [f32; 2], // 16-byte alignment
 }
@@ -35,14 +102,14 @@ pub(super) struct ViewportUniform {
 
 #[repr(C)]
 #[derive(Debug, Copy, Clone, Pod, Zeroable)]
-pub(super) struct QuadVertex {
+pub(in crate::render) struct QuadVertex {
     pub pos: [f32; 2], // 0..1
 }
 
 impl QuadVertex {
     const ATTRS: [wgpu::VertexAttribute; 1] = wgpu::vertex_attr_array![0 => Float32x2];
 
-    pub(super) fn layout() -> wgpu::VertexBufferLayout<'static> {
+    pub(in crate::render) fn layout() -> wgpu::VertexBufferLayout<'static> {
         wgpu::VertexBufferLayout {
             array_stride: std::mem::size_of::<QuadVertex>() as u64,
             step_mode: wgpu::VertexStepMode::Vertex,
@@ -51,14 +118,14 @@ impl QuadVertex {
     }
 }
 
-pub(super) const QUAD_VERTICES: [QuadVertex; 4] = [
+pub(in crate::render) const QUAD_VERTICES: [QuadVertex; 4] = [
     QuadVertex { pos: [0.0, 0.0] },
     QuadVertex { pos: [1.0, 0.0] },
     QuadVertex { pos: [1.0, 1.0] },
     QuadVertex { pos: [0.0, 1.0] },
 ];
 
-pub(super) const QUAD_INDICES: [u16; 6] = [0, 1, 2, 0, 2, 3];
+pub(in crate::render) const QUAD_INDICES: [u16; 6] = [0, 1, 2, 0, 2, 3];
 
 // ── scissor rect ──────────────────────────────────────────────────────────
 
@@ -68,7 +135,7 @@ pub(super) const QUAD_INDICES: [u16; 6] = [0, 1, 2, 0, 2, 3];
 /// Returns `Some((x, y, w, h))` in physical pixels, clamped to the viewport.
 ///
 /// `clip = None` means "no scissor" → returns the full viewport rect.
-pub(super) fn logical_clip_to_scissor(
+pub(in crate::render) fn logical_clip_to_scissor(
     clip: Option<Rect>,
     viewport: Viewport,
     scale: f32,
@@ -97,7 +164,7 @@ pub(super) fn logical_clip_to_scissor(
 /// `ViewportUniform` contains two `[f32; 2]` fields (16 bytes total) so its
 /// size is always non-zero. Centralising this avoids `.unwrap()` at each
 /// renderer's pipeline-creation site.
-pub(super) fn viewport_ubo_min_binding_size() -> std::num::NonZeroU64 {
+pub(in crate::render) fn viewport_ubo_min_binding_size() -> std::num::NonZeroU64 {
     std::num::NonZeroU64::new(std::mem::size_of::<ViewportUniform>() as u64)
         .expect("ViewportUniform has non-zero size by construction")
 }
@@ -111,7 +178,7 @@ pub(super) fn viewport_ubo_min_binding_size() -> std::num::NonZeroU64 {
 ///
 /// Linear gradients are clamped to 2 stops (first and last); more stops are
 /// unsupported and emit a one-time debug message.
-pub(super) fn resolve_paint(
+pub(in crate::render) fn resolve_paint(
     paint: &Paint,
     warned_multi_stop: &mut bool,
 ) -> ([f32; 4], [f32; 4], [f32; 2], [f32; 2]) {
@@ -145,3 +212,38 @@ pub(super) fn resolve_paint(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_run_merges_same_kind_and_clip() {
+        let mut runs = Vec::new();
+        push_run(&mut runs, 'r', None, 0..2);
+        push_run(&mut runs, 'r', None, 2..3);
+        assert_eq!(runs, vec![Run { kind: 'r', clip: None, instances: 0..3 }]);
+    }
+
+    #[test]
+    fn push_run_splits_on_kind_or_clip_change() {
+        let clip = Some(Rect::new(0.0, 0.0, 10.0, 10.0));
+        let mut runs = Vec::new();
+        push_run(&mut runs, 'r', None, 0..1);
+        push_run(&mut runs, 't', None, 0..4);
+        push_run(&mut runs, 'r', None, 1..2);
+        push_run(&mut runs, 'r', clip, 2..3);
+        let kinds: Vec<_> = runs.iter().map(|r| (r.kind, r.instances.clone())).collect();
+        assert_eq!(kinds, vec![('r', 0..1), ('t', 0..4), ('r', 1..2), ('r', 2..3)]);
+    }
+
+    #[test]
+    fn push_run_skips_empty_ranges() {
+        let mut runs = Vec::new();
+        push_run(&mut runs, 'r', None, 0..1);
+        push_run(&mut runs, 't', None, 0..0);
+        push_run(&mut runs, 'r', None, 1..2);
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].instances, 0..2);
+    }
+}
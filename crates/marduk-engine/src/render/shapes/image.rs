@@ -5,11 +5,12 @@ use wgpu::util::DeviceExt;
 
 use crate::image::{ImageId, ImageStore};
 use crate::render::{RenderCtx, RenderTarget};
+use crate::scene::shapes::image::ImageCmd;
 use crate::scene::{DrawCmd, DrawList};
 
 use super::common::{
-    logical_clip_to_scissor, premul_alpha_blend, viewport_ubo_min_binding_size,
-    QuadVertex, ViewportUniform, QUAD_INDICES, QUAD_VERTICES,
+    begin_load_pass, logical_clip_to_scissor, premul_alpha_blend, push_run,
+    viewport_ubo_min_binding_size, QuadVertex, ViewportUniform, QUAD_INDICES, QUAD_VERTICES,
 };
 
 // ── Per-GPU-image state ───────────────────────────────────────────────────
@@ -48,6 +49,9 @@ pub struct ImageRenderer {
     quad_vbo: Option<wgpu::Buffer>,
     quad_ibo: Option<wgpu::Buffer>,
 
+    instances: Vec<ImageInstance>,
+    instance_vbo: Option<wgpu::Buffer>,
+
     gpu_images: HashMap<ImageId, GpuImage>,
 }
 
@@ -56,6 +60,10 @@ impl ImageRenderer {
         Self::default()
     }
 
+    /// Renders every `DrawCmd::Image` in `draw_list` into `target`, in its own pass.
+    ///
+    /// Use [`BatchRenderer`](crate::render::BatchRenderer) to interleave images
+    /// with other command types in true paint order.
     pub fn render(
         &mut self,
         ctx: &RenderCtx<'_>,
@@ -63,107 +71,113 @@ impl ImageRenderer {
         draw_list: &mut DrawList,
         store: &ImageStore,
     ) {
-        self.ensure_pipeline(ctx);
-        self.ensure_static_buffers(ctx);
-        self.ensure_viewport_binding(ctx);
-
-        // Collect instances, grouping by ImageId so we can batch by texture.
-        // Each entry: (instance_data, image_id, clip_rect)
-        let mut instances: Vec<(ImageInstance, ImageId, Option<crate::coords::Rect>)> = Vec::new();
+        self.begin(ctx);
 
+        // Runs break on image id (different texture bind group) as well as clip.
+        let mut runs = Vec::new();
         for item in draw_list.iter_in_paint_order() {
             let DrawCmd::Image(cmd) = &item.cmd else { continue };
+            let start = self.staged();
+            self.push(ctx, cmd, store);
+            push_run(&mut runs, cmd.image_id, item.clip_rect, start..self.staged());
+        }
 
-            let r = cmd.rect.normalized();
-            if r.is_empty() {
-                continue;
-            }
+        if runs.is_empty() {
+            return;
+        }
+        self.upload(ctx);
 
-            // Ensure the GPU image exists for this id.
-            self.ensure_gpu_image(ctx, cmd.image_id, store);
-
-            let rd = cmd.corner_radii;
-            instances.push((
-                ImageInstance {
-                    origin: [r.origin.x, r.origin.y],
-                    size: [r.size.x, r.size.y],
-                    uv_min: cmd.uv_min,
-                    uv_max: cmd.uv_max,
-                    tint: cmd.tint,
-                    radii: [rd.top_left, rd.top_right, rd.bottom_right, rd.bottom_left],
-                },
-                cmd.image_id,
-                item.clip_rect,
-            ));
+        let mut rpass = begin_load_pass(target.encoder, target.color_view, "marduk image pass");
+        if !self.bind(&mut rpass) {
+            return;
         }
+        for run in runs {
+            if let Some((sx, sy, sw, sh)) =
+                logical_clip_to_scissor(run.clip, ctx.viewport, ctx.scale_factor)
+                && self.bind_image(&mut rpass, run.kind)
+            {
+                rpass.set_scissor_rect(sx, sy, sw, sh);
+                rpass.draw_indexed(0..6, 0, run.instances);
+            }
+        }
+    }
+
+    // ── batched rendering ──────────────────────────────────────────────────
+
+    /// Ensures GPU resources exist and discards instances staged last frame.
+    pub(in crate::render) fn begin(&mut self, ctx: &RenderCtx<'_>) {
+        self.ensure_pipeline(ctx);
+        self.ensure_static_buffers(ctx);
+        self.ensure_viewport_binding(ctx);
+        self.instances.clear();
+    }
+
+    /// Number of instances staged since [`begin`](Self::begin).
+    pub(in crate::render) fn staged(&self) -> u32 {
+        self.instances.len() as u32
+    }
 
-        if instances.is_empty() {
+    /// Stages `cmd` as an instance, uploading its texture on first use.
+    /// Empty rects are skipped.
+    pub(in crate::render) fn push(&mut self, ctx: &RenderCtx<'_>, cmd: &ImageCmd, store: &ImageStore) {
+        let r = cmd.rect.normalized();
+        if r.is_empty() {
             return;
         }
 
-        self.write_viewport_uniform(ctx);
+        self.ensure_gpu_image(ctx, cmd.image_id, store);
 
-        let raw: Vec<ImageInstance> = instances.iter().map(|(inst, _, _)| *inst).collect();
-        let instance_vbo = ctx.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("marduk image instance vbo"),
-            contents: bytemuck::cast_slice(&raw),
-            usage: wgpu::BufferUsages::VERTEX,
+        let rd = cmd.corner_radii;
+        self.instances.push(ImageInstance {
+            origin: [r.origin.x, r.origin.y],
+            size: [r.size.x, r.size.y],
+            uv_min: cmd.uv_min,
+            uv_max: cmd.uv_max,
+            tint: cmd.tint,
+            radii: [rd.top_left, rd.top_right, rd.bottom_right, rd.bottom_left],
         });
+    }
 
-        let Some(pipeline) = self.pipeline.as_ref() else { return };
-        let Some(bg_viewport) = self.bg_viewport.as_ref() else { return };
-        let Some(quad_vbo) = self.quad_vbo.as_ref() else { return };
-        let Some(quad_ibo) = self.quad_ibo.as_ref() else { return };
-
-        let mut rpass = target.encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-            label: Some("marduk image pass"),
-            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                view: target.color_view,
-                resolve_target: None,
-                ops: wgpu::Operations {
-                    load: wgpu::LoadOp::Load,
-                    store: wgpu::StoreOp::Store,
-                },
-                depth_slice: None,
-            })],
-            depth_stencil_attachment: None,
-            timestamp_writes: None,
-            occlusion_query_set: None,
-            multiview_mask: None,
-        });
+    /// Uploads staged instances and the viewport uniform.
+    pub(in crate::render) fn upload(&mut self, ctx: &RenderCtx<'_>) {
+        if self.instances.is_empty() {
+            self.instance_vbo = None;
+            return;
+        }
+        self.write_viewport_uniform(ctx);
+        self.instance_vbo = Some(ctx.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("marduk image instance vbo"),
+            contents: bytemuck::cast_slice(&self.instances),
+            usage: wgpu::BufferUsages::VERTEX,
+        }));
+    }
 
+    /// Binds pipeline, viewport and buffers on `rpass`. Returns `false` if
+    /// nothing was uploaded. Call [`bind_image`](Self::bind_image) before each draw.
+    pub(in crate::render) fn bind(&self, rpass: &mut wgpu::RenderPass<'_>) -> bool {
+        let (Some(pipeline), Some(bg_viewport), Some(quad_vbo), Some(quad_ibo), Some(instance_vbo)) = (
+            self.pipeline.as_ref(),
+            self.bg_viewport.as_ref(),
+            self.quad_vbo.as_ref(),
+            self.quad_ibo.as_ref(),
+            self.instance_vbo.as_ref(),
+        ) else {
+            return false;
+        };
         rpass.set_pipeline(pipeline);
         rpass.set_bind_group(0, bg_viewport, &[]);
         rpass.set_vertex_buffer(0, quad_vbo.slice(..));
         rpass.set_vertex_buffer(1, instance_vbo.slice(..));
         rpass.set_index_buffer(quad_ibo.slice(..), wgpu::IndexFormat::Uint16);
+        true
+    }
 
-        // Draw each instance individually (each may have a different texture).
-        // Group consecutive instances with the same image_id AND same clip.
-        let mut i = 0u32;
-        while i < instances.len() as u32 {
-            let (_, img_id, clip) = &instances[i as usize];
-            let img_id = *img_id;
-            let clip = *clip;
-
-            // Extend run only if same image_id and same clip.
-            let mut j = i + 1;
-            while j < instances.len() as u32 {
-                let (_, jid, jclip) = &instances[j as usize];
-                if *jid != img_id || *jclip != clip { break; }
-                j += 1;
-            }
-
-            if let (Some(gpu_img), Some((sx, sy, sw, sh))) = (
-                self.gpu_images.get(&img_id),
-                logical_clip_to_scissor(clip, ctx.viewport, ctx.scale_factor),
-            ) {
-                rpass.set_scissor_rect(sx, sy, sw, sh);
-                rpass.set_bind_group(1, &gpu_img.bind_group, &[]);
-                rpass.draw_indexed(0..6, 0, i..j);
-            }
-            i = j;
-        }
+    /// Binds the texture for `id`. Returns `false` if it was never uploaded
+    /// (e.g. the id is not in the store).
+    pub(in crate::render) fn bind_image(&self, rpass: &mut wgpu::RenderPass<'_>, id: ImageId) -> bool {
+        let Some(gpu_img) = self.gpu_images.get(&id) else { return false };
+        rpass.set_bind_group(1, &gpu_img.bind_group, &[]);
+        true
     }
 
     // ── private helpers ────────────────────────────────────────────────────
@@ -1,6 +1,6 @@
 //! Shape renderers.
 
-mod common;
+pub(in crate::render) mod common;
 
 pub mod circle;
 pub mod image;
@@ -3,11 +3,12 @@ use wgpu::util::DeviceExt;
 
 use crate::paint::Paint;
 use crate::render::{RenderCtx, RenderTarget};
+use crate::scene::shapes::rect::RectCmd;
 use crate::scene::{DrawCmd, DrawList};
 
 use super::common::{
-    logical_clip_to_scissor, premul_alpha_blend, viewport_ubo_min_binding_size, QuadVertex,
-    ViewportUniform, QUAD_INDICES, QUAD_VERTICES,
+    begin_load_pass, logical_clip_to_scissor, premul_alpha_blend, push_run,
+    viewport_ubo_min_binding_size, QuadVertex, ViewportUniform, QUAD_INDICES, QUAD_VERTICES,
 };
 
 /// Rectangle renderer (solid fill only for v0).
@@ -26,6 +27,9 @@ pub struct RectRenderer {
     quad_vbo: Option<wgpu::Buffer>,
     quad_ibo: Option<wgpu::Buffer>,
 
+    instances: Vec<RectInstance>,
+    instance_vbo: Option<wgpu::Buffer>,
+
     warned_non_solid: bool,
 }
 
@@ -40,108 +44,123 @@ impl RectRenderer {
     /// - `DrawCmd::Rect` with `Paint::Solid`
     ///
     /// Unsupported paints are ignored (one-time debug message).
+    ///
+    /// This draws rectangles only, in their own pass. Use
+    /// [`BatchRenderer`](crate::render::BatchRenderer) to interleave them with
+    /// other command types in true paint order.
     pub fn render(
         &mut self,
         ctx: &RenderCtx<'_>,
         target: &mut RenderTarget<'_>,
         draw_list: &mut DrawList,
     ) {
+        self.begin(ctx);
+
+        let mut runs = Vec::new();
+        for item in draw_list.iter_in_paint_order() {
+            let DrawCmd::Rect(cmd) = &item.cmd else { continue };
+            let start = self.staged();
+            self.push(cmd);
+            push_run(&mut runs, (), item.clip_rect, start..self.staged());
+        }
+
+        if runs.is_empty() {
+            return;
+        }
+        self.upload(ctx);
+
+        let mut rpass = begin_load_pass(target.encoder, target.color_view, "marduk rect pass");
+        if !self.bind(&mut rpass) {
+            return;
+        }
+        // Draw one instanced call per consecutive clip-rect group.
+        for run in runs {
+            if let Some((sx, sy, sw, sh)) =
+                logical_clip_to_scissor(run.clip, ctx.viewport, ctx.scale_factor)
+            {
+                rpass.set_scissor_rect(sx, sy, sw, sh);
+                rpass.draw_indexed(0..6, 0, run.instances);
+            }
+        }
+    }
+
+    // ── batched rendering ──────────────────────────────────────────────────
+
+    /// Ensures GPU resources exist and discards instances staged last frame.
+    pub(in crate::render) fn begin(&mut self, ctx: &RenderCtx<'_>) {
         self.ensure_pipeline(ctx);
         self.ensure_static_buffers(ctx);
         self.ensure_bindings(ctx);
+        self.instances.clear();
+    }
 
-        // Build instance data from draw list in paint order.
-        // Each entry pairs the GPU instance with its clip rect.
-        let mut instances: Vec<(RectInstance, Option<crate::coords::Rect>)> = Vec::new();
+    /// Number of instances staged since [`begin`](Self::begin).
+    pub(in crate::render) fn staged(&self) -> u32 {
+        self.instances.len() as u32
+    }
 
-        for item in draw_list.iter_in_paint_order() {
-            let DrawCmd::Rect(cmd) = &item.cmd else { continue };
-            match &cmd.paint {
-                Paint::Solid(c) => {
-                    let r = cmd.rect.normalized();
-                    if r.is_empty() {
-                        continue;
-                    }
-                    instances.push((
-                        RectInstance {
-                            origin: [r.origin.x, r.origin.y],
-                            size: [r.size.x, r.size.y],
-                            color: [c.r, c.g, c.b, c.a],
-                        },
-                        item.clip_rect,
-                    ));
+    /// Stages `cmd` as an instance. Empty and non-solid rects are skipped.
+    pub(in crate::render) fn push(&mut self, cmd: &RectCmd) {
+        match &cmd.paint {
+            Paint::Solid(c) => {
+                let r = cmd.rect.normalized();
+                if r.is_empty() {
+                    return;
                 }
-                _ => {
-                    if !self.warned_non_solid {
-                        log::debug!("RectRenderer: non-solid paint encountered; ignored in v0");
-                        self.warned_non_solid = true;
-                    }
+                self.instances.push(RectInstance {
+                    origin: [r.origin.x, r.origin.y],
+                    size: [r.size.x, r.size.y],
+                    color: [c.r, c.g, c.b, c.a],
+                });
+            }
+            _ => {
+                if !self.warned_non_solid {
+                    log::debug!("RectRenderer: non-solid paint encountered; ignored in v0");
+                    self.warned_non_solid = true;
                 }
             }
         }
+    }
 
-        if instances.is_empty() {
+    /// Uploads staged instances and the viewport uniform.
+    ///
+    /// A fresh instance buffer is created on every call: `write_buffer` is
+    /// deferred to queue submit, so reusing one buffer across several passes
+    /// in the same frame would corrupt earlier draws.
+    pub(in crate::render) fn upload(&mut self, ctx: &RenderCtx<'_>) {
+        if self.instances.is_empty() {
+            self.instance_vbo = None;
             return;
         }
-
         self.write_viewport_uniform(ctx);
-
-        // Create a fresh per-call buffer so that two-pass rendering doesn't corrupt
-        // the first pass's data when write_buffer is deferred to queue submit.
-        let raw: Vec<RectInstance> = instances.iter().map(|(inst, _)| *inst).collect();
-        let instance_vbo = ctx.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        self.instance_vbo = Some(ctx.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: Some("marduk rect instance vbo"),
-            contents: bytemuck::cast_slice(&raw),
+            contents: bytemuck::cast_slice(&self.instances),
             usage: wgpu::BufferUsages::VERTEX,
-        });
-
-        let Some(pipeline) = self.pipeline.as_ref() else { return };
-        let Some(bind_group) = self.bind_group.as_ref() else { return };
-        let Some(quad_vbo) = self.quad_vbo.as_ref() else { return };
-        let Some(quad_ibo) = self.quad_ibo.as_ref() else { return };
-
-        let mut rpass = target.encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-            label: Some("marduk rect pass"),
-            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                view: target.color_view,
-                resolve_target: None,
-                ops: wgpu::Operations {
-                    load: wgpu::LoadOp::Load,
-                    store: wgpu::StoreOp::Store,
-                },
-                depth_slice: None,
-            })],
-            depth_stencil_attachment: None,
-            timestamp_writes: None,
-            occlusion_query_set: None,
-            multiview_mask: None,
-        });
+        }));
+    }
 
+    /// Binds pipeline and buffers on `rpass`. Returns `false` if nothing was uploaded.
+    pub(in crate::render) fn bind(&self, rpass: &mut wgpu::RenderPass<'_>) -> bool {
+        let (Some(pipeline), Some(bind_group), Some(quad_vbo), Some(quad_ibo), Some(instance_vbo)) = (
+            self.pipeline.as_ref(),
+            self.bind_group.as_ref(),
+            self.quad_vbo.as_ref(),
+            self.quad_ibo.as_ref(),
+            self.instance_vbo.as_ref(),
+        ) else {
+            return false;
+        };
         rpass.set_pipeline(pipeline);
         rpass.set_bind_group(0, bind_group, &[]);
         rpass.set_vertex_buffer(0, quad_vbo.slice(..));
         rpass.set_vertex_buffer(1, instance_vbo.slice(..));
         rpass.set_index_buffer(quad_ibo.slice(..), wgpu::IndexFormat::Uint16);
-        // instance_vbo is a local; wgpu holds a ref internally until GPU completes
-
-        // Draw one instanced call per consecutive clip-rect group.
-        let mut i = 0u32;
-        while i < instances.len() as u32 {
-            let clip = instances[i as usize].1;
-            let mut j = i + 1;
-            while j < instances.len() as u32 && instances[j as usize].1 == clip {
-                j += 1;
-            }
-            if let Some((sx, sy, sw, sh)) =
-                logical_clip_to_scissor(clip, ctx.viewport, ctx.scale_factor)
-            {
-                rpass.set_scissor_rect(sx, sy, sw, sh);
-                rpass.draw_indexed(0..6, 0, i..j);
-            }
-            i = j;
-        }
+        true
     }
 
+    // ── private helpers ────────────────────────────────────────────────────
+
     fn ensure_pipeline(&mut self, ctx: &RenderCtx<'_>) {
         if self.pipeline_format == Some(ctx.surface_format) && self.pipeline.is_some() {
             return;
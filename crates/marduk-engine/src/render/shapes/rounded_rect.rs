@@ -2,11 +2,12 @@ use bytemuck::{Pod, Zeroable};
 use wgpu::util::DeviceExt;
 
 use crate::render::{RenderCtx, RenderTarget};
+use crate::scene::shapes::rounded_rect::RoundedRectCmd;
 use crate::scene::{DrawCmd, DrawList};
 
 use super::common::{
-    logical_clip_to_scissor, premul_alpha_blend, resolve_paint, viewport_ubo_min_binding_size,
-    QuadVertex, ViewportUniform, QUAD_INDICES, QUAD_VERTICES,
+    begin_load_pass, logical_clip_to_scissor, premul_alpha_blend, push_run, resolve_paint,
+    viewport_ubo_min_binding_size, QuadVertex, ViewportUniform, QUAD_INDICES, QUAD_VERTICES,
 };
 
 /// Renderer for `DrawCmd::RoundedRect`.
@@ -28,6 +29,9 @@ pub struct RoundedRectRenderer {
     quad_vbo: Option<wgpu::Buffer>,
     quad_ibo: Option<wgpu::Buffer>,
 
+    instances: Vec<RoundedRectInstance>,
+    instance_vbo: Option<wgpu::Buffer>,
+
     warned_multi_stop: bool,
 }
 
@@ -36,107 +40,120 @@ impl RoundedRectRenderer {
         Self::default()
     }
 
+    /// Renders every `DrawCmd::RoundedRect` in `draw_list` into `target`, in its own pass.
+    ///
+    /// Use [`BatchRenderer`](crate::render::BatchRenderer) to interleave these
+    /// with other command types in true paint order.
     pub fn render(
         &mut self,
         ctx: &RenderCtx<'_>,
         target: &mut RenderTarget<'_>,
         draw_list: &mut DrawList,
     ) {
-        self.ensure_pipeline(ctx);
-        self.ensure_static_buffers(ctx);
-        self.ensure_bindings(ctx);
-
-        let mut instances: Vec<(RoundedRectInstance, Option<crate::coords::Rect>)> = Vec::new();
+        self.begin(ctx);
 
+        let mut runs = Vec::new();
         for item in draw_list.iter_in_paint_order() {
             let DrawCmd::RoundedRect(cmd) = &item.cmd else { continue };
+            let start = self.staged();
+            self.push(cmd);
+            push_run(&mut runs, (), item.clip_rect, start..self.staged());
+        }
 
-            let r = cmd.rect.normalized();
-            if r.is_empty() {
-                continue;
-            }
+        if runs.is_empty() {
+            return;
+        }
+        self.upload(ctx);
 
-            let (color0, color1, grad_p0, grad_p1) =
-                resolve_paint(&cmd.paint, &mut self.warned_multi_stop);
-
-            let rd = cmd.radii;
-            let (border_width, border_color) = match &cmd.border {
-                Some(b) => (b.width.max(0.0), [b.color.r, b.color.g, b.color.b, b.color.a]),
-                None => (0.0, [0.0f32; 4]),
-            };
-
-            instances.push((
-                RoundedRectInstance {
-                    origin: [r.origin.x, r.origin.y],
-                    size: [r.size.x, r.size.y],
-                    radii: [rd.top_left, rd.top_right, rd.bottom_right, rd.bottom_left],
-                    color0,
-                    color1,
-                    grad_p0,
-                    grad_p1,
-                    border_width_pad: [border_width, 0.0],
-                    border_color,
-                },
-                item.clip_rect,
-            ));
+        let mut rpass = begin_load_pass(target.encoder, target.color_view, "marduk rounded_rect pass");
+        if !self.bind(&mut rpass) {
+            return;
+        }
+        for run in runs {
+            if let Some((sx, sy, sw, sh)) =
+                logical_clip_to_scissor(run.clip, ctx.viewport, ctx.scale_factor)
+            {
+                rpass.set_scissor_rect(sx, sy, sw, sh);
+                rpass.draw_indexed(0..6, 0, run.instances);
+            }
         }
+    }
+
+    // ── batched rendering ──────────────────────────────────────────────────
+
+    /// Ensures GPU resources exist and discards instances staged last frame.
+    pub(in crate::render) fn begin(&mut self, ctx: &RenderCtx<'_>) {
+        self.ensure_pipeline(ctx);
+        self.ensure_static_buffers(ctx);
+        self.ensure_bindings(ctx);
+        self.instances.clear();
+    }
 
-        if instances.is_empty() {
+    /// Number of instances staged since [`begin`](Self::begin).
+    pub(in crate::render) fn staged(&self) -> u32 {
+        self.instances.len() as u32
+    }
+
+    /// Stages `cmd` as an instance. Empty rects are skipped.
+    pub(in crate::render) fn push(&mut self, cmd: &RoundedRectCmd) {
+        let r = cmd.rect.normalized();
+        if r.is_empty() {
             return;
         }
 
-        self.write_viewport_uniform(ctx);
+        let (color0, color1, grad_p0, grad_p1) =
+            resolve_paint(&cmd.paint, &mut self.warned_multi_stop);
+
+        let rd = cmd.radii;
+        let (border_width, border_color) = match &cmd.border {
+            Some(b) => (b.width.max(0.0), [b.color.r, b.color.g, b.color.b, b.color.a]),
+            None => (0.0, [0.0f32; 4]),
+        };
+
+        self.instances.push(RoundedRectInstance {
+            origin: [r.origin.x, r.origin.y],
+            size: [r.size.x, r.size.y],
+            radii: [rd.top_left, rd.top_right, rd.bottom_right, rd.bottom_left],
+            color0,
+            color1,
+            grad_p0,
+            grad_p1,
+            border_width_pad: [border_width, 0.0],
+            border_color,
+        });
+    }
 
-        let raw: Vec<RoundedRectInstance> = instances.iter().map(|(inst, _)| *inst).collect();
-        let instance_vbo = ctx.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+    /// Uploads staged instances and the viewport uniform.
+    pub(in crate::render) fn upload(&mut self, ctx: &RenderCtx<'_>) {
+        if self.instances.is_empty() {
+            self.instance_vbo = None;
+            return;
+        }
+        self.write_viewport_uniform(ctx);
+        self.instance_vbo = Some(ctx.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: Some("marduk rounded_rect instance vbo"),
-            contents: bytemuck::cast_slice(&raw),
+            contents: bytemuck::cast_slice(&self.instances),
             usage: wgpu::BufferUsages::VERTEX,
-        });
-
-        let Some(pipeline) = self.pipeline.as_ref() else { return };
-        let Some(bind_group) = self.bind_group.as_ref() else { return };
-        let Some(quad_vbo) = self.quad_vbo.as_ref() else { return };
-        let Some(quad_ibo) = self.quad_ibo.as_ref() else { return };
-
-        let mut rpass = target.encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-            label: Some("marduk rounded_rect pass"),
-            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                view: target.color_view,
-                resolve_target: None,
-                ops: wgpu::Operations {
-                    load: wgpu::LoadOp::Load,
-                    store: wgpu::StoreOp::Store,
-                },
-                depth_slice: None,
-            })],
-            depth_stencil_attachment: None,
-            timestamp_writes: None,
-            occlusion_query_set: None,
-            multiview_mask: None,
-        });
+        }));
+    }
 
+    /// Binds pipeline and buffers on `rpass`. Returns `false` if nothing was uploaded.
+    pub(in crate::render) fn bind(&self, rpass: &mut wgpu::RenderPass<'_>) -> bool {
+        let (Some(pipeline), Some(bind_group), Some(quad_vbo), Some(quad_ibo), Some(instance_vbo)) = (
+            self.pipeline.as_ref(),
+            self.bind_group.as_ref(),
+            self.quad_vbo.as_ref(),
+            self.quad_ibo.as_ref(),
+            self.instance_vbo.as_ref(),
+        ) else {
+            return false;
+        };
         rpass.set_pipeline(pipeline);
         rpass.set_bind_group(0, bind_group, &[]);
         rpass.set_vertex_buffer(0, quad_vbo.slice(..));
         rpass.set_vertex_buffer(1, instance_vbo.slice(..));
         rpass.set_index_buffer(quad_ibo.slice(..), wgpu::IndexFormat::Uint16);
-
-        let mut i = 0u32;
-        while i < instances.len() as u32 {
-            let clip = instances[i as usize].1;
-            let mut j = i + 1;
-            while j < instances.len() as u32 && instances[j as usize].1 == clip {
-                j += 1;
-            }
-            if let Some((sx, sy, sw, sh)) =
-                logical_clip_to_scissor(clip, ctx.viewport, ctx.scale_factor)
-            {
-                rpass.set_scissor_rect(sx, sy, sw, sh);
-                rpass.draw_indexed(0..6, 0, i..j);
-            }
-            i = j;
-        }
+        true
     }
 
     // ── private helpers ────────────────────────────────────────────────────
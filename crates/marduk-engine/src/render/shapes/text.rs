@@ -5,12 +5,13 @@ use fontdue::layout::{CoordinateSystem, GlyphRasterConfig, Layout, LayoutSetting
 use wgpu::util::DeviceExt;
 
 use crate::render::{RenderCtx, RenderTarget};
+use crate::scene::shapes::text::TextCmd;
 use crate::scene::{DrawCmd, DrawList};
 use crate::text::FontSystem;
 
 use super::common::{
-    logical_clip_to_scissor, premul_alpha_blend, viewport_ubo_min_binding_size, QuadVertex,
-    ViewportUniform, QUAD_INDICES, QUAD_VERTICES,
+    begin_load_pass, logical_clip_to_scissor, premul_alpha_blend, push_run,
+    viewport_ubo_min_binding_size, QuadVertex, ViewportUniform, QUAD_INDICES, QUAD_VERTICES,
 };
 
 // ── atlas constants ────────────────────────────────────────────────────────
@@ -68,6 +69,10 @@ pub struct TextRenderer {
     quad_vbo: Option<wgpu::Buffer>,
     quad_ibo: Option<wgpu::Buffer>,
 
+    // per-frame glyph instances
+    instances: Vec<GlyphInstance>,
+    instance_vbo: Option<wgpu::Buffer>,
+
     // reusable fontdue layout
     layout: Layout<()>,
 }
@@ -93,6 +98,8 @@ impl Default for TextRenderer {
             current_raster_scale: 0.0, // 0.0 forces an update on the first frame
             quad_vbo: None,
             quad_ibo: None,
+            instances: Vec::new(),
+            instance_vbo: None,
             layout: Layout::new(CoordinateSystem::PositiveYDown),
         }
     }
@@ -103,7 +110,10 @@ impl TextRenderer {
         Self::default()
     }
 
-    /// Renders all `DrawCmd::Text` entries in `draw_list`.
+    /// Renders all `DrawCmd::Text` entries in `draw_list`, in their own pass.
+    ///
+    /// Use [`BatchRenderer`](crate::render::BatchRenderer) to interleave text
+    /// with other command types in true paint order.
     pub fn render(
         &mut self,
         ctx: &RenderCtx<'_>,
@@ -111,10 +121,45 @@ impl TextRenderer {
         draw_list: &mut DrawList,
         font_system: &FontSystem,
     ) {
+        self.begin(ctx);
+
+        let mut runs = Vec::new();
+        for item in draw_list.iter_in_paint_order() {
+            let DrawCmd::Text(cmd) = &item.cmd else { continue };
+            let start = self.staged();
+            self.push(ctx, cmd, font_system);
+            push_run(&mut runs, (), item.clip_rect, start..self.staged());
+        }
+
+        if runs.is_empty() {
+            return;
+        }
+        self.upload(ctx);
+
+        let mut rpass = begin_load_pass(target.encoder, target.color_view, "marduk text pass");
+        if !self.bind(&mut rpass) {
+            return;
+        }
+        for run in runs {
+            if let Some((sx, sy, sw, sh)) =
+                logical_clip_to_scissor(run.clip, ctx.viewport, ctx.scale_factor)
+            {
+                rpass.set_scissor_rect(sx, sy, sw, sh);
+                rpass.draw_indexed(0..6, 0, run.instances);
+            }
+        }
+    }
+
+    // ── batched rendering ──────────────────────────────────────────────────
+
+    /// Ensures GPU resources exist, invalidates the glyph cache if the
+    /// physical scale changed, and discards instances staged last frame.
+    pub(in crate::render) fn begin(&mut self, ctx: &RenderCtx<'_>) {
         self.ensure_pipeline(ctx);
         self.ensure_atlas(ctx);
         self.ensure_sampler(ctx);
         self.ensure_static_buffers(ctx);
+        self.instances.clear();
 
         // ── physical scale for crisp rasterization ─────────────────────────
         // Quantise to 0.25 steps so the atlas doesn't accumulate dozens of
@@ -131,149 +176,114 @@ impl TextRenderer {
             self.atlas_generation += 1;
             self.current_raster_scale = raster_scale;
         }
+    }
 
-        // ── collect text commands (with clip rects) ────────────────────────
-        let text_cmds: Vec<_> = draw_list
-            .iter_in_paint_order()
-            .filter_map(|item| {
-                if let DrawCmd::Text(cmd) = &item.cmd {
-                    Some((cmd.clone(), item.clip_rect))
-                } else {
-                    None
-                }
-            })
+    /// Number of glyph instances staged since [`begin`](Self::begin).
+    pub(in crate::render) fn staged(&self) -> u32 {
+        self.instances.len() as u32
+    }
+
+    /// Lays out `cmd` and stages one instance per visible glyph, rasterizing
+    /// and uploading glyphs that are not yet in the atlas.
+    pub(in crate::render) fn push(&mut self, ctx: &RenderCtx<'_>, cmd: &TextCmd, font_system: &FontSystem) {
+        let Some(font) = font_system.get(cmd.font) else {
+            log::warn!("TextRenderer: unknown FontId {:?}, skipping", cmd.font);
+            return;
+        };
+
+        let raster_scale = self.current_raster_scale;
+        let color = [cmd.color.r, cmd.color.g, cmd.color.b, cmd.color.a];
+
+        // Lay out at physical pixel size so fontdue rasterizes at full
+        // resolution.  Round the origin to the nearest integer physical
+        // pixel so glyph quad edges always land on exact pixel boundaries —
+        // avoids sub-pixel fringe when divided back to logical coordinates.
+        self.layout.reset(&LayoutSettings {
+            x: (cmd.origin.x * raster_scale).round(),
+            y: (cmd.origin.y * raster_scale).round(),
+            max_width: cmd.max_width.map(|w| (w * raster_scale).ceil()),
+            ..LayoutSettings::default()
+        });
+        self.layout.append(&[font], &TextStyle::new(&cmd.text, cmd.size * raster_scale, 0));
+
+        // Snapshot glyph positions into a plain Vec so that the borrow on
+        // `self.layout` ends before we call `self.try_place_glyph` (which
+        // needs `&mut self`).
+        let glyph_snap: Vec<(GlyphRasterConfig, f32, f32, usize, usize)> = self
+            .layout
+            .glyphs()
+            .iter()
+            .filter(|g| g.char_data.rasterize() && g.width > 0 && g.height > 0)
+            .map(|g| (g.key, g.x, g.y, g.width, g.height))
             .collect();
 
-        // ── build glyph instance list ──────────────────────────────────────
-        let mut instances: Vec<(GlyphInstance, Option<crate::coords::Rect>)> = Vec::new();
-
-        for (cmd, clip) in &text_cmds {
-            let Some(font) = font_system.get(cmd.font) else {
-                log::warn!("TextRenderer: unknown FontId {:?}, skipping", cmd.font);
-                continue;
-            };
-
-            let color = [cmd.color.r, cmd.color.g, cmd.color.b, cmd.color.a];
-
-            // Lay out at physical pixel size so fontdue rasterizes at full
-            // resolution.  Round the origin to the nearest integer physical
-            // pixel so glyph quad edges always land on exact pixel boundaries —
-            // avoids sub-pixel fringe when divided back to logical coordinates.
-            self.layout.reset(&LayoutSettings {
-                x: (cmd.origin.x * raster_scale).round(),
-                y: (cmd.origin.y * raster_scale).round(),
-                max_width: cmd.max_width.map(|w| (w * raster_scale).ceil()),
-                ..LayoutSettings::default()
-            });
-            self.layout.append(&[font], &TextStyle::new(&cmd.text, cmd.size * raster_scale, 0));
-
-            // Snapshot glyph positions into a plain Vec so that the borrow on
-            // `self.layout` ends before we call `self.try_place_glyph` (which
-            // needs `&mut self`).
-            let glyph_snap: Vec<(GlyphRasterConfig, f32, f32, usize, usize)> = self
-                .layout
-                .glyphs()
-                .iter()
-                .filter(|g| g.char_data.rasterize() && g.width > 0 && g.height > 0)
-                .map(|g| (g.key, g.x, g.y, g.width, g.height))
-                .collect();
-
-            for (key, x, y, w, h) in glyph_snap {
-                // Rasterize + upload on first encounter.
-                if !self.glyph_cache.contains_key(&key) {
-                    let (metrics, bitmap) = font.rasterize_config(key);
-                    if metrics.width == 0 || metrics.height == 0 {
-                        continue;
-                    }
-                    if let Some(entry) = self.try_place_glyph(
-                        ctx,
-                        &bitmap,
-                        metrics.width as u32,
-                        metrics.height as u32,
-                    ) {
-                        self.glyph_cache.insert(key, entry);
-                    }
+        for (key, x, y, w, h) in glyph_snap {
+            // Rasterize + upload on first encounter.
+            if !self.glyph_cache.contains_key(&key) {
+                let (metrics, bitmap) = font.rasterize_config(key);
+                if metrics.width == 0 || metrics.height == 0 {
+                    continue;
+                }
+                if let Some(entry) = self.try_place_glyph(
+                    ctx,
+                    &bitmap,
+                    metrics.width as u32,
+                    metrics.height as u32,
+                ) {
+                    self.glyph_cache.insert(key, entry);
                 }
-
-                let Some(cached) = self.glyph_cache.get(&key) else { continue };
-
-                // Round to integer physical pixels first, then divide to
-                // logical.  This ensures each quad edge aligns to an exact
-                // screen pixel, preventing the nearest-neighbour sampler from
-                // straddling a texel boundary and producing a 1px fringe.
-                let rs = raster_scale;
-                instances.push((
-                    GlyphInstance {
-                        dst_min: [x.round() / rs,              y.round() / rs],
-                        dst_max: [(x + w as f32).round() / rs, (y + h as f32).round() / rs],
-                        uv_min:  cached.uv_min,
-                        uv_max:  cached.uv_max,
-                        color,
-                    },
-                    *clip,
-                ));
             }
+
+            let Some(cached) = self.glyph_cache.get(&key) else { continue };
+
+            // Round to integer physical pixels first, then divide to
+            // logical.  This ensures each quad edge aligns to an exact
+            // screen pixel, preventing the nearest-neighbour sampler from
+            // straddling a texel boundary and producing a 1px fringe.
+            let rs = raster_scale;
+            self.instances.push(GlyphInstance {
+                dst_min: [x.round() / rs,              y.round() / rs],
+                dst_max: [(x + w as f32).round() / rs, (y + h as f32).round() / rs],
+                uv_min:  cached.uv_min,
+                uv_max:  cached.uv_max,
+                color,
+            });
         }
+    }
 
-        if instances.is_empty() {
+    /// Rebuilds bindings if the atlas changed, then uploads staged instances
+    /// and the viewport uniform.
+    pub(in crate::render) fn upload(&mut self, ctx: &RenderCtx<'_>) {
+        if self.instances.is_empty() {
+            self.instance_vbo = None;
             return;
         }
-
-        // ── mutable operations before any immutable borrows ────────────────
         self.ensure_bindings(ctx);
         self.write_viewport_uniform(ctx);
-
-        let raw: Vec<GlyphInstance> = instances.iter().map(|(inst, _)| *inst).collect();
-        let instance_vbo = ctx.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        self.instance_vbo = Some(ctx.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: Some("marduk text instance vbo"),
-            contents: bytemuck::cast_slice(&raw),
+            contents: bytemuck::cast_slice(&self.instances),
             usage: wgpu::BufferUsages::VERTEX,
-        });
-
-        // ── immutable borrows ──────────────────────────────────────────────
-        let Some(pipeline)   = self.pipeline.as_ref()   else { return; };
-        let Some(bind_group) = self.bind_group.as_ref() else { return; };
-        let Some(quad_vbo)   = self.quad_vbo.as_ref()   else { return; };
-        let Some(quad_ibo)   = self.quad_ibo.as_ref()   else { return; };
-
-        let mut rpass = target.encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-            label: Some("marduk text pass"),
-            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                view: target.color_view,
-                resolve_target: None,
-                ops: wgpu::Operations {
-                    load: wgpu::LoadOp::Load,
-                    store: wgpu::StoreOp::Store,
-                },
-                depth_slice: None,
-            })],
-            depth_stencil_attachment: None,
-            timestamp_writes: None,
-            occlusion_query_set: None,
-            multiview_mask: None,
-        });
+        }));
+    }
 
+    /// Binds pipeline, atlas and buffers on `rpass`. Returns `false` if nothing was uploaded.
+    pub(in crate::render) fn bind(&self, rpass: &mut wgpu::RenderPass<'_>) -> bool {
+        let (Some(pipeline), Some(bind_group), Some(quad_vbo), Some(quad_ibo), Some(instance_vbo)) = (
+            self.pipeline.as_ref(),
+            self.bind_group.as_ref(),
+            self.quad_vbo.as_ref(),
+            self.quad_ibo.as_ref(),
+            self.instance_vbo.as_ref(),
+        ) else {
+            return false;
+        };
         rpass.set_pipeline(pipeline);
         rpass.set_bind_group(0, bind_group, &[]);
         rpass.set_vertex_buffer(0, quad_vbo.slice(..));
         rpass.set_vertex_buffer(1, instance_vbo.slice(..));
         rpass.set_index_buffer(quad_ibo.slice(..), wgpu::IndexFormat::Uint16);
-
-        let mut i = 0u32;
-        while i < instances.len() as u32 {
-            let clip = instances[i as usize].1;
-            let mut j = i + 1;
-            while j < instances.len() as u32 && instances[j as usize].1 == clip {
-                j += 1;
-            }
-            if let Some((sx, sy, sw, sh)) =
-                logical_clip_to_scissor(clip, ctx.viewport, ctx.scale_factor)
-            {
-                rpass.set_scissor_rect(sx, sy, sw, sh);
-                rpass.draw_indexed(0..6, 0, i..j);
-            }
-            i = j;
-        }
+        true
     }
 
     // ── atlas helpers ──────────────────────────────────────────────────────
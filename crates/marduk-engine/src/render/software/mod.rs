@@ -0,0 +1,599 @@
+//! Pure-CPU rasterizer for `DrawList`s.
+//!
+//! [`SoftwareRenderer`] draws every `DrawCmd` variant into a [`Pixmap`] without
+//! touching wgpu, so scenes can be turned into pixels on machines with no GPU
+//! (CI, headless test runs).
+//!
+//! It is a reference implementation of the WGSL shaders in
+//! `render/shapes/shaders`: every fragment is evaluated at the physical pixel
+//! centre, converted to logical coordinates, and run through the same SDF,
+//! smoothstep, gradient and blend math. Results should match the GPU output
+//! to within rounding, with two known differences:
+//! - bilinear image sampling uses exact `f32` weights, whereas GPUs quantize
+//!   filter weights (typically to 8 bits);
+//! - the glyph atlas can fill up on the GPU, dropping glyphs; the software
+//!   glyph cache is unbounded.
+
+mod pixmap;
+
+pub use pixmap::Pixmap;
+
+use std::collections::HashMap;
+
+use fontdue::layout::{CoordinateSystem, GlyphRasterConfig, Layout, LayoutSettings, TextStyle};
+
+use crate::coords::{CornerRadii, Rect, Vec2, Viewport};
+use crate::image::{CpuImage, ImageStore};
+use crate::paint::Paint;
+use crate::scene::shapes::circle::CircleCmd;
+use crate::scene::shapes::image::ImageCmd;
+use crate::scene::shapes::rect::RectCmd;
+use crate::scene::shapes::rounded_rect::RoundedRectCmd;
+use crate::scene::shapes::text::TextCmd;
+use crate::scene::{DrawCmd, DrawList};
+use crate::text::FontSystem;
+
+use super::shapes::common::{logical_clip_to_scissor, resolve_paint};
+
+// ── cached glyph ──────────────────────────────────────────────────────────
+
+struct GlyphBitmap {
+    width: usize,
+    height: usize,
+    coverage: Vec<u8>,
+}
+
+// ── SoftwareRenderer ──────────────────────────────────────────────────────
+
+/// Rasterizes a `DrawList` into a [`Pixmap`] on the CPU.
+///
+/// Keeps a glyph cache across calls (keyed like the GPU `TextRenderer`'s), so
+/// reuse one renderer for repeated renders of similar scenes.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let mut pixmap = Pixmap::new(800, 600);
+/// pixmap.fill(Color::from_srgb(0.1, 0.1, 0.1, 1.0));
+/// SoftwareRenderer::new().render(&mut pixmap, 1.0, &mut draw_list, &fonts, &images);
+/// let rgba = pixmap.to_rgba8();
+/// ```
+pub struct SoftwareRenderer {
+    glyph_cache: HashMap<GlyphRasterConfig, GlyphBitmap>,
+    /// Quantized scale the cached glyphs were rasterized at (see `TextRenderer`).
+    raster_scale: f32,
+    layout: Layout<()>,
+
+    warned_non_solid: bool,
+    warned_multi_stop: bool,
+}
+
+impl Default for SoftwareRenderer {
+    fn default() -> Self {
+        Self {
+            glyph_cache: HashMap::new(),
+            raster_scale: 0.0,
+            layout: Layout::new(CoordinateSystem::PositiveYDown),
+            warned_non_solid: false,
+            warned_multi_stop: false,
+        }
+    }
+}
+
+impl SoftwareRenderer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Draws every item of `draw_list` into `target`, in paint order.
+    ///
+    /// `target` is in physical pixels; `scale_factor` maps logical draw-list
+    /// coordinates onto it, exactly like `RenderCtx::scale_factor`. The logical
+    /// viewport is therefore `target.size / scale_factor`.
+    ///
+    /// Existing pixels are composited over, not cleared — call
+    /// [`Pixmap::fill`] first for a background.
+    pub fn render(
+        &mut self,
+        target: &mut Pixmap,
+        scale_factor: f32,
+        draw_list: &mut DrawList,
+        font_system: &FontSystem,
+        images: &ImageStore,
+    ) {
+        let scale = scale_factor.max(f32::EPSILON);
+        let viewport = Viewport::new(target.width() as f32 / scale, target.height() as f32 / scale);
+
+        let raster_scale = (scale * 4.0).round() / 4.0;
+        if (raster_scale - self.raster_scale).abs() > 0.001 {
+            self.glyph_cache.clear();
+            self.raster_scale = raster_scale;
+        }
+
+        for item in draw_list.iter_in_paint_order() {
+            let Some(scissor) = logical_clip_to_scissor(item.clip_rect, viewport, scale) else {
+                continue;
+            };
+            let mut raster = Raster { target: &mut *target, scale, scissor };
+            match &item.cmd {
+                DrawCmd::Rect(cmd) => {
+                    if !matches!(cmd.paint, Paint::Solid(_)) && !self.warned_non_solid {
+                        log::debug!("SoftwareRenderer: non-solid Rect paint ignored (matches RectRenderer)");
+                        self.warned_non_solid = true;
+                    }
+                    draw_rect(&mut raster, cmd);
+                }
+                DrawCmd::RoundedRect(cmd) => {
+                    draw_rounded_rect(&mut raster, cmd, &mut self.warned_multi_stop);
+                }
+                DrawCmd::Circle(cmd) => {
+                    draw_circle(&mut raster, cmd, &mut self.warned_multi_stop);
+                }
+                DrawCmd::Image(cmd) => {
+                    if let Some(img) = images.get(cmd.image_id) {
+                        draw_image(&mut raster, cmd, img);
+                    }
+                }
+                DrawCmd::Text(cmd) => self.draw_text(&mut raster, cmd, font_system),
+            }
+        }
+    }
+
+    /// Mirrors `TextRenderer::push`: lays out at the quantized physical scale,
+    /// snaps glyph quads to physical pixels and samples coverage with nearest
+    /// filtering.
+    fn draw_text(&mut self, raster: &mut Raster<'_>, cmd: &TextCmd, font_system: &FontSystem) {
+        let Some(font) = font_system.get(cmd.font) else {
+            log::warn!("SoftwareRenderer: unknown FontId {:?}, skipping", cmd.font);
+            return;
+        };
+        let rs = self.raster_scale;
+        let color = [cmd.color.r, cmd.color.g, cmd.color.b, cmd.color.a];
+
+        self.layout.reset(&LayoutSettings {
+            x: (cmd.origin.x * rs).round(),
+            y: (cmd.origin.y * rs).round(),
+            max_width: cmd.max_width.map(|w| (w * rs).ceil()),
+            ..LayoutSettings::default()
+        });
+        self.layout.append(&[font], &TextStyle::new(&cmd.text, cmd.size * rs, 0));
+
+        for g in self.layout.glyphs() {
+            if !g.char_data.rasterize() || g.width == 0 || g.height == 0 {
+                continue;
+            }
+            let glyph = self.glyph_cache.entry(g.key).or_insert_with(|| {
+                let (metrics, coverage) = font.rasterize_config(g.key);
+                GlyphBitmap { width: metrics.width, height: metrics.height, coverage }
+            });
+            if glyph.width == 0 || glyph.height == 0 {
+                continue;
+            }
+
+            let dst_min = Vec2::new(g.x.round() / rs, g.y.round() / rs);
+            let dst_max = Vec2::new(
+                (g.x + g.width as f32).round() / rs,
+                (g.y + g.height as f32).round() / rs,
+            );
+            let size = Vec2::new(dst_max.x - dst_min.x, dst_max.y - dst_min.y);
+            if size.x <= 0.0 || size.y <= 0.0 {
+                continue;
+            }
+
+            raster.shade(dst_min, dst_max, |p| {
+                let tx = ((p.x - dst_min.x) / size.x * glyph.width as f32) as usize;
+                let ty = ((p.y - dst_min.y) / size.y * glyph.height as f32) as usize;
+                let tx = tx.min(glyph.width - 1);
+                let ty = ty.min(glyph.height - 1);
+                let coverage = glyph.coverage[ty * glyph.width + tx] as f32 / 255.0;
+                if coverage <= 0.0 {
+                    return None;
+                }
+                Some(scale4(color, coverage))
+            });
+        }
+    }
+}
+
+// ── raster helper ─────────────────────────────────────────────────────────
+
+/// A pixmap restricted to one scissor rect, shading in logical coordinates.
+struct Raster<'a> {
+    target: &'a mut Pixmap,
+    scale: f32,
+    /// Physical `(x, y, w, h)` from `logical_clip_to_scissor`.
+    scissor: (u32, u32, u32, u32),
+}
+
+impl Raster<'_> {
+    /// Runs `shade` for every physical pixel whose centre lies inside the
+    /// logical box `[min, max)` and the scissor, blending any returned
+    /// premultiplied color — the CPU analogue of rasterizing one quad.
+    fn shade(&mut self, min: Vec2, max: Vec2, mut shade: impl FnMut(Vec2) -> Option<[f32; 4]>) {
+        let (sx, sy, sw, sh) = self.scissor;
+        let s = self.scale;
+        // Pixel centre (i + 0.5) ∈ [min·s, max·s)  ⇔  i ∈ [ceil(min·s − 0.5), ceil(max·s − 0.5))
+        let span = |lo: f32, hi: f32, clip_lo: u32, clip_len: u32| {
+            let a = (lo * s - 0.5).ceil().max(clip_lo as f32) as u32;
+            let b = (hi * s - 0.5).ceil().min((clip_lo + clip_len) as f32).max(0.0) as u32;
+            a..b.max(a)
+        };
+        let xs = span(min.x, max.x, sx, sw);
+        let ys = span(min.y, max.y, sy, sh);
+
+        for y in ys {
+            for x in xs.clone() {
+                let p = Vec2::new((x as f32 + 0.5) / s, (y as f32 + 0.5) / s);
+                if let Some(src) = shade(p) {
+                    self.target.blend(x, y, src);
+                }
+            }
+        }
+    }
+}
+
+// ── shapes ────────────────────────────────────────────────────────────────
+
+/// `rect.wgsl`: hard-edged quad, solid paint only.
+fn draw_rect(raster: &mut Raster<'_>, cmd: &RectCmd) {
+    let Paint::Solid(c) = &cmd.paint else { return };
+    let r = cmd.rect.normalized();
+    if r.is_empty() {
+        return;
+    }
+    let color = [c.r, c.g, c.b, c.a];
+    raster.shade(r.origin, max_corner(r), |_| Some(color));
+}
+
+/// `rounded_rect.wgsl`: SDF fill with an inner-edge border ring.
+fn draw_rounded_rect(raster: &mut Raster<'_>, cmd: &RoundedRectCmd, warned_multi_stop: &mut bool) {
+    let r = cmd.rect.normalized();
+    if r.is_empty() {
+        return;
+    }
+    let (color0, color1, grad_p0, grad_p1) = resolve_paint(&cmd.paint, warned_multi_stop);
+    let (border_width, border_color) = match &cmd.border {
+        Some(b) => (b.width.max(0.0), [b.color.r, b.color.g, b.color.b, b.color.a]),
+        None => (0.0, [0.0f32; 4]),
+    };
+
+    let half = Vec2::new(r.size.x * 0.5, r.size.y * 0.5);
+    let center = Vec2::new(r.origin.x + half.x, r.origin.y + half.y);
+    let radii = clamp_radii(cmd.radii, half);
+
+    // Expand bounding quad by border + 1 px for AA fringe.
+    let e = border_width + 1.0;
+    let min = Vec2::new(r.origin.x - e, r.origin.y - e);
+    let max = Vec2::new(r.origin.x + r.size.x + e, r.origin.y + r.size.y + e);
+
+    raster.shade(min, max, |p| {
+        let dist = sd_rounded_box(Vec2::new(p.x - center.x, p.y - center.y), half, radii);
+        let fill = sample_fill(p, color0, color1, grad_p0, grad_p1);
+        ring(dist, border_width, fill, border_color)
+    });
+}
+
+/// `circle.wgsl`: same ring model as rounded rects with a radial distance.
+fn draw_circle(raster: &mut Raster<'_>, cmd: &CircleCmd, warned_multi_stop: &mut bool) {
+    if cmd.radius <= 0.0 {
+        return;
+    }
+    let (color0, color1, grad_p0, grad_p1) = resolve_paint(&cmd.paint, warned_multi_stop);
+    let (border_width, border_color) = match &cmd.border {
+        Some(b) => (b.width.max(0.0), [b.color.r, b.color.g, b.color.b, b.color.a]),
+        None => (0.0, [0.0f32; 4]),
+    };
+
+    let c = cmd.center;
+    let half_extent = cmd.radius + border_width + 1.0;
+    let min = Vec2::new(c.x - half_extent, c.y - half_extent);
+    let max = Vec2::new(c.x + half_extent, c.y + half_extent);
+
+    raster.shade(min, max, |p| {
+        let dx = p.x - c.x;
+        let dy = p.y - c.y;
+        let dist = (dx * dx + dy * dy).sqrt() - cmd.radius;
+        let fill = sample_fill(p, color0, color1, grad_p0, grad_p1);
+        ring(dist, border_width, fill, border_color)
+    });
+}
+
+/// `image.wgsl`: bilinear clamp-to-edge sample, straight tint, SDF corner mask.
+fn draw_image(raster: &mut Raster<'_>, cmd: &ImageCmd, img: &CpuImage) {
+    let r = cmd.rect.normalized();
+    if r.is_empty() || img.width == 0 || img.height == 0 {
+        return;
+    }
+    let half = Vec2::new(r.size.x * 0.5, r.size.y * 0.5);
+    let radii = clamp_radii(cmd.corner_radii, half);
+    let tint = cmd.tint;
+
+    let min = Vec2::new(r.origin.x - 1.0, r.origin.y - 1.0);
+    let max = Vec2::new(r.origin.x + r.size.x + 1.0, r.origin.y + r.size.y + 1.0);
+
+    raster.shade(min, max, |p| {
+        let local = Vec2::new(p.x - r.origin.x, p.y - r.origin.y);
+        let dist = sd_rounded_box(Vec2::new(local.x - half.x, local.y - half.y), half, radii);
+        let mask = smoothstep(0.5, -0.5, dist);
+        if mask <= 0.0 {
+            return None;
+        }
+        let uv = [
+            cmd.uv_min[0] + (cmd.uv_max[0] - cmd.uv_min[0]) * (local.x / r.size.x),
+            cmd.uv_min[1] + (cmd.uv_max[1] - cmd.uv_min[1]) * (local.y / r.size.y),
+        ];
+        let t = sample_bilinear(img, uv);
+        let color = [t[0] * tint[0], t[1] * tint[1], t[2] * tint[2], t[3] * tint[3]];
+        Some(scale4(color, mask))
+    });
+}
+
+// ── shader math (ported from WGSL) ────────────────────────────────────────
+
+/// WGSL `smoothstep(low, high, x)`; `low > high` gives a falling edge.
+#[inline]
+fn smoothstep(low: f32, high: f32, x: f32) -> f32 {
+    let t = ((x - low) / (high - low)).clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
+
+/// Signed distance to a rounded box centred at the origin (+Y down).
+/// `r` is `[tl, tr, br, bl]`.
+fn sd_rounded_box(p: Vec2, b: Vec2, r: [f32; 4]) -> f32 {
+    let corner_r = if p.x > 0.0 {
+        if p.y > 0.0 { r[2] } else { r[1] }
+    } else if p.y > 0.0 {
+        r[3]
+    } else {
+        r[0]
+    };
+    let qx = p.x.abs() - b.x + corner_r;
+    let qy = p.y.abs() - b.y + corner_r;
+    let outside = Vec2::new(qx.max(0.0), qy.max(0.0));
+    qx.max(qy).min(0.0) + (outside.x * outside.x + outside.y * outside.y).sqrt() - corner_r
+}
+
+/// Clamps radii so they never exceed the smallest half-dimension.
+fn clamp_radii(r: CornerRadii, half: Vec2) -> [f32; 4] {
+    let max_r = half.x.min(half.y).max(0.0);
+    [
+        r.top_left.min(max_r),
+        r.top_right.min(max_r),
+        r.bottom_right.min(max_r),
+        r.bottom_left.min(max_r),
+    ]
+}
+
+/// Shared fragment logic of the rounded-rect and circle shaders.
+fn ring(dist: f32, border_width: f32, fill: [f32; 4], border: [f32; 4]) -> Option<[f32; 4]> {
+    let shape_mask = smoothstep(0.5, -0.5, dist);
+    if shape_mask <= 0.0 {
+        return None;
+    }
+    let fill_mask = smoothstep(0.5, -0.5, dist + border_width);
+    let border_mask = shape_mask - fill_mask;
+    Some([
+        fill[0] * fill_mask + border[0] * border_mask,
+        fill[1] * fill_mask + border[1] * border_mask,
+        fill[2] * fill_mask + border[2] * border_mask,
+        fill[3] * fill_mask + border[3] * border_mask,
+    ])
+}
+
+/// Two-stop linear gradient; a degenerate axis returns `color0`.
+fn sample_fill(p: Vec2, color0: [f32; 4], color1: [f32; 4], p0: [f32; 2], p1: [f32; 2]) -> [f32; 4] {
+    let dir = [p1[0] - p0[0], p1[1] - p0[1]];
+    let len_sq = dir[0] * dir[0] + dir[1] * dir[1];
+    if len_sq < 0.0001 {
+        return color0;
+    }
+    let t = (((p.x - p0[0]) * dir[0] + (p.y - p0[1]) * dir[1]) / len_sq).clamp(0.0, 1.0);
+    [
+        color0[0] + (color1[0] - color0[0]) * t,
+        color0[1] + (color1[1] - color0[1]) * t,
+        color0[2] + (color1[2] - color0[2]) * t,
+        color0[3] + (color1[3] - color0[3]) * t,
+    ]
+}
+
+/// Bilinear, clamp-to-edge sample of a premultiplied RGBA8 image, as `[0, 1]` floats
+/// (the texture is `Rgba8Unorm`, so no sRGB decode happens on the GPU either).
+fn sample_bilinear(img: &CpuImage, uv: [f32; 2]) -> [f32; 4] {
+    let (w, h) = (img.width as i64, img.height as i64);
+    let x = uv[0] * w as f32 - 0.5;
+    let y = uv[1] * h as f32 - 0.5;
+    let (x0, y0) = (x.floor(), y.floor());
+    let (fx, fy) = (x - x0, y - y0);
+
+    let texel = |tx: i64, ty: i64| -> [f32; 4] {
+        let tx = tx.clamp(0, w - 1) as usize;
+        let ty = ty.clamp(0, h - 1) as usize;
+        let i = (ty * w as usize + tx) * 4;
+        match img.pixels.get(i..i + 4) {
+            Some(px) => [
+                px[0] as f32 / 255.0,
+                px[1] as f32 / 255.0,
+                px[2] as f32 / 255.0,
+                px[3] as f32 / 255.0,
+            ],
+            None => [0.0; 4],
+        }
+    };
+
+    let (x0, y0) = (x0 as i64, y0 as i64);
+    let (a, b) = (texel(x0, y0), texel(x0 + 1, y0));
+    let (c, d) = (texel(x0, y0 + 1), texel(x0 + 1, y0 + 1));
+    let mut out = [0.0; 4];
+    for i in 0..4 {
+        let top = a[i] + (b[i] - a[i]) * fx;
+        let bottom = c[i] + (d[i] - c[i]) * fx;
+        out[i] = top + (bottom - top) * fy;
+    }
+    out
+}
+
+#[inline]
+fn scale4(c: [f32; 4], k: f32) -> [f32; 4] {
+    [c[0] * k, c[1] * k, c[2] * k, c[3] * k]
+}
+
+#[inline]
+fn max_corner(r: Rect) -> Vec2 {
+    Vec2::new(r.origin.x + r.size.x, r.origin.y + r.size.y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::paint::Color;
+    use crate::scene::{Border, ZIndex};
+
+    fn render(w: u32, h: u32, scale: f32, dl: &mut DrawList) -> Pixmap {
+        render_with(w, h, scale, dl, &ImageStore::new())
+    }
+
+    fn render_with(w: u32, h: u32, scale: f32, dl: &mut DrawList, images: &ImageStore) -> Pixmap {
+        let mut pixmap = Pixmap::new(w, h);
+        SoftwareRenderer::new().render(&mut pixmap, scale, dl, &FontSystem::new(), images);
+        pixmap
+    }
+
+    fn approx(a: Color, b: Color) -> bool {
+        (a.r - b.r).abs() < 1e-3 && (a.g - b.g).abs() < 1e-3
+            && (a.b - b.b).abs() < 1e-3 && (a.a - b.a).abs() < 1e-3
+    }
+
+    #[test]
+    fn rect_covers_pixel_centres_inside() {
+        let red = Color::from_straight(1.0, 0.0, 0.0, 1.0);
+        let mut dl = DrawList::new();
+        dl.push_solid_rect(ZIndex(0), Rect::new(1.0, 1.0, 2.0, 2.0), red);
+        let px = render(4, 4, 1.0, &mut dl);
+        assert_eq!(px.pixel(1, 1), Some(red));
+        assert_eq!(px.pixel(2, 2), Some(red));
+        assert_eq!(px.pixel(0, 0), Some(Color::transparent()));
+        assert_eq!(px.pixel(3, 3), Some(Color::transparent()));
+    }
+
+    #[test]
+    fn scale_factor_maps_logical_to_physical() {
+        let red = Color::from_straight(1.0, 0.0, 0.0, 1.0);
+        let mut dl = DrawList::new();
+        dl.push_solid_rect(ZIndex(0), Rect::new(1.0, 0.0, 1.0, 1.0), red);
+        let px = render(4, 2, 2.0, &mut dl);
+        assert_eq!(px.pixel(1, 0), Some(Color::transparent()));
+        assert_eq!(px.pixel(2, 1), Some(red));
+        assert_eq!(px.pixel(3, 1), Some(red));
+    }
+
+    #[test]
+    fn clip_rect_restricts_drawing() {
+        let red = Color::from_straight(1.0, 0.0, 0.0, 1.0);
+        let mut dl = DrawList::new();
+        dl.push_clip(Rect::new(0.0, 0.0, 2.0, 4.0));
+        dl.push_solid_rect(ZIndex(0), Rect::new(0.0, 0.0, 4.0, 4.0), red);
+        dl.pop_clip();
+        let px = render(4, 4, 1.0, &mut dl);
+        assert_eq!(px.pixel(1, 3), Some(red));
+        assert_eq!(px.pixel(2, 0), Some(Color::transparent()));
+    }
+
+    #[test]
+    fn paint_order_and_premultiplied_blending() {
+        let mut dl = DrawList::new();
+        dl.push_solid_rect(ZIndex(1), Rect::new(0.0, 0.0, 1.0, 1.0), Color::from_straight(0.0, 0.0, 1.0, 0.5));
+        dl.push_solid_rect(ZIndex(0), Rect::new(0.0, 0.0, 1.0, 1.0), Color::from_straight(1.0, 0.0, 0.0, 1.0));
+        let px = render(1, 1, 1.0, &mut dl);
+        // 50 % blue over opaque red.
+        assert!(approx(px.pixel(0, 0).unwrap(), Color::from_premul(0.5, 0.0, 0.5, 1.0)));
+    }
+
+    #[test]
+    fn rounded_rect_corners_are_cut_and_border_drawn() {
+        let fill = Color::from_straight(1.0, 1.0, 1.0, 1.0);
+        let edge = Color::from_straight(0.0, 1.0, 0.0, 1.0);
+        let mut dl = DrawList::new();
+        dl.push_rounded_rect(
+            ZIndex(0),
+            Rect::new(0.0, 0.0, 20.0, 20.0),
+            CornerRadii::all(8.0),
+            Paint::Solid(fill),
+            Some(Border::new(2.0, edge)),
+        );
+        let px = render(20, 20, 1.0, &mut dl);
+        assert_eq!(px.pixel(0, 0), Some(Color::transparent()));
+        assert!(approx(px.pixel(10, 10).unwrap(), fill));
+        assert!(approx(px.pixel(10, 0).unwrap(), edge));
+    }
+
+    #[test]
+    fn circle_fills_inside_radius() {
+        let c = Color::from_straight(0.0, 0.0, 1.0, 1.0);
+        let mut dl = DrawList::new();
+        dl.push_circle(ZIndex(0), Vec2::new(5.0, 5.0), 4.0, Paint::Solid(c), None);
+        let px = render(10, 10, 1.0, &mut dl);
+        assert!(approx(px.pixel(5, 5).unwrap(), c));
+        assert_eq!(px.pixel(0, 0), Some(Color::transparent()));
+    }
+
+    #[test]
+    fn image_is_sampled_and_tinted() {
+        let mut images = ImageStore::new();
+        let id = images.insert(vec![255, 0, 0, 255], 1, 1);
+        let mut dl = DrawList::new();
+        dl.push_image(
+            ZIndex(0),
+            Rect::new(0.0, 0.0, 2.0, 2.0),
+            id,
+            [0.0, 0.0],
+            [1.0, 1.0],
+            [1.0, 1.0, 1.0, 0.5],
+            CornerRadii::all(0.0),
+        );
+        let px = render_with(2, 2, 1.0, &mut dl, &images);
+        assert!(approx(px.pixel(1, 1).unwrap(), Color::from_premul(1.0, 0.0, 0.0, 0.5)));
+    }
+
+    #[test]
+    fn text_is_rasterized_inside_its_bounds() {
+        let mut fonts = FontSystem::new();
+        let font = fonts
+            .load_font(include_bytes!("../../../../../assets/fonts/Tuffy.ttf"))
+            .unwrap();
+        let white = Color::from_straight(1.0, 1.0, 1.0, 1.0);
+        let origin = Vec2::new(4.0, 4.0);
+        let size = fonts.measure_text("Hi", font, 16.0, None);
+
+        let mut dl = DrawList::new();
+        dl.push_text(ZIndex(0), "Hi", font, 16.0, white, origin, None);
+        let mut px = Pixmap::new(48, 32);
+        SoftwareRenderer::new().render(&mut px, 1.0, &mut dl, &fonts, &ImageStore::new());
+
+        // One pixel of slack for glyph quads snapped to the pixel grid.
+        let inside = |x: u32, y: u32| {
+            let (x, y) = (x as f32, y as f32);
+            x >= origin.x - 1.0 && x < origin.x + size.x + 1.0
+                && y >= origin.y - 1.0 && y < origin.y + size.y + 1.0
+        };
+        let mut covered = 0;
+        for y in 0..px.height() {
+            for x in 0..px.width() {
+                let a = px.pixel(x, y).unwrap().a;
+                if inside(x, y) {
+                    covered += usize::from(a > 0.0);
+                } else {
+                    assert_eq!(a, 0.0, "coverage outside the text bounds at ({x}, {y})");
+                }
+            }
+        }
+        assert!(covered > 10, "only {covered} pixels of text coverage");
+    }
+
+    #[test]
+    fn rgba8_encodes_srgb_straight_alpha() {
+        let mut px = Pixmap::new(1, 1);
+        px.fill(Color::from_straight(0.5, 0.0, 1.0, 0.5));
+        assert_eq!(px.to_rgba8(), vec![188, 0, 255, 128]);
+        assert_eq!(px.to_rgba8_premul(), vec![137, 0, 188, 128]);
+    }
+}
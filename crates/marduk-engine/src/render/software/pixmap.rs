@@ -0,0 +1,120 @@
+use crate::paint::Color;
+
+// ── Pixmap ────────────────────────────────────────────────────────────────
+
+/// CPU render target: a grid of linear premultiplied RGBA pixels.
+///
+/// Pixels are kept as `f32` while drawing so blending happens in linear space,
+/// exactly like the GPU path writing to an sRGB surface. Convert to bytes with
+/// [`to_rgba8`](Self::to_rgba8) (straight alpha, PNG-ready) or
+/// [`to_rgba8_premul`](Self::to_rgba8_premul) (what an `Rgba8UnormSrgb`
+/// texture stores).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pixmap {
+    width: u32,
+    height: u32,
+    pixels: Vec<Color>,
+}
+
+impl Pixmap {
+    /// Creates a fully transparent pixmap of `width × height` physical pixels.
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![Color::transparent(); (width as usize) * (height as usize)],
+        }
+    }
+
+    #[inline]
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    #[inline]
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Overwrites every pixel with `color` (the equivalent of `LoadOp::Clear`).
+    pub fn fill(&mut self, color: Color) {
+        self.pixels.fill(color);
+    }
+
+    /// Returns the pixel at `(x, y)`, or `None` when out of bounds.
+    #[inline]
+    pub fn pixel(&self, x: u32, y: u32) -> Option<Color> {
+        if x < self.width && y < self.height {
+            Some(self.pixels[(y * self.width + x) as usize])
+        } else {
+            None
+        }
+    }
+
+    /// Composites premultiplied `src` over the pixel at `(x, y)`.
+    ///
+    /// Uses the same blend equation as the GPU pipelines:
+    /// `dst = src + dst * (1 - src.a)` for both color and alpha.
+    #[inline]
+    pub(crate) fn blend(&mut self, x: u32, y: u32, src: [f32; 4]) {
+        let dst = &mut self.pixels[(y * self.width + x) as usize];
+        let k = 1.0 - src[3];
+        dst.r = src[0] + dst.r * k;
+        dst.g = src[1] + dst.g * k;
+        dst.b = src[2] + dst.b * k;
+        dst.a = src[3] + dst.a * k;
+    }
+
+    /// Encodes as sRGB RGBA8 with straight (un-premultiplied) alpha.
+    ///
+    /// This is the layout image encoders such as PNG expect.
+    pub fn to_rgba8(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(self.pixels.len() * 4);
+        for c in &self.pixels {
+            let (r, g, b, a) = c.clamped().to_straight();
+            out.extend_from_slice(&[
+                linear_to_srgb_u8(r),
+                linear_to_srgb_u8(g),
+                linear_to_srgb_u8(b),
+                unorm_u8(a),
+            ]);
+        }
+        out
+    }
+
+    /// Encodes as sRGB RGBA8 with premultiplied alpha.
+    ///
+    /// Byte-for-byte what an `Rgba8UnormSrgb` render target stores for the same
+    /// linear premultiplied values, so it can be compared against GPU readback.
+    pub fn to_rgba8_premul(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(self.pixels.len() * 4);
+        for c in &self.pixels {
+            let c = c.clamped();
+            out.extend_from_slice(&[
+                linear_to_srgb_u8(c.r),
+                linear_to_srgb_u8(c.g),
+                linear_to_srgb_u8(c.b),
+                unorm_u8(c.a),
+            ]);
+        }
+        out
+    }
+}
+
+// ── encoding ──────────────────────────────────────────────────────────────
+
+/// Linear `[0, 1]` → sRGB-encoded byte (the transfer an `*Srgb` format applies on store).
+pub(crate) fn linear_to_srgb_u8(v: f32) -> u8 {
+    let v = v.clamp(0.0, 1.0);
+    let s = if v <= 0.003_130_8 {
+        v * 12.92
+    } else {
+        1.055 * v.powf(1.0 / 2.4) - 0.055
+    };
+    unorm_u8(s)
+}
+
+#[inline]
+fn unorm_u8(v: f32) -> u8 {
+    (v.clamp(0.0, 1.0) * 255.0 + 0.5) as u8
+}
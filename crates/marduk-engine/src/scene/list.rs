@@ -42,8 +42,8 @@ pub struct DrawList {
     /// Optional z-range filter applied by [`iter_in_paint_order`].
     ///
     /// When `Some((min, max))`, only items with `z ∈ [min, max]` are yielded.
-    /// Set via [`set_z_range`] / [`reset_z_range`] from the render loop to implement
-    /// two-pass rendering (normal content first, overlay content second).
+    /// Set via [`set_z_range`] / [`reset_z_range`] from the render loop to draw
+    /// only a slice of the z-order (e.g. overlays) with a given renderer.
     z_filter: Option<(i32, i32)>,
 }
 
@@ -138,7 +138,7 @@ impl DrawList {
 
     /// Restrict [`iter_in_paint_order`] to items with z ∈ `[min_z, max_z]` (inclusive).
     ///
-    /// Call this before invoking renderers to implement two-pass overlay rendering.
+    /// Call this before invoking renderers to draw only part of the z-order.
     /// Reset with [`reset_z_range`] after the pass is complete.
     #[inline]
     pub fn set_z_range(&mut self, min_z: i32, max_z: i32) {
@@ -165,10 +165,10 @@ impl DrawList {
         let items = &self.items;
         self.sorted_indices.iter().filter_map(move |&i| {
             let item = &items[i];
-            if let Some((min_z, max_z)) = z_filter {
-                if item.key.z.0 < min_z || item.key.z.0 > max_z {
-                    return None;
-                }
+            if let Some((min_z, max_z)) = z_filter
+                && (item.key.z.0 < min_z || item.key.z.0 > max_z)
+            {
+                return None;
             }
             Some(item)
         })
@@ -19,6 +19,7 @@ pub struct TextCmd {
 
 impl DrawList {
     /// Records a text draw command.
+    #[allow(clippy::too_many_arguments)]
     pub fn push_text(
         &mut self,
         z: ZIndex,
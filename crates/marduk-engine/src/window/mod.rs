@@ -2,7 +2,9 @@
 //!
 //! Owns the `winit` EventLoop and Window, and wires them to the GPU layer.
 
+mod proxy;
 mod runtime;
 
+pub use proxy::Proxy;
 pub use runtime::{Runtime, RuntimeConfig, RuntimeCtx, WindowMode};
 pub use winit::window::CursorIcon;
\ No newline at end of file
@@ -0,0 +1,47 @@
+use std::sync::{Arc, Mutex};
+
+use winit::event_loop::EventLoopProxy;
+
+/// User event the runtime's event loop carries: "something outside the
+/// event loop changed, draw again".
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Wake;
+
+/// Thread-safe handle that wakes the runtime from any thread.
+///
+/// The runtime sleeps until input arrives or a requested deadline passes;
+/// a background thread that changed what the UI shows calls [`wake`] so
+/// every window draws a new frame and the app sees [`App::on_wake`].
+///
+/// A proxy can be created before the event loop exists and handed to
+/// threads up front: wakes are dropped until [`Runtime::run_with_proxy`]
+/// connects it, and again after the loop has exited.
+///
+/// [`wake`]: Proxy::wake
+/// [`App::on_wake`]: crate::core::App::on_wake
+/// [`Runtime::run_with_proxy`]: super::Runtime::run_with_proxy
+#[derive(Debug, Clone, Default)]
+pub struct Proxy {
+    event_loop: Arc<Mutex<Option<EventLoopProxy<Wake>>>>,
+}
+
+impl Proxy {
+    /// A proxy not yet connected to an event loop.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Wake the event loop. Returns `false` when no loop is running.
+    pub fn wake(&self) -> bool {
+        let event_loop = self.event_loop.lock().unwrap_or_else(|e| e.into_inner());
+        event_loop.as_ref().is_some_and(|p| p.send_event(Wake).is_ok())
+    }
+
+    pub(crate) fn connect(&self, proxy: EventLoopProxy<Wake>) {
+        *self.event_loop.lock().unwrap_or_else(|e| e.into_inner()) = Some(proxy);
+    }
+
+    pub(crate) fn disconnect(&self) {
+        *self.event_loop.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+}
@@ -1,6 +1,7 @@
 use anyhow::{Context, Result};
 use ouroboros::self_referencing;
 use std::collections::HashMap;
+use std::time::Instant;
 
 use winit::application::ApplicationHandler;
 use winit::dpi::LogicalSize;
@@ -14,6 +15,8 @@ use crate::input::{InputEvent, InputFrame, InputState};
 use crate::input::platform::winit as input_winit;
 use crate::time::FrameClock;
 
+use super::proxy::{Proxy, Wake};
+
 /// How the initial window should be presented.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum WindowMode {
@@ -64,11 +67,21 @@ impl RuntimeCtx {
     pub fn exit(&mut self) {
         self.commands.push(Command::Exit);
     }
+
+    /// Schedules a frame for window `id` at `at`, even if no input arrives.
+    ///
+    /// The runtime sleeps until the earliest requested time. A deadline is
+    /// dropped once the window draws a frame, so keep requesting it from
+    /// `on_frame` while it is still wanted (e.g. a tooltip delay).
+    pub fn request_redraw_at(&mut self, id: WindowId, at: Instant) {
+        self.commands.push(Command::RedrawAt(id, at));
+    }
 }
 
 enum Command {
     CreateWindow(RuntimeConfig),
     CloseWindow(WindowId),
+    RedrawAt(WindowId, Instant),
     Exit,
 }
 
@@ -80,14 +93,26 @@ impl Runtime {
     where
         A: 'static + CoreApp,
     {
-        let event_loop = EventLoop::new().context("failed to create winit EventLoop")?;
+        Self::run_with_proxy(initial, gpu_init, app, &Proxy::new())
+    }
+
+    /// Like [`run`](Self::run), connecting `proxy` to the event loop so other
+    /// threads can wake it while it runs.
+    pub fn run_with_proxy<A>(initial: RuntimeConfig, gpu_init: GpuInit, app: A, proxy: &Proxy) -> Result<()>
+    where
+        A: 'static + CoreApp,
+    {
+        let event_loop = EventLoop::<Wake>::with_user_event()
+            .build()
+            .context("failed to create winit EventLoop")?;
+        proxy.connect(event_loop.create_proxy());
         let mut state = AppState::new(initial, gpu_init, app);
 
-        event_loop
+        let result = event_loop
             .run_app(&mut state)
-            .context("winit event loop terminated with error")?;
-
-        Ok(())
+            .context("winit event loop terminated with error");
+        proxy.disconnect();
+        result
     }
 }
 
@@ -113,6 +138,8 @@ where
     app: A,
 
     windows: HashMap<WindowId, WindowEntry>,
+    /// Earliest requested frame time per window; see [`RuntimeCtx::request_redraw_at`].
+    redraw_at: HashMap<WindowId, Instant>,
     exit_requested: bool,
 }
 
@@ -126,6 +153,7 @@ where
             gpu_init,
             app,
             windows: HashMap::new(),
+            redraw_at: HashMap::new(),
             exit_requested: false,
         }
     }
@@ -191,6 +219,7 @@ where
 
     fn destroy_window_entry(&mut self, id: WindowId) {
         self.windows.remove(&id);
+        self.redraw_at.remove(&id);
     }
 
     fn apply_commands(&mut self, event_loop: &ActiveEventLoop, mut ctx: RuntimeCtx) {
@@ -203,6 +232,10 @@ where
                     }
                 }
                 Command::CloseWindow(id) => self.destroy_window_entry(id),
+                Command::RedrawAt(id, at) => {
+                    let due = self.redraw_at.entry(id).or_insert(at);
+                    *due = (*due).min(at);
+                }
                 Command::Exit => self.request_exit(),
             }
         }
@@ -217,7 +250,7 @@ where
     }
 }
 
-impl<A> ApplicationHandler for AppState<A>
+impl<A> ApplicationHandler<Wake> for AppState<A>
 where
     A: CoreApp + 'static,
 {
@@ -325,6 +358,8 @@ where
             WindowEvent::RedrawRequested => {
                 let mut runtime_ctx = RuntimeCtx::default();
                 let mut app_control = AppControl::Continue;
+                // This frame serves any pending deadline; the app asks again if needed.
+                self.redraw_at.remove(&window_id);
 
                 if let Some(entry) = self.windows.get_mut(&window_id) {
                     entry.with_mut(|fields| {
@@ -377,10 +412,10 @@ where
                 // Any event that visually changes the UI (cursor move, click,
                 // scroll, key, text) needs a repaint. Modifier/focus changes
                 // are filtered out by the `had_visual_input` flag above.
-                if had_visual_input {
-                    if let Some(entry) = self.windows.get(&window_id) {
-                        entry.with_window(|w| w.request_redraw());
-                    }
+                if had_visual_input
+                    && let Some(entry) = self.windows.get(&window_id)
+                {
+                    entry.with_window(|w| w.request_redraw());
                 }
             }
         }
@@ -390,16 +425,40 @@ where
         }
     }
 
+    fn user_event(&mut self, _event_loop: &ActiveEventLoop, _event: Wake) {
+        self.app.on_wake();
+        for entry in self.windows.values() {
+            entry.with_window(|w| w.request_redraw());
+        }
+    }
+
     fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
         if self.exit_requested {
             event_loop.exit();
             return;
         }
 
-        // Sleep until the next OS event (mouse move, key press, resize, …).
-        // Redraws are requested explicitly: once on window creation, and again
-        // on Resized / ScaleFactorChanged. This keeps CPU/GPU usage near zero
-        // while the window is idle.
-        event_loop.set_control_flow(ControlFlow::Wait);
+        // Deadlines that have passed become redraws now.
+        let now = Instant::now();
+        let windows = &self.windows;
+        self.redraw_at.retain(|id, at| {
+            if *at > now {
+                return true;
+            }
+            if let Some(entry) = windows.get(id) {
+                entry.with_window(|w| w.request_redraw());
+            }
+            false
+        });
+
+        // Sleep until the next OS event (mouse move, key press, resize, …),
+        // proxy wake, or requested deadline. Redraws are requested explicitly:
+        // once on window creation, again on Resized / ScaleFactorChanged and
+        // input, and whenever the app asks for one. This keeps CPU/GPU usage
+        // near zero while the window is idle.
+        match self.redraw_at.values().min() {
+            Some(&at) => event_loop.set_control_flow(ControlFlow::WaitUntil(at)),
+            None => event_loop.set_control_flow(ControlFlow::Wait),
+        }
     }
 }
\ No newline at end of file
@@ -0,0 +1,252 @@
+//! Code actions: quick fixes for [`check`] issues, and small refactors.
+
+use std::collections::HashMap;
+
+use marduk_mkml::ast::{Control, DslDocument, Node};
+use marduk_mkml::check::{check, Issue};
+use marduk_mkml::lexer::{Lexer, Token};
+use marduk_mkml::schema::Schema;
+use tower_lsp::lsp_types::{
+    CodeAction, CodeActionKind, CodeActionOrCommand, Position, Range, TextEdit, Url, WorkspaceEdit,
+};
+
+use crate::backend::{issue_diagnostic, span_range};
+use crate::navigate::offset_at;
+
+/// Every action that applies to `range` of the document at `uri`.
+///
+/// `files` are the workspace's `.mkml` files, candidates for a missing
+/// `import`.
+pub fn code_actions(
+    uri: &Url,
+    text: &str,
+    schema: &Schema,
+    range: Range,
+    files: &[Url],
+) -> Vec<CodeActionOrCommand> {
+    let start = offset_at(text, &range.start);
+    let end = offset_at(text, &range.end);
+    let mut actions = color_fixes(uri, text, start, end);
+    if let Ok(doc) = marduk_mkml::parse_str(text) {
+        // An empty selection still touches the name the cursor is on.
+        let touched = |issue: &&Issue| issue.span.start.offset <= end && start <= issue.span.end.offset;
+        for issue in check(&doc, schema, &[]).iter().filter(touched) {
+            actions.extend(replacement_fix(uri, text, issue));
+            actions.extend(import_fixes(uri, text, &doc, issue, files));
+        }
+        actions.extend(wrap_actions(uri, text, &doc, start, end));
+    }
+    actions.into_iter().map(CodeActionOrCommand::CodeAction).collect()
+}
+
+// ── Quick fixes ───────────────────────────────────────────────────────────────
+
+/// Writes the issue's suggested replacement over it.
+fn replacement_fix(uri: &Url, text: &str, issue: &Issue) -> Option<CodeAction> {
+    let replacement = issue.replacement.as_ref()?;
+    let edit = TextEdit::new(span_range(text, issue.span), replacement.clone());
+    Some(CodeAction {
+        title: format!("Replace with `{replacement}`"),
+        diagnostics: Some(vec![issue_diagnostic(text, issue)]),
+        is_preferred: Some(true),
+        ..action(uri, edit, CodeActionKind::QUICKFIX)
+    })
+}
+
+/// `import "…" as Name` for an unknown capitalised widget named like a
+/// workspace file: `Sidebar` ↔ `sidebar.mkml`.
+fn import_fixes(uri: &Url, text: &str, doc: &DslDocument, issue: &Issue, files: &[Url]) -> Vec<CodeAction> {
+    let Some(name) = issue.message.strip_prefix("unknown widget `").and_then(|m| m.split('`').next()) else {
+        return vec![];
+    };
+    if !name.starts_with(|c: char| c.is_uppercase()) {
+        return vec![];
+    }
+    let at = doc.imports.last().map_or(Position::new(0, 0), |i| Position::new(i.span.end.line as u32, 0));
+    files
+        .iter()
+        .filter(|f| *f != uri && stem(f).is_some_and(|s| s.eq_ignore_ascii_case(name)))
+        .filter_map(|f| relative(uri, f))
+        .map(|path| {
+            let edit = TextEdit::new(Range::new(at, at), format!("import \"{path}\" as {name}\n"));
+            CodeAction {
+                title: format!("Import `{path}` as `{name}`"),
+                diagnostics: Some(vec![issue_diagnostic(text, issue)]),
+                ..action(uri, edit, CodeActionKind::QUICKFIX)
+            }
+        })
+        .collect()
+}
+
+/// `#rrggbb` → `#rrggbbff`: the canonical form carries its alpha.
+fn color_fixes(uri: &Url, text: &str, start: usize, end: usize) -> Vec<CodeAction> {
+    let Ok(tokens) = Lexer::new(text).tokenize() else { return vec![] };
+    tokens
+        .iter()
+        .filter(|t| matches!(t.token, Token::Color(_)))
+        .filter(|t| t.span.start.offset <= end && start <= t.span.end.offset)
+        .filter(|t| t.span.end.offset - t.span.start.offset == 7)
+        .map(|t| {
+            let short = &text[t.span.start.offset..t.span.end.offset];
+            let edit = TextEdit::new(span_range(text, t.span), format!("{short}ff"));
+            CodeAction {
+                title: format!("Write `{short}` as `{short}ff`"),
+                ..action(uri, edit, CodeActionKind::QUICKFIX)
+            }
+        })
+        .collect()
+}
+
+// ── Refactors ─────────────────────────────────────────────────────────────────
+
+/// Wraps the sibling widgets on the selected lines in a `Column` or `Row`.
+fn wrap_actions(uri: &Url, text: &str, doc: &DslDocument, start: usize, end: usize) -> Vec<CodeAction> {
+    if start == end {
+        return vec![];
+    }
+    let from = text[..start].rfind('\n').map_or(0, |i| i + 1);
+    let to = text[end..].find('\n').map_or(text.len(), |i| end + i);
+    let Some(nodes) = selected(&doc.root, from, to) else { return vec![] };
+    let (first, last) = (&nodes[0], &nodes[nodes.len() - 1]);
+
+    let line_start = text[..first.span.start.offset].rfind('\n').map_or(0, |i| i + 1);
+    let indent: String = text[line_start..].chars().take_while(|c| *c == ' ' || *c == '\t').collect();
+    let body: Vec<String> = text[line_start..last.span.end.offset]
+        .lines()
+        .map(|l| if l.trim().is_empty() { String::new() } else { format!("    {l}") })
+        .collect();
+    let range = Range::new(Position::new(first.span.start.line as u32 - 1, 0), span_range(text, last.span).end);
+
+    ["Column", "Row"]
+        .into_iter()
+        .map(|container| {
+            let new_text = format!("{indent}{container} {{\n{}\n{indent}}}", body.join("\n"));
+            CodeAction {
+                title: format!("Wrap in `{container} {{ }}`"),
+                ..action(uri, TextEdit::new(range, new_text), CodeActionKind::REFACTOR)
+            }
+        })
+        .collect()
+}
+
+/// The deepest run of sibling nodes lying within `from..to`.
+fn selected(node: &Node, from: usize, to: usize) -> Option<&[Node]> {
+    let otherwise: &[Node] = match &node.control {
+        Some(Control::If { otherwise, .. }) => otherwise,
+        _ => &[],
+    };
+    for list in [&node.children[..], otherwise] {
+        let inside = |n: &Node| from <= n.span.start.offset && n.span.end.offset <= to;
+        if let Some(deeper) = list
+            .iter()
+            .filter(|n| n.span.start.offset <= from && to <= n.span.end.offset)
+            .find_map(|n| selected(n, from, to))
+        {
+            return Some(deeper);
+        }
+        if let Some(first) = list.iter().position(inside) {
+            let count = list[first..].iter().take_while(|n| inside(n)).count();
+            return Some(&list[first..first + count]);
+        }
+    }
+    None
+}
+
+// ── Helpers ───────────────────────────────────────────────────────────────────
+
+fn action(uri: &Url, edit: TextEdit, kind: CodeActionKind) -> CodeAction {
+    CodeAction {
+        title: String::new(),
+        kind: Some(kind),
+        edit: Some(WorkspaceEdit::new(HashMap::from([(uri.clone(), vec![edit])]))),
+        ..Default::default()
+    }
+}
+
+fn stem(uri: &Url) -> Option<&str> {
+    uri.path_segments()?.next_back()?.strip_suffix(".mkml")
+}
+
+/// `to` as an import path from the file `from`: `../parts/side.mkml`.
+fn relative(from: &Url, to: &Url) -> Option<String> {
+    if from.scheme() != to.scheme() || from.host() != to.host() {
+        return None;
+    }
+    let mut dir: Vec<&str> = from.path_segments()?.collect();
+    dir.pop();
+    let target: Vec<&str> = to.path_segments()?.collect();
+    let common = dir.iter().zip(&target).take_while(|(a, b)| a == b).count();
+    let mut parts = vec![".."; dir.len() - common];
+    parts.extend(&target[common..]);
+    Some(parts.join("/"))
+}
+
+// ── tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn uri(path: &str) -> Url {
+        Url::parse(&format!("file:///w/{path}")).unwrap()
+    }
+
+    /// `(title, new text)` of each action at `needle` (or over `needle..until`).
+    fn actions(src: &str, needle: &str, until: Option<&str>, files: &[Url]) -> Vec<(String, String)> {
+        let pos = |offset: usize| {
+            let line = src[..offset].matches('\n').count();
+            let col = src[..offset].rsplit('\n').next().unwrap().encode_utf16().count();
+            Position::new(line as u32, col as u32)
+        };
+        let start = src.find(needle).unwrap();
+        let end = until.map_or(start, |u| src.find(u).unwrap() + u.len());
+        code_actions(&uri("ui/main.mkml"), src, &Schema::new(), Range::new(pos(start), pos(end)), files)
+            .into_iter()
+            .map(|a| match a {
+                CodeActionOrCommand::CodeAction(a) => {
+                    let edits = a.edit.unwrap().changes.unwrap().into_values().next().unwrap();
+                    (a.title, edits[0].new_text.clone())
+                }
+                CodeActionOrCommand::Command(_) => unreachable!(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn typo_and_alias_fixes() {
+        assert_eq!(actions("Row { corner_radus: 2 }", "corner", None, &[]), [(
+            "Replace with `corner_radius`".to_string(),
+            "corner_radius".to_string(),
+        )]);
+        assert_eq!(actions("Row { spacing: 4 }", "spacing", None, &[])[0].1, "gap");
+    }
+
+    #[test]
+    fn short_colors_gain_alpha() {
+        assert_eq!(actions("Row { bg: #1E1E1E }", "#", None, &[]), [(
+            "Write `#1E1E1E` as `#1E1E1Eff`".to_string(),
+            "#1E1E1Eff".to_string(),
+        )]);
+    }
+
+    #[test]
+    fn imports_a_matching_file() {
+        let files = [uri("ui/main.mkml"), uri("ui/parts/sidebar.mkml"), uri("lib/sidebar.mkml")];
+        let found = actions("Row { Sidebar { } }", "Sidebar", None, &files);
+        assert_eq!(found, [
+            ("Import `parts/sidebar.mkml` as `Sidebar`".to_string(), "import \"parts/sidebar.mkml\" as Sidebar\n".to_string()),
+            ("Import `../lib/sidebar.mkml` as `Sidebar`".to_string(), "import \"../lib/sidebar.mkml\" as Sidebar\n".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn wraps_selected_siblings() {
+        let src = "Column {\n    Text \"a\"\n    Text \"b\" {\n        size: 2\n    }\n    Text \"c\"\n}";
+        let found = actions(src, "Text \"a\"", Some("size: 2\n    }"), &[]);
+        assert_eq!(found[0], (
+            "Wrap in `Column { }`".to_string(),
+            "    Column {\n        Text \"a\"\n        Text \"b\" {\n            size: 2\n        }\n    }".to_string(),
+        ));
+        assert_eq!(found[1].0, "Wrap in `Row { }`");
+    }
+}
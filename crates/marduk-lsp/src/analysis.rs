@@ -6,6 +6,8 @@
 
 use tower_lsp::lsp_types::Position;
 
+use crate::navigate::column_offset;
+
 // ── Context kind ──────────────────────────────────────────────────────────────
 
 /// What the cursor is positioned inside, used to drive completions.
@@ -30,7 +32,7 @@ pub enum Context {
 /// Returns a sub-slice of the line, so the lifetime is tied to `text`.
 pub fn word_at<'t>(text: &'t str, pos: &Position) -> Option<&'t str> {
     let line = text.lines().nth(pos.line as usize)?;
-    let col = (pos.character as usize).min(line.len());
+    let col = column_offset(line, pos.character);
 
     let start = line[..col]
         .rfind(|c: char| !c.is_alphanumeric() && c != '_')
@@ -90,11 +92,11 @@ fn widget_name_on_line(line: &str) -> Option<String> {
 /// Classify the cursor position for completion.
 pub fn completion_context(text: &str, pos: &Position) -> Context {
     let line_idx = pos.line as usize;
-    let col = pos.character as usize;
 
     let lines: Vec<&str> = text.lines().collect();
     let current_line = lines.get(line_idx).copied().unwrap_or("");
-    let before_cursor = &current_line[..col.min(current_line.len())];
+    let col = column_offset(current_line, pos.character);
+    let before_cursor = &current_line[..col];
     let effective = strip_comment(before_cursor);
 
     // After a colon → we're completing a value.
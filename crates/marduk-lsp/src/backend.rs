@@ -1,37 +1,137 @@
-//! LSP backend: document store, diagnostics, hover, and completion.
+//! LSP backend: document store, diagnostics, hover, completion,
+//! navigation, formatting, outline, and semantic highlighting.
 
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::path::PathBuf;
+use std::sync::{Arc, OnceLock};
 
 use tokio::sync::RwLock;
 use tower_lsp::jsonrpc::Result;
 use tower_lsp::lsp_types::*;
 use tower_lsp::{Client, LanguageServer};
 
+use crate::actions::code_actions;
 use crate::analysis::{completion_context, find_enclosing_widget, word_at, Context};
-use crate::knowledge::{prop_in_widget, widget_by_name, PropKind, WIDGETS};
+use crate::colors::{document_colors, presentations};
+use crate::hover::{color_at, color_doc, component_doc, prop_at, prop_doc, widget_doc};
+use crate::navigate::{
+    column_offset, import_of, is_valid_name, occurrences, offset_at, position_at, same_symbol, symbol_at,
+    Symbol,
+};
+use crate::outline::{document_symbols, folding_ranges};
+use crate::semantic;
+use crate::widgets;
+use crate::workspace::Workspace;
+use marduk_mkml::ast::Span;
+use marduk_mkml::DslDocument;
+use marduk_mkml::check::{check, Issue, Severity};
+use marduk_mkml::format::{format_range, format_str, FormatOptions};
+use marduk_mkml::schema::{PropKind, Schema};
 
 // ── Backend ───────────────────────────────────────────────────────────────────
 
 pub struct Backend {
     client: Client,
-    docs: Arc<RwLock<HashMap<Url, String>>>,
+    /// Every `.mkml` file in the workspace; open documents hold the editor's text.
+    workspace: Arc<RwLock<Workspace>>,
+    /// Widget vocabulary shared with the UI builder, plus the application's
+    /// own widgets; set by `initialize`.
+    schema: OnceLock<Schema>,
 }
 
 impl Backend {
     pub fn new(client: Client) -> Self {
         Self {
             client,
-            docs: Arc::new(RwLock::new(HashMap::new())),
+            workspace: Arc::new(RwLock::new(Workspace::default())),
+            schema: OnceLock::new(),
         }
     }
 
-    async fn update(&self, uri: Url, text: String) {
-        let diagnostics = parse_diagnostics(&text);
-        self.client
-            .publish_diagnostics(uri.clone(), diagnostics, None)
-            .await;
-        self.docs.write().await.insert(uri, text);
+    /// Re-validates `uri` and every open file that imports it, since a
+    /// change can fix or break their imports too.
+    async fn changed(&self, uri: &Url) {
+        let published: Vec<(Url, Vec<Diagnostic>)> = {
+            let ws = self.workspace.read().await;
+            std::iter::once(uri.clone())
+                .chain(ws.dependants(uri))
+                .filter(|u| ws.is_open(u))
+                .filter_map(|u| {
+                    let text = ws.text(&u)?;
+                    let mut diagnostics = diagnostics(text, self.schema());
+                    diagnostics.extend(ws.import_issues(&u).into_iter().map(|(span, message)| {
+                        Diagnostic {
+                            range: span_range(text, span),
+                            severity: Some(DiagnosticSeverity::ERROR),
+                            source: Some("marduk-lsp".to_string()),
+                            message,
+                            ..Default::default()
+                        }
+                    }));
+                    Some((u, diagnostics))
+                })
+                .collect()
+        };
+        for (uri, diagnostics) in published {
+            self.client.publish_diagnostics(uri, diagnostics, None).await;
+        }
+    }
+
+    fn schema(&self) -> &Schema {
+        self.schema.get_or_init(Schema::new)
+    }
+
+    async fn text(&self, uri: &Url) -> Option<String> {
+        self.workspace.read().await.text(uri).map(str::to_string)
+    }
+
+    /// Every indexed file.
+    async fn uris(&self) -> Vec<Url> {
+        self.workspace.read().await.uris().cloned().collect()
+    }
+
+    /// The file defining component `alias` when no import names it — e.g. a
+    /// component registered from Rust. Matches the file stem, ignoring case.
+    async fn component_file(&self, alias: &str) -> Option<Url> {
+        self.uris().await.into_iter().find(|uri| {
+            uri.to_file_path().ok().is_some_and(|path| {
+                path.file_stem().is_some_and(|stem| stem.to_string_lossy().eq_ignore_ascii_case(alias))
+            })
+        })
+    }
+
+    /// Every occurrence of `symbol`, found in `origin`, across the
+    /// workspace, by file — only in files where it means the same thing.
+    async fn find_all(
+        &self,
+        origin: (&Url, &DslDocument),
+        symbol: &Symbol,
+    ) -> HashMap<Url, Vec<(Range, bool)>> {
+        let ws = self.workspace.read().await;
+        let mut out = HashMap::new();
+        for (uri, text) in ws.files() {
+            let Ok(doc) = marduk_mkml::parse_str(text) else { continue };
+            if !same_symbol(symbol, origin, (uri, &doc)) {
+                continue;
+            }
+            let found: Vec<_> = occurrences(&doc, self.schema(), symbol)
+                .into_iter()
+                .map(|o| (span_range(text, o.span), o.declaration))
+                .collect();
+            if !found.is_empty() {
+                out.insert(uri.clone(), found);
+            }
+        }
+        out
+    }
+
+    /// The document at `uri`, parsed, with its text and the byte offset of
+    /// `pos` in it.
+    async fn parsed_at(&self, uri: &Url, pos: &Position) -> Option<(String, DslDocument, usize)> {
+        let text = self.text(uri).await?;
+        let doc = marduk_mkml::parse_str(&text).ok()?;
+        let offset = offset_at(&text, pos);
+        Some((text, doc, offset))
     }
 }
 
@@ -39,11 +139,32 @@ impl Backend {
 
 #[tower_lsp::async_trait]
 impl LanguageServer for Backend {
-    async fn initialize(&self, _params: InitializeParams) -> Result<InitializeResult> {
+    async fn initialize(&self, params: InitializeParams) -> Result<InitializeResult> {
+        let mut roots: Vec<PathBuf> = params
+            .workspace_folders
+            .iter()
+            .flatten()
+            .filter_map(|f| f.uri.to_file_path().ok())
+            .collect();
+        #[allow(deprecated)]
+        if roots.is_empty()
+            && let Some(root) = params.root_uri.and_then(|u| u.to_file_path().ok())
+        {
+            roots.push(root);
+        }
+        let (schema, errors) = widgets::load(&roots);
+        let _ = self.schema.set(schema);
+        for error in errors {
+            self.client.log_message(MessageType::WARNING, error).await;
+        }
+        self.workspace.write().await.set_roots(roots);
+
         Ok(InitializeResult {
             capabilities: ServerCapabilities {
+                // Also the default: columns count UTF-16 code units.
+                position_encoding: Some(PositionEncodingKind::UTF16),
                 text_document_sync: Some(TextDocumentSyncCapability::Kind(
-                    TextDocumentSyncKind::FULL,
+                    TextDocumentSyncKind::INCREMENTAL,
                 )),
                 hover_provider: Some(HoverProviderCapability::Simple(true)),
                 completion_provider: Some(CompletionOptions {
@@ -54,6 +175,28 @@ impl LanguageServer for Backend {
                     ]),
                     ..Default::default()
                 }),
+                definition_provider: Some(OneOf::Left(true)),
+                references_provider: Some(OneOf::Left(true)),
+                rename_provider: Some(OneOf::Right(RenameOptions {
+                    prepare_provider: Some(true),
+                    work_done_progress_options: Default::default(),
+                })),
+                document_formatting_provider: Some(OneOf::Left(true)),
+                document_range_formatting_provider: Some(OneOf::Left(true)),
+                document_symbol_provider: Some(OneOf::Left(true)),
+                color_provider: Some(ColorProviderCapability::Simple(true)),
+                code_action_provider: Some(CodeActionProviderCapability::Options(CodeActionOptions {
+                    code_action_kinds: Some(vec![CodeActionKind::QUICKFIX, CodeActionKind::REFACTOR]),
+                    ..Default::default()
+                })),
+                folding_range_provider: Some(FoldingRangeProviderCapability::Simple(true)),
+                semantic_tokens_provider: Some(
+                    SemanticTokensServerCapabilities::SemanticTokensOptions(SemanticTokensOptions {
+                        legend: semantic::legend(),
+                        full: Some(SemanticTokensFullOptions::Bool(true)),
+                        ..Default::default()
+                    }),
+                ),
                 ..Default::default()
             },
             server_info: Some(ServerInfo {
@@ -64,6 +207,24 @@ impl LanguageServer for Backend {
     }
 
     async fn initialized(&self, _params: InitializedParams) {
+        self.workspace.write().await.scan();
+
+        // Hear about `.mkml` files changed outside the editor, so importers of
+        // a component edited elsewhere are re-validated.
+        let watch = DidChangeWatchedFilesRegistrationOptions {
+            watchers: vec![FileSystemWatcher {
+                glob_pattern: GlobPattern::String("**/*.mkml".to_string()),
+                kind: None,
+            }],
+        };
+        let registration = Registration {
+            id: "mkml-files".to_string(),
+            method: "workspace/didChangeWatchedFiles".to_string(),
+            register_options: serde_json::to_value(watch).ok(),
+        };
+        // Clients without dynamic registration just never send the events.
+        let _ = self.client.register_capability(vec![registration]).await;
+
         self.client
             .log_message(MessageType::INFO, "marduk-lsp ready")
             .await;
@@ -76,19 +237,33 @@ impl LanguageServer for Backend {
     // ── Document lifecycle ────────────────────────────────────────────────────
 
     async fn did_open(&self, params: DidOpenTextDocumentParams) {
-        self.update(params.text_document.uri, params.text_document.text)
-            .await;
+        let uri = params.text_document.uri;
+        self.workspace.write().await.open(uri.clone(), params.text_document.text);
+        self.changed(&uri).await;
     }
 
     async fn did_change(&self, params: DidChangeTextDocumentParams) {
-        // We request FULL sync, so there's always exactly one change entry.
-        if let Some(change) = params.content_changes.into_iter().last() {
-            self.update(params.text_document.uri, change.text).await;
-        }
+        let uri = params.text_document.uri;
+        self.workspace.write().await.edit(&uri, params.content_changes);
+        self.changed(&uri).await;
     }
 
     async fn did_close(&self, params: DidCloseTextDocumentParams) {
-        self.docs.write().await.remove(&params.text_document.uri);
+        let uri = params.text_document.uri;
+        self.workspace.write().await.close(&uri);
+        // Closed files are no longer reported on; importers may be affected
+        // if the saved text differs from what was open.
+        self.client.publish_diagnostics(uri.clone(), vec![], None).await;
+        self.changed(&uri).await;
+    }
+
+    async fn did_change_watched_files(&self, params: DidChangeWatchedFilesParams) {
+        for change in &params.changes {
+            self.workspace.write().await.reload(&change.uri);
+        }
+        for change in params.changes {
+            self.changed(&change.uri).await;
+        }
     }
 
     // ── Hover ─────────────────────────────────────────────────────────────────
@@ -96,32 +271,49 @@ impl LanguageServer for Backend {
     async fn hover(&self, params: HoverParams) -> Result<Option<Hover>> {
         let uri = &params.text_document_position_params.text_document.uri;
         let pos = &params.text_document_position_params.position;
+        let Some(text) = self.text(uri).await else { return Ok(None) };
+        let offset = offset_at(&text, pos);
 
-        let docs = self.docs.read().await;
-        let text = match docs.get(uri) {
-            Some(t) => t,
-            None => return Ok(None),
-        };
+        if let Some((span, rgba)) = color_at(&text, offset) {
+            return Ok(Some(markdown_hover(color_doc(rgba), Some(span_range(&text, span)))));
+        }
 
-        let word = match word_at(text, pos) {
-            Some(w) => w,
-            None => return Ok(None),
-        };
+        // With a parsed document, answer for the exact node under the cursor.
+        if let Ok(doc) = marduk_mkml::parse_str(&text) {
+            if let Some((Symbol::Component(alias), occurrence)) = symbol_at(&doc, self.schema(), offset) {
+                let file = match import_of(&doc, &alias) {
+                    Some(import) => uri.join(&import.path).ok(),
+                    None => self.component_file(&alias).await,
+                };
+                let source = match &file {
+                    Some(file) => self.text(file).await,
+                    None => None,
+                };
+                let md = component_doc(&alias, source.as_deref());
+                return Ok(Some(markdown_hover(md, Some(span_range(&text, occurrence.span)))));
+            }
+            if let Some((node, prop)) = prop_at(&doc, offset)
+                && let Some(widget) = self.schema().widget(&node.widget)
+                && let Some(schema) = widget.prop(&prop.key)
+            {
+                let range = span_range(&text, prop.key_span);
+                return Ok(Some(markdown_hover(prop_doc(widget, schema, Some(node)), Some(range))));
+            }
+        }
+
+        let Some(word) = word_at(&text, pos) else { return Ok(None) };
 
         // Widget name hover
-        if let Some(widget) = widget_by_name(word) {
-            let md = format!("**{}**\n\n{}", widget.name, widget.doc);
-            return Ok(Some(markdown_hover(md)));
+        if let Some(widget) = self.schema().widget(word) {
+            return Ok(Some(markdown_hover(widget_doc(widget), None)));
         }
 
         // Property name hover — find the enclosing widget for context
-        let before = text_before_pos(text, pos);
-        if let Some(widget_name) = find_enclosing_widget(&before) {
-            if let Some(prop) = prop_in_widget(&widget_name, word) {
-                let kind_label = kind_doc(&prop.kind);
-                let md = format!("**{}** · {}\n\n{}", prop.name, kind_label, prop.doc);
-                return Ok(Some(markdown_hover(md)));
-            }
+        let before = text_before_pos(&text, pos);
+        if let Some(widget) = find_enclosing_widget(&before).and_then(|w| self.schema().widget(&w))
+            && let Some(prop) = widget.prop(word)
+        {
+            return Ok(Some(markdown_hover(prop_doc(widget, prop, None), None)));
         }
 
         Ok(None)
@@ -136,32 +328,215 @@ impl LanguageServer for Backend {
         let uri = &params.text_document_position.text_document.uri;
         let pos = &params.text_document_position.position;
 
-        let docs = self.docs.read().await;
-        let text = match docs.get(uri) {
+        let ws = self.workspace.read().await;
+        let text = match ws.text(uri) {
             Some(t) => t,
             None => return Ok(None),
         };
 
         let items = match completion_context(text, pos) {
-            Context::Widget => widget_items(),
-            Context::Property { widget } => property_items(&widget),
-            Context::Value { widget, prop } => value_items(&widget, &prop),
+            Context::Widget => widget_items(self.schema()),
+            Context::Property { widget } => property_items(self.schema(), &widget),
+            Context::Value { widget, prop } => value_items(self.schema(), &widget, &prop),
             Context::Unknown => vec![],
         };
 
         Ok(Some(CompletionResponse::Array(items)))
     }
+
+    // ── Navigation ────────────────────────────────────────────────────────────
+
+    async fn goto_definition(
+        &self,
+        params: GotoDefinitionParams,
+    ) -> Result<Option<GotoDefinitionResponse>> {
+        let uri = &params.text_document_position_params.text_document.uri;
+        let pos = &params.text_document_position_params.position;
+        let Some((_, doc, offset)) = self.parsed_at(uri, pos).await else { return Ok(None) };
+
+        // On an import path, or on a component its alias names: open the file.
+        let target = if let Some(import) = doc.imports.iter().find(|i| i.path_span.contains(offset)) {
+            uri.join(&import.path).ok()
+        } else if let Some((Symbol::Component(alias), _)) = symbol_at(&doc, self.schema(), offset) {
+            match import_of(&doc, &alias) {
+                Some(import) => uri.join(&import.path).ok(),
+                None => self.component_file(&alias).await,
+            }
+        } else {
+            // Events are defined in Rust, outside the workspace's `.mkml` files.
+            None
+        };
+
+        Ok(target.map(|uri| {
+            GotoDefinitionResponse::Scalar(Location::new(uri, Range::default()))
+        }))
+    }
+
+    async fn references(&self, params: ReferenceParams) -> Result<Option<Vec<Location>>> {
+        let uri = &params.text_document_position.text_document.uri;
+        let pos = &params.text_document_position.position;
+        let Some((_, doc, offset)) = self.parsed_at(uri, pos).await else { return Ok(None) };
+        let Some((symbol, _)) = symbol_at(&doc, self.schema(), offset) else { return Ok(None) };
+
+        let declarations = params.context.include_declaration;
+        let mut locations: Vec<Location> = self
+            .find_all((uri, &doc), &symbol)
+            .await
+            .into_iter()
+            .flat_map(|(uri, found)| {
+                found
+                    .into_iter()
+                    .filter(move |(_, declaration)| declarations || !declaration)
+                    .map(move |(range, _)| Location::new(uri.clone(), range))
+            })
+            .collect();
+        locations.sort_by(|a, b| {
+            (a.uri.as_str(), a.range.start).cmp(&(b.uri.as_str(), b.range.start))
+        });
+        Ok(Some(locations))
+    }
+
+    async fn prepare_rename(
+        &self,
+        params: TextDocumentPositionParams,
+    ) -> Result<Option<PrepareRenameResponse>> {
+        let Some((text, doc, offset)) =
+            self.parsed_at(&params.text_document.uri, &params.position).await
+        else {
+            return Ok(None);
+        };
+        Ok(symbol_at(&doc, self.schema(), offset).map(|(symbol, here)| {
+            PrepareRenameResponse::RangeWithPlaceholder {
+                range: span_range(&text, here.span),
+                placeholder: symbol.name().to_string(),
+            }
+        }))
+    }
+
+    /// Renames in every `.mkml` file of the workspace. Names in Rust source —
+    /// `.component("Header", …)`, `.on_event("launch", …)` — are left alone.
+    async fn rename(&self, params: RenameParams) -> Result<Option<WorkspaceEdit>> {
+        let uri = &params.text_document_position.text_document.uri;
+        let pos = &params.text_document_position.position;
+        if !is_valid_name(&params.new_name) {
+            return Err(tower_lsp::jsonrpc::Error::invalid_params(format!(
+                "`{}` is not a valid name",
+                params.new_name
+            )));
+        }
+        let Some((_, doc, offset)) = self.parsed_at(uri, pos).await else { return Ok(None) };
+        let Some((symbol, _)) = symbol_at(&doc, self.schema(), offset) else { return Ok(None) };
+
+        let changes = self
+            .find_all((uri, &doc), &symbol)
+            .await
+            .into_iter()
+            .map(|(uri, found)| {
+                let edits = found
+                    .into_iter()
+                    .map(|(range, _)| TextEdit::new(range, params.new_name.clone()))
+                    .collect();
+                (uri, edits)
+            })
+            .collect();
+        Ok(Some(WorkspaceEdit::new(changes)))
+    }
+
+    // ── Code actions ──────────────────────────────────────────────────────────
+
+    async fn code_action(&self, params: CodeActionParams) -> Result<Option<CodeActionResponse>> {
+        let uri = &params.text_document.uri;
+        let Some(text) = self.text(uri).await else { return Ok(None) };
+        let files = self.uris().await;
+        Ok(Some(code_actions(uri, &text, self.schema(), params.range, &files)))
+    }
+
+    // ── Outline and highlighting ──────────────────────────────────────────────
+
+    async fn document_symbol(
+        &self,
+        params: DocumentSymbolParams,
+    ) -> Result<Option<DocumentSymbolResponse>> {
+        let Some(text) = self.text(&params.text_document.uri).await else { return Ok(None) };
+        let Ok(doc) = marduk_mkml::parse_str(&text) else { return Ok(None) };
+        Ok(Some(DocumentSymbolResponse::Nested(document_symbols(&text, &doc, self.schema()))))
+    }
+
+    async fn folding_range(&self, params: FoldingRangeParams) -> Result<Option<Vec<FoldingRange>>> {
+        let Some(text) = self.text(&params.text_document.uri).await else { return Ok(None) };
+        Ok(Some(folding_ranges(&text)))
+    }
+
+    async fn semantic_tokens_full(
+        &self,
+        params: SemanticTokensParams,
+    ) -> Result<Option<SemanticTokensResult>> {
+        let Some(text) = self.text(&params.text_document.uri).await else { return Ok(None) };
+        Ok(semantic::semantic_tokens(&text, self.schema()).map(|data| {
+            SemanticTokensResult::Tokens(SemanticTokens { result_id: None, data })
+        }))
+    }
+
+    // ── Colors ────────────────────────────────────────────────────────────────
+
+    async fn document_color(&self, params: DocumentColorParams) -> Result<Vec<ColorInformation>> {
+        let text = self.text(&params.text_document.uri).await.unwrap_or_default();
+        Ok(document_colors(&text))
+    }
+
+    async fn color_presentation(
+        &self,
+        params: ColorPresentationParams,
+    ) -> Result<Vec<ColorPresentation>> {
+        Ok(presentations(params.color, params.range))
+    }
+
+    // ── Formatting ────────────────────────────────────────────────────────────
+
+    async fn formatting(&self, params: DocumentFormattingParams) -> Result<Option<Vec<TextEdit>>> {
+        let Some(text) = self.text(&params.text_document.uri).await else { return Ok(None) };
+        // A document that does not parse is left alone; its diagnostics say why.
+        let Ok(formatted) = format_str(&text, &format_options(&params.options)) else {
+            return Ok(None);
+        };
+        if formatted == text {
+            return Ok(Some(vec![]));
+        }
+        let whole = Range { start: Position::new(0, 0), end: end_position(&text) };
+        Ok(Some(vec![TextEdit::new(whole, formatted)]))
+    }
+
+    async fn range_formatting(
+        &self,
+        params: DocumentRangeFormattingParams,
+    ) -> Result<Option<Vec<TextEdit>>> {
+        let Some(text) = self.text(&params.text_document.uri).await else { return Ok(None) };
+        let start = offset_at(&text, &params.range.start);
+        let end = offset_at(&text, &params.range.end);
+        let Ok((span, formatted)) = format_range(&text, start, end, &format_options(&params.options)) else {
+            return Ok(None);
+        };
+        if text[span.start.offset..span.end.offset] == formatted {
+            return Ok(Some(vec![]));
+        }
+        Ok(Some(vec![TextEdit::new(span_range(&text, span), formatted)]))
+    }
 }
 
 // ── Diagnostics ───────────────────────────────────────────────────────────────
 
-fn parse_diagnostics(text: &str) -> Vec<Diagnostic> {
+/// Syntax errors, or — for a document that parses — schema violations.
+pub(crate) fn diagnostics(text: &str, schema: &Schema) -> Vec<Diagnostic> {
     match marduk_mkml::parse_str(text) {
-        Ok(_) => vec![],
+        Ok(doc) => check(&doc, schema, &[]).iter().map(|i| issue_diagnostic(text, i)).collect(),
         Err(e) => {
-            // ParseError line/col are 1-based; LSP Position is 0-based.
-            let line = e.line.saturating_sub(1) as u32;
-            let col  = e.col.saturating_sub(1) as u32;
+            // ParseError line/col are 1-based and count characters; LSP
+            // Position is 0-based and counts UTF-16 units.
+            let line = e.line.saturating_sub(1);
+            let col: usize = text.lines().nth(line).map_or(0, |l| {
+                l.chars().take(e.col.saturating_sub(1)).map(char::len_utf16).sum()
+            });
+            let (line, col) = (line as u32, col as u32);
             vec![Diagnostic {
                 range: Range {
                     start: Position::new(line, col),
@@ -176,11 +551,25 @@ fn parse_diagnostics(text: &str) -> Vec<Diagnostic> {
     }
 }
 
+pub(crate) fn issue_diagnostic(text: &str, issue: &Issue) -> Diagnostic {
+    Diagnostic {
+        range: span_range(text, issue.span),
+        severity: Some(match issue.severity {
+            Severity::Error => DiagnosticSeverity::ERROR,
+            Severity::Warning => DiagnosticSeverity::WARNING,
+            Severity::Hint => DiagnosticSeverity::HINT,
+        }),
+        source: Some("marduk-lsp".to_string()),
+        message: issue.message.clone(),
+        ..Default::default()
+    }
+}
+
 // ── Completion item builders ──────────────────────────────────────────────────
 
-fn widget_items() -> Vec<CompletionItem> {
-    WIDGETS
-        .iter()
+fn widget_items(schema: &Schema) -> Vec<CompletionItem> {
+    schema
+        .widgets()
         .map(|w| {
             let detail = w.doc.lines().next().unwrap_or("").to_string();
             let mut item = CompletionItem::new_simple(w.name.to_string(), detail);
@@ -192,26 +581,28 @@ fn widget_items() -> Vec<CompletionItem> {
         .collect()
 }
 
-fn property_items(widget: &str) -> Vec<CompletionItem> {
-    let info = match widget_by_name(widget) {
+fn property_items(schema: &Schema, widget: &str) -> Vec<CompletionItem> {
+    let info = match schema.widget(widget) {
         Some(i) => i,
         None => return vec![],
     };
-    info.props
-        .iter()
+    info.all_props()
         .map(|p| {
             let detail = p.doc.lines().next().unwrap_or("").to_string();
             let mut item = CompletionItem::new_simple(p.name.to_string(), detail);
             item.kind = Some(CompletionItemKind::PROPERTY);
             item.insert_text = Some(format!("{}: $0", p.name));
             item.insert_text_format = Some(InsertTextFormat::SNIPPET);
+            if p.deprecated {
+                item.tags = Some(vec![CompletionItemTag::DEPRECATED]);
+            }
             item
         })
         .collect()
 }
 
-fn value_items(widget: &str, prop: &str) -> Vec<CompletionItem> {
-    let prop_info = match prop_in_widget(widget, prop) {
+fn value_items(schema: &Schema, widget: &str, prop: &str) -> Vec<CompletionItem> {
+    let prop_info = match schema.prop(widget, prop) {
         Some(p) => p,
         None => return vec![],
     };
@@ -227,6 +618,16 @@ fn value_items(widget: &str, prop: &str) -> Vec<CompletionItem> {
             })
             .collect(),
 
+        PropKind::Size => ["fill", "natural"]
+            .iter()
+            .map(|v| {
+                let mut item =
+                    CompletionItem::new_simple(v.to_string(), String::new());
+                item.kind = Some(CompletionItemKind::ENUM_MEMBER);
+                item
+            })
+            .collect(),
+
         PropKind::Bool => vec![
             bool_item("0", "false"),
             bool_item("1", "true"),
@@ -256,37 +657,42 @@ fn bool_item(label: &str, detail: &str) -> CompletionItem {
 
 // ── Misc helpers ──────────────────────────────────────────────────────────────
 
-fn markdown_hover(md: String) -> Hover {
+fn markdown_hover(md: String, range: Option<Range>) -> Hover {
     Hover {
         contents: HoverContents::Markup(MarkupContent {
             kind: MarkupKind::Markdown,
             value: md,
         }),
-        range: None,
+        range,
     }
 }
 
-fn kind_doc(kind: &PropKind) -> &'static str {
-    match kind {
-        PropKind::Number => "number",
-        PropKind::Color => "color (`#rrggbbaa`)",
-        PropKind::Bool => "bool (`0` or `1`)",
-        PropKind::Enum(_) => "enum",
-        PropKind::Event => "event name",
-        PropKind::Font => "font name",
-    }
+/// The editor's indentation width; other options keep their defaults.
+fn format_options(options: &FormattingOptions) -> FormatOptions {
+    FormatOptions { indent: options.tab_size as usize, ..FormatOptions::default() }
+}
+
+/// The position just past the last character of `text`.
+fn end_position(text: &str) -> Position {
+    let line = text.matches('\n').count();
+    let col = text.rsplit('\n').next().map_or(0, |l| l.encode_utf16().count());
+    Position::new(line as u32, col as u32)
+}
+
+/// Converts a parser span of `text` to an LSP range.
+pub(crate) fn span_range(text: &str, span: Span) -> Range {
+    Range { start: position_at(text, span.start), end: position_at(text, span.end) }
 }
 
 fn text_before_pos(text: &str, pos: &Position) -> String {
     let line_idx = pos.line as usize;
-    let col = pos.character as usize;
     let mut out = String::new();
     for (i, line) in text.lines().enumerate() {
         if i < line_idx {
             out.push_str(line);
             out.push('\n');
         } else if i == line_idx {
-            out.push_str(&line[..col.min(line.len())]);
+            out.push_str(&line[..column_offset(line, pos.character)]);
             break;
         } else {
             break;
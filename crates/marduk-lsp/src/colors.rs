@@ -0,0 +1,82 @@
+//! Color swatches and the color picker.
+//!
+//! Colors come straight from the lexer, so swatches stay up while the rest
+//! of the document has a parse error. Editors work in `0.0..=1.0` floats;
+//! the source holds straight-alpha bytes.
+
+use marduk_mkml::lexer::{Lexer, Token};
+use tower_lsp::lsp_types::{Color, ColorInformation, ColorPresentation, Range, TextEdit};
+
+use crate::backend::span_range;
+
+/// Every color literal in `text`, with its value.
+pub fn document_colors(text: &str) -> Vec<ColorInformation> {
+    let Ok(tokens) = Lexer::new(text).tokenize() else { return vec![] };
+    tokens
+        .iter()
+        .filter_map(|t| match t.token {
+            Token::Color([r, g, b, a]) => Some(ColorInformation {
+                range: span_range(text, t.span),
+                color: Color {
+                    red:   f32::from(r) / 255.0,
+                    green: f32::from(g) / 255.0,
+                    blue:  f32::from(b) / 255.0,
+                    alpha: f32::from(a) / 255.0,
+                },
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+/// How a picked color is written back over `range`: always `#rrggbbaa`.
+pub fn presentations(color: Color, range: Range) -> Vec<ColorPresentation> {
+    let label = hex(color);
+    vec![ColorPresentation {
+        text_edit: Some(TextEdit::new(range, label.clone())),
+        label,
+        additional_text_edits: None,
+    }]
+}
+
+/// `#rrggbbaa`, lowercase, each channel rounded to the nearest byte.
+fn hex(color: Color) -> String {
+    let byte = |c: f32| (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+    format!(
+        "#{:02x}{:02x}{:02x}{:02x}",
+        byte(color.red),
+        byte(color.green),
+        byte(color.blue),
+        byte(color.alpha),
+    )
+}
+
+// ── tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tower_lsp::lsp_types::Position;
+
+    #[test]
+    fn finds_colors_even_in_broken_documents() {
+        let colors = document_colors("Row { bg: #ff000080\n  border_color: #00FF00\n  gap: ");
+        assert_eq!(colors.len(), 2);
+        assert_eq!(colors[0].range, Range::new(Position::new(0, 10), Position::new(0, 19)));
+        assert_eq!((colors[0].color.red, colors[0].color.alpha), (1.0, 128.0 / 255.0));
+        assert_eq!(colors[1].color.alpha, 1.0);
+    }
+
+    #[test]
+    fn writes_back_eight_digits() {
+        let color = Color { red: 1.0, green: 0.5, blue: 0.0, alpha: 1.0 };
+        let range = Range::new(Position::new(0, 10), Position::new(0, 17));
+        let p = &presentations(color, range)[0];
+        assert_eq!(p.label, "#ff8000ff");
+        assert_eq!(p.text_edit.as_ref().unwrap().range, range);
+
+        // Round-trips what the lexer read.
+        let read = document_colors("Row { bg: #12345678 }")[0].color;
+        assert_eq!(hex(read), "#12345678");
+    }
+}
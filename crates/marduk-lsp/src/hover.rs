@@ -0,0 +1,269 @@
+//! Hover markdown: widget and property docs, color values, and a preview of
+//! a component's structure.
+//!
+//! Property hovers include the default the builder applies when the property
+//! is omitted and the range it is clamped to. Colors show a swatch and the
+//! premultiplied value `Color::from_srgb_u8` turns the literal into, so what
+//! is written can be compared with what Rust code sees.
+
+use marduk_mkml::ast::{Control, DslDocument, Node, Prop, Span, Value};
+use marduk_mkml::lexer::{Lexer, Token};
+use marduk_mkml::schema::{PropKind, PropSchema, WidgetSchema};
+
+use crate::navigate::walk;
+
+/// Levels of a component shown below its root before collapsing to `{ … }`.
+const PREVIEW_DEPTH: usize = 3;
+
+// ── Lookup ────────────────────────────────────────────────────────────────────
+
+/// The color literal under byte `offset`, from the lexer so it works while
+/// the document has a parse error.
+pub fn color_at(text: &str, offset: usize) -> Option<(Span, [u8; 4])> {
+    let tokens = Lexer::new(text).tokenize().ok()?;
+    tokens.iter().find_map(|t| match t.token {
+        Token::Color(rgba) if t.span.contains(offset) => Some((t.span, rgba)),
+        _ => None,
+    })
+}
+
+/// The property whose key is under byte `offset`, with the node it is set on.
+pub fn prop_at(doc: &DslDocument, offset: usize) -> Option<(&Node, &Prop)> {
+    let mut found = None;
+    walk(&doc.root, &mut |node| {
+        if let Some(prop) = node.props.iter().find(|p| p.key_span.contains(offset)) {
+            found = Some((node, prop));
+        }
+    });
+    found
+}
+
+// ── Markdown ──────────────────────────────────────────────────────────────────
+
+pub fn widget_doc(widget: &WidgetSchema) -> String {
+    format!("**{}**\n\n{}", widget.name, widget.doc)
+}
+
+/// A property of `widget`: kind, docs, default, range and alias notes.
+///
+/// With the `node` it is set on, range bounds naming sibling properties show
+/// the values written there.
+pub fn prop_doc(widget: &WidgetSchema, prop: &PropSchema, node: Option<&Node>) -> String {
+    let mut md = format!("**{}** · {}\n\n{}", prop.name, prop.kind.describe(), prop.doc);
+    if let Some(default) = prop.default {
+        md.push_str(&format!("\n\nDefault: `{default}`"));
+        if prop.kind == PropKind::Color
+            && let Some((_, rgba)) = color_at(default, 0)
+        {
+            md.push_str(&format!(" {}", swatch(rgba)));
+        }
+    }
+    if let Some((min, max)) = prop.range {
+        let bound = |b: &str| match resolve_bound(widget, b, node) {
+            Some(value) => format!("`{b}` (`{value}`)"),
+            None => format!("`{b}`"),
+        };
+        md.push_str(&format!("\n\nRange: {} to {}", bound(min), bound(max)));
+    }
+    match (prop.alias_of, prop.deprecated) {
+        (Some(canonical), true) => md.push_str(&format!("\n\n*Deprecated* — use `{canonical}`.")),
+        (Some(canonical), false) => md.push_str(&format!("\n\nAlias of `{canonical}`.")),
+        (None, _) => {}
+    }
+    md
+}
+
+/// A color literal: swatch, hex, and the premultiplied `Color` it becomes.
+pub fn color_doc([r, g, b, a]: [u8; 4]) -> String {
+    let alpha = f32::from(a) / 255.0;
+    let premul = |c: u8| f32::from(c) / 255.0 * alpha;
+    format!(
+        "{} `#{r:02x}{g:02x}{b:02x}{a:02x}`\n\n\
+         `Color::from_srgb_u8({r}, {g}, {b}, {a})` → premultiplied linear \
+         `({:.3}, {:.3}, {:.3}, {alpha:.3})`",
+        swatch([r, g, b, a]),
+        premul(r),
+        premul(g),
+        premul(b),
+    )
+}
+
+/// Component `alias`: its parameters and the widgets under its root, from
+/// the `source` of the file defining it.
+pub fn component_doc(alias: &str, source: Option<&str>) -> String {
+    let mut md = format!("**{alias}** · component");
+    let Some(source) = source else {
+        md.push_str("\n\nNo `.mkml` file defines it; it may be registered from Rust.");
+        return md;
+    };
+    let Ok(doc) = marduk_mkml::parse_str(source) else {
+        md.push_str("\n\nIts file does not parse.");
+        return md;
+    };
+    md.push_str("\n\n```mkml\n");
+    for param in &doc.params {
+        md.push_str(&source[param.span.start.offset..param.span.end.offset]);
+        md.push('\n');
+    }
+    if !doc.params.is_empty() {
+        md.push('\n');
+    }
+    outline(source, &doc.root, 0, &mut md);
+    md.push_str("```");
+    md
+}
+
+// ── internal ──────────────────────────────────────────────────────────────────
+
+/// The value of range bound `bound` when it names a sibling property: as
+/// written on `node`, or else that property's default.
+fn resolve_bound(widget: &WidgetSchema, bound: &str, node: Option<&Node>) -> Option<String> {
+    if bound.parse::<f32>().is_ok() {
+        return None;
+    }
+    match node.and_then(|n| n.prop(bound)) {
+        Some(Value::Number(v)) => Some(v.to_string()),
+        Some(_) => None,
+        None => widget.prop(bound)?.default.map(str::to_string),
+    }
+}
+
+/// A 32 × 16 SVG swatch over a checkerboard, as a markdown image.
+fn swatch([r, g, b, a]: [u8; 4]) -> String {
+    let svg = format!(
+        "<svg xmlns='http://www.w3.org/2000/svg' width='32' height='16'>\
+         <defs><pattern id='c' width='8' height='8' patternUnits='userSpaceOnUse'>\
+         <rect width='8' height='8' fill='#fff'/>\
+         <rect width='4' height='4' fill='#ccc'/><rect x='4' y='4' width='4' height='4' fill='#ccc'/>\
+         </pattern></defs>\
+         <rect width='32' height='16' fill='url(#c)'/>\
+         <rect width='32' height='16' fill='#{r:02x}{g:02x}{b:02x}' fill-opacity='{:.3}'/></svg>",
+        f32::from(a) / 255.0,
+    );
+    let mut uri = String::from("data:image/svg+xml,");
+    for byte in svg.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-_.~/:=',;".contains(&byte) {
+            uri.push(byte as char);
+        } else {
+            uri.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    format!("![swatch]({uri})")
+}
+
+/// One line per node — widget and inline content, without properties —
+/// collapsing anything deeper than [`PREVIEW_DEPTH`].
+fn outline(source: &str, node: &Node, depth: usize, out: &mut String) {
+    let indent = "    ".repeat(depth);
+    let head = match &node.control {
+        Some(Control::For { item, list }) => format!("for {item} in {list}"),
+        Some(Control::If { cond, .. }) => format!("if {cond}"),
+        Some(Control::Slot) => "slot".to_string(),
+        None => match node.content_span {
+            Some(span) => format!("{} {}", node.widget, &source[span.start.offset..span.end.offset]),
+            None => node.widget.clone(),
+        },
+    };
+    let otherwise = match &node.control {
+        Some(Control::If { otherwise, .. }) => otherwise.as_slice(),
+        _ => &[],
+    };
+    if node.children.is_empty() && otherwise.is_empty() {
+        if node.control == Some(Control::Slot) {
+            out.push_str(&format!("{indent}{head}\n"));
+        } else {
+            out.push_str(&format!("{indent}{head} {{ }}\n"));
+        }
+        return;
+    }
+    if depth >= PREVIEW_DEPTH {
+        out.push_str(&format!("{indent}{head} {{ … }}\n"));
+        return;
+    }
+    out.push_str(&format!("{indent}{head} {{\n"));
+    for child in &node.children {
+        outline(source, child, depth + 1, out);
+    }
+    if !otherwise.is_empty() {
+        out.push_str(&format!("{indent}}} else {{\n"));
+        for child in otherwise {
+            outline(source, child, depth + 1, out);
+        }
+    }
+    out.push_str(&format!("{indent}}}\n"));
+}
+
+// ── tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use marduk_mkml::schema::Schema;
+
+    #[test]
+    fn colors_show_the_premultiplied_value() {
+        let text = "Row { bg: #ff800080 }";
+        let (span, rgba) = color_at(text, 12).unwrap();
+        assert_eq!((span.start.offset, rgba), (10, [255, 128, 0, 128]));
+        assert!(color_at(text, 4).is_none());
+
+        let md = color_doc(rgba);
+        assert!(md.contains("`#ff800080`"), "{md}");
+        assert!(md.contains("`Color::from_srgb_u8(255, 128, 0, 128)`"), "{md}");
+        assert!(md.contains("`(0.502, 0.252, 0.000, 0.502)`"), "{md}");
+        assert!(md.starts_with("![swatch](data:image/svg+xml,"), "{md}");
+        assert!(!md[..md.find(") ").unwrap()].contains('#'), "{md}");
+    }
+
+    #[test]
+    fn props_show_defaults_and_ranges() {
+        let schema = Schema::new();
+        let slider = schema.widget("Slider").unwrap();
+        let value = slider.prop("value").unwrap();
+
+        let md = prop_doc(slider, value, None);
+        assert!(md.contains("Default: `min`"), "{md}");
+        assert!(md.contains("Range: `min` (`0`) to `max` (`1`)"), "{md}");
+
+        let doc = marduk_mkml::parse_str("Slider { min: 10  max: 50  value: 20 }").unwrap();
+        let offset = "Slider { min: 10  max: 50  va".len();
+        let (node, prop) = prop_at(&doc, offset).unwrap();
+        assert_eq!(prop.key, "value");
+        let md = prop_doc(slider, value, Some(node));
+        assert!(md.contains("Range: `min` (`10`) to `max` (`50`)"), "{md}");
+
+        let bar = schema.widget("ProgressBar").unwrap();
+        let md = prop_doc(bar, bar.prop("value").unwrap(), None);
+        assert!(md.contains("Range: `0` to `1`"), "{md}");
+
+        let button = schema.widget("Button").unwrap();
+        let md = prop_doc(button, button.prop("bg").unwrap(), None);
+        assert!(md.contains("Default: `#00000000` ![swatch]"), "{md}");
+    }
+
+    #[test]
+    fn components_preview_their_structure() {
+        let source = "param title: \"Untitled\"\n\
+                      Column { gap: 4\n\
+                      \x20 Text \"{title}\" { size: 20 }\n\
+                      \x20 if open { Row { Column { Stack { Text \"deep\" { } } } } } else { slot }\n\
+                      }";
+        let md = component_doc("Card", Some(source));
+        let expected = "**Card** · component\n\n```mkml\n\
+                        param title: \"Untitled\"\n\n\
+                        Column {\n\
+                        \x20   Text \"{title}\" { }\n\
+                        \x20   if open {\n\
+                        \x20       Row {\n\
+                        \x20           Column { … }\n\
+                        \x20       }\n\
+                        \x20   } else {\n\
+                        \x20       slot\n\
+                        \x20   }\n\
+                        }\n```";
+        assert_eq!(md, expected);
+
+        assert!(component_doc("Card", Some("Column {")).ends_with("Its file does not parse."));
+        assert!(component_doc("Gauge", None).contains("registered from Rust"));
+    }
+}
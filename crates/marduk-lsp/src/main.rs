@@ -1,8 +1,15 @@
 use tower_lsp::{LspService, Server};
 
+mod actions;
 mod analysis;
 mod backend;
-mod knowledge;
+mod colors;
+mod hover;
+mod navigate;
+mod outline;
+mod semantic;
+mod widgets;
+mod workspace;
 
 use backend::Backend;
 
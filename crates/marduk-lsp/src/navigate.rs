@@ -0,0 +1,309 @@
+//! Symbol navigation: which component or event the cursor is on, and where
+//! else it occurs in a document.
+//!
+//! Unlike [`analysis`](crate::analysis) this works on the parsed document,
+//! so it only answers for files that currently parse.
+
+use marduk_mkml::ast::{Control, DslDocument, Import, Node, Pos, Span, Value};
+use marduk_mkml::schema::{PropKind, Schema};
+use tower_lsp::lsp_types::{Position, Url};
+
+// ── Symbols ───────────────────────────────────────────────────────────────────
+
+/// Something that can be navigated to and renamed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Symbol {
+    /// A component alias: `Sidebar { }`, `import "…" as Sidebar`.
+    Component(String),
+    /// An application event named by an event property: `on_click: launch`.
+    Event(String),
+}
+
+impl Symbol {
+    pub fn name(&self) -> &str {
+        match self {
+            Symbol::Component(name) | Symbol::Event(name) => name,
+        }
+    }
+}
+
+/// One place a symbol's name is written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Occurrence {
+    /// The name itself — without quotes for a quoted event name.
+    pub span: Span,
+    /// Whether this is the `import … as Alias` that introduces a component.
+    pub declaration: bool,
+}
+
+/// The symbol under byte `offset`, with its occurrence there.
+pub fn symbol_at(doc: &DslDocument, schema: &Schema, offset: usize) -> Option<(Symbol, Occurrence)> {
+    if let Some(import) = doc.imports.iter().find(|i| i.alias_span.contains(offset)) {
+        let occurrence = Occurrence { span: import.alias_span, declaration: true };
+        return Some((Symbol::Component(import.alias.clone()), occurrence));
+    }
+    let mut found = None;
+    walk(&doc.root, &mut |node| {
+        if found.is_some() {
+            return;
+        }
+        if node.name_span.contains(offset) && is_component(doc, schema, node) {
+            let occurrence = Occurrence { span: node.name_span, declaration: false };
+            found = Some((Symbol::Component(node.widget.clone()), occurrence));
+            return;
+        }
+        for (name, span) in events(schema, node) {
+            if span.contains(offset) {
+                found = Some((Symbol::Event(name.to_string()), Occurrence { span, declaration: false }));
+            }
+        }
+    });
+    found
+}
+
+/// Every occurrence of `symbol` in `doc`, in source order.
+pub fn occurrences(doc: &DslDocument, schema: &Schema, symbol: &Symbol) -> Vec<Occurrence> {
+    let mut out = Vec::new();
+    match symbol {
+        Symbol::Component(alias) => {
+            out.extend(
+                doc.imports
+                    .iter()
+                    .filter(|i| &i.alias == alias)
+                    .map(|i| Occurrence { span: i.alias_span, declaration: true }),
+            );
+            walk(&doc.root, &mut |node| {
+                if &node.widget == alias && node.control.is_none() {
+                    out.push(Occurrence { span: node.name_span, declaration: false });
+                }
+            });
+        }
+        Symbol::Event(event) => walk(&doc.root, &mut |node| {
+            out.extend(
+                events(schema, node)
+                    .filter(|(name, _)| name == event)
+                    .map(|(_, span)| Occurrence { span, declaration: false }),
+            );
+        }),
+    }
+    out
+}
+
+/// The import that introduces component `alias`, if the document has one.
+pub fn import_of<'d>(doc: &'d DslDocument, alias: &str) -> Option<&'d Import> {
+    doc.imports.iter().find(|i| i.alias == alias)
+}
+
+/// The file component `alias` names in the document at `uri`, resolved from
+/// its import; `None` when the document does not import it.
+pub fn alias_target(doc: &DslDocument, uri: &Url, alias: &str) -> Option<Url> {
+    import_of(doc, alias).and_then(|import| uri.join(&import.path).ok())
+}
+
+/// Whether `symbol`, found in `origin`, means the same thing in `here` —
+/// both given as a document and its URI. Events are global. An alias is
+/// local to its file, so it matches only where it imports the same target;
+/// one `origin` does not import, e.g. a widget registered from Rust, stays
+/// within `origin`.
+pub fn same_symbol(symbol: &Symbol, origin: (&Url, &DslDocument), here: (&Url, &DslDocument)) -> bool {
+    match symbol {
+        Symbol::Event(_) => true,
+        Symbol::Component(_) if origin.0 == here.0 => true,
+        Symbol::Component(alias) => {
+            let wanted = alias_target(origin.1, origin.0, alias);
+            wanted.is_some() && alias_target(here.1, here.0, alias) == wanted
+        }
+    }
+}
+
+/// Whether `name` can replace a component alias or event identifier.
+pub fn is_valid_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || c == '_')
+}
+
+// ── Positions ─────────────────────────────────────────────────────────────────
+
+// LSP columns count UTF-16 code units — the protocol's default encoding,
+// and the only one the server speaks. The parser counts characters.
+
+/// Byte offset of an LSP position (0-based line, UTF-16 column).
+pub fn offset_at(text: &str, pos: &Position) -> usize {
+    let mut offset = 0;
+    for (i, line) in text.split_inclusive('\n').enumerate() {
+        if i == pos.line as usize {
+            return offset + column_offset(line.trim_end_matches('\n'), pos.character);
+        }
+        offset += line.len();
+    }
+    offset
+}
+
+/// Byte offset into `line` of UTF-16 column `character`, clamped to the end
+/// of the line. A column inside a surrogate pair moves past the character.
+pub fn column_offset(line: &str, character: u32) -> usize {
+    let mut units = 0;
+    for (b, c) in line.char_indices() {
+        if units >= character as usize {
+            return b;
+        }
+        units += c.len_utf16();
+    }
+    line.len()
+}
+
+/// The LSP position of parser position `pos` in `text`, the source it came from.
+pub fn position_at(text: &str, pos: Pos) -> Position {
+    let before = &text[..pos.offset];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    let col = before[line_start..].encode_utf16().count();
+    Position::new(pos.line.saturating_sub(1) as u32, col as u32)
+}
+
+// ── Tree helpers ──────────────────────────────────────────────────────────────
+
+/// Calls `f` on every widget node, including those inside `for` / `if` /
+/// `slot` blocks.
+pub fn walk<'d>(node: &'d Node, f: &mut impl FnMut(&'d Node)) {
+    if node.control.is_none() {
+        f(node);
+    }
+    if let Some(Control::If { otherwise, .. }) = &node.control {
+        for child in otherwise {
+            walk(child, f);
+        }
+    }
+    for child in &node.children {
+        walk(child, f);
+    }
+}
+
+/// A node naming a component rather than a built-in widget.
+pub fn is_component(doc: &DslDocument, schema: &Schema, node: &Node) -> bool {
+    node.control.is_none() && (import_of(doc, &node.widget).is_some() || schema.widget(&node.widget).is_none())
+}
+
+/// The event names `node` mentions, with the span of each name.
+///
+/// Event properties are those the schema marks as events; on widgets it
+/// does not know, any `on_*` property.
+pub fn events<'n>(schema: &Schema, node: &'n Node) -> impl Iterator<Item = (&'n str, Span)> {
+    let widget = schema.widget(&node.widget);
+    node.props.iter().filter_map(move |prop| {
+        let is_event = match widget {
+            Some(w) => w.prop(&prop.key).is_some_and(|p| p.kind == PropKind::Event),
+            None => prop.key.starts_with("on_"),
+        };
+        match &prop.value {
+            Value::Ident(name) if is_event => Some((name.as_str(), prop.value_span)),
+            Value::Str(name) if is_event => Some((name.as_str(), unquoted(prop.value_span))),
+            _ => None,
+        }
+    })
+}
+
+/// The span inside a single-line quoted string.
+fn unquoted(mut span: Span) -> Span {
+    span.start.offset += 1;
+    span.start.col += 1;
+    span.end.offset -= 1;
+    span.end.col -= 1;
+    span
+}
+
+// ── tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SRC: &str = r#"import "sidebar.mkml" as Sidebar
+Column {
+    Sidebar { }
+    Button "Go" { on_click: launch }
+    if busy { Button "Stop" { on_click: "launch" } }
+    Header { on_close: launch }
+}"#;
+
+    fn at(needle: &str, nth: usize) -> usize {
+        SRC.match_indices(needle).nth(nth).unwrap().0
+    }
+
+    fn text(span: Span) -> &'static str {
+        &SRC[span.start.offset..span.end.offset]
+    }
+
+    #[test]
+    fn component_occurrences() {
+        let doc = marduk_mkml::parse_str(SRC).unwrap();
+        let schema = Schema::new();
+        let (symbol, here) = symbol_at(&doc, &schema, at("Sidebar", 1) + 2).unwrap();
+        assert_eq!(symbol, Symbol::Component("Sidebar".into()));
+        assert!(!here.declaration);
+
+        let all = occurrences(&doc, &schema, &symbol);
+        assert_eq!(all.len(), 2);
+        assert!(all[0].declaration);
+        assert!(all.iter().all(|o| text(o.span) == "Sidebar"));
+    }
+
+    #[test]
+    fn event_occurrences_include_quoted_and_unknown_widgets() {
+        let doc = marduk_mkml::parse_str(SRC).unwrap();
+        let schema = Schema::new();
+        let (symbol, _) = symbol_at(&doc, &schema, at("launch", 0)).unwrap();
+        assert_eq!(symbol, Symbol::Event("launch".into()));
+        let all = occurrences(&doc, &schema, &symbol);
+        assert_eq!(all.len(), 3);
+        assert!(all.iter().all(|o| text(o.span) == "launch"));
+    }
+
+    #[test]
+    fn aliases_match_by_import_target() {
+        let file = |name: &str| Url::parse(&format!("file:///ws/ui/{name}")).unwrap();
+        let parse = |src: &str| marduk_mkml::parse_str(src).unwrap();
+        let header = (file("header.mkml"), parse("import \"components/fleet.mkml\" as Panel\nPanel { }"));
+        let settings = (file("settings.mkml"), parse("import \"components/routing.mkml\" as Panel\nPanel { }"));
+        let nested = (file("pages/status.mkml"), parse("import \"../components/fleet.mkml\" as Panel\nPanel { }"));
+        let plain = (file("plain.mkml"), parse("Panel { }"));
+        let all = [&header, &settings, &nested, &plain];
+        let panel = Symbol::Component("Panel".into());
+        let matching = |origin: &(Url, DslDocument)| -> Vec<&str> {
+            all.iter()
+                .filter(|here| same_symbol(&panel, (&origin.0, &origin.1), (&here.0, &here.1)))
+                .map(|here| here.0.path())
+                .collect()
+        };
+
+        assert_eq!(matching(&header), ["/ws/ui/header.mkml", "/ws/ui/pages/status.mkml"]);
+        assert_eq!(matching(&settings), ["/ws/ui/settings.mkml"]);
+        assert_eq!(matching(&plain), ["/ws/ui/plain.mkml"]);
+
+        let launch = Symbol::Event("launch".into());
+        assert!(same_symbol(&launch, (&header.0, &header.1), (&plain.0, &plain.1)));
+    }
+
+    #[test]
+    fn builtin_widgets_are_not_symbols() {
+        let doc = marduk_mkml::parse_str(SRC).unwrap();
+        assert_eq!(symbol_at(&doc, &Schema::new(), at("Column", 0)), None);
+    }
+
+    #[test]
+    fn positions_to_offsets() {
+        assert_eq!(offset_at("ab\ncd", &Position::new(1, 1)), 4);
+        assert_eq!(offset_at("ab\ncd", &Position::new(0, 9)), 2);
+        assert_eq!(offset_at("é\nx", &Position::new(1, 0)), 3);
+        // `𝄞` is two UTF-16 units; a column inside it moves past it.
+        assert_eq!(offset_at("𝄞x", &Position::new(0, 2)), 4);
+        assert_eq!(offset_at("𝄞x", &Position::new(0, 1)), 4);
+    }
+
+    #[test]
+    fn positions_count_utf16_units() {
+        let src = "a\n𝄞{";
+        let brace = Pos { offset: src.find('{').unwrap(), line: 2, col: 2 };
+        assert_eq!(position_at(src, brace), Position::new(1, 2));
+    }
+}
@@ -0,0 +1,147 @@
+//! Document outline and folding.
+
+use marduk_mkml::ast::{Control, DslDocument, Node, Value};
+use marduk_mkml::lexer::{Lexer, Token};
+use marduk_mkml::schema::Schema;
+use tower_lsp::lsp_types::{DocumentSymbol, FoldingRange, FoldingRangeKind, Range, SymbolKind};
+
+use crate::backend::span_range;
+use crate::navigate::is_component;
+
+// ── Document symbols ──────────────────────────────────────────────────────────
+
+/// Imports and params, then the widget tree as a nested outline.
+///
+/// `text` is the source `doc` was parsed from.
+pub fn document_symbols(text: &str, doc: &DslDocument, schema: &Schema) -> Vec<DocumentSymbol> {
+    let imports = doc.imports.iter().map(|i| {
+        let (span, name_span) = (span_range(text, i.span), span_range(text, i.alias_span));
+        symbol(i.alias.clone(), Some(i.path.clone()), SymbolKind::MODULE, span, name_span, vec![])
+    });
+    let params = doc.params.iter().map(|p| {
+        let span = span_range(text, p.span);
+        symbol(p.name.clone(), None, SymbolKind::VARIABLE, span, span, vec![])
+    });
+    imports
+        .chain(params)
+        .chain(std::iter::once(node_symbol(text, doc, schema, &doc.root)))
+        .collect()
+}
+
+fn node_symbol(text: &str, doc: &DslDocument, schema: &Schema, node: &Node) -> DocumentSymbol {
+    let mut children: Vec<&Node> = node.children.iter().collect();
+    let (name, detail, kind) = match &node.control {
+        None => {
+            // `Text "Save"` reads better than `Text`; fall back to the `id`.
+            let detail = match &node.content {
+                Some(Value::Str(s)) => Some(format!("\"{s}\"")),
+                _ => node.prop_str("id").map(|id| format!("#{id}")),
+            };
+            let kind = if is_component(doc, schema, node) { SymbolKind::MODULE } else { SymbolKind::CLASS };
+            (node.widget.clone(), detail, kind)
+        }
+        Some(Control::For { item, list }) => (format!("for {item} in {list}"), None, SymbolKind::OPERATOR),
+        Some(Control::If { cond, otherwise }) => {
+            children.extend(otherwise);
+            (format!("if {cond}"), None, SymbolKind::OPERATOR)
+        }
+        Some(Control::Slot) => ("slot".to_string(), None, SymbolKind::OPERATOR),
+    };
+    let children = children.into_iter().map(|c| node_symbol(text, doc, schema, c)).collect();
+    let (span, name_span) = (span_range(text, node.span), span_range(text, node.name_span));
+    symbol(name, detail, kind, span, name_span, children)
+}
+
+#[allow(deprecated)] // `DocumentSymbol::deprecated` has to be spelled out
+fn symbol(
+    name: String,
+    detail: Option<String>,
+    kind: SymbolKind,
+    range: Range,
+    selection_range: Range,
+    children: Vec<DocumentSymbol>,
+) -> DocumentSymbol {
+    DocumentSymbol {
+        name,
+        detail,
+        kind,
+        tags: None,
+        deprecated: None,
+        range,
+        selection_range,
+        children: (!children.is_empty()).then_some(children),
+    }
+}
+
+// ── Folding ───────────────────────────────────────────────────────────────────
+
+/// Every `{ }` block and block comment spanning more than one line.
+///
+/// Works from the lexer alone, so blocks still fold while the document has
+/// a parse error. The line of the closing `}` stays visible.
+pub fn folding_ranges(text: &str) -> Vec<FoldingRange> {
+    let Ok((tokens, comments)) = Lexer::new(text).tokenize_with_comments() else {
+        return vec![];
+    };
+
+    let mut ranges = Vec::new();
+    let mut open = Vec::new();
+    for t in &tokens {
+        match t.token {
+            Token::LBrace => open.push(t.line),
+            Token::RBrace => {
+                if let Some(start) = open.pop()
+                    && t.line > start + 1
+                {
+                    ranges.push(fold(start, t.line - 1, None));
+                }
+            }
+            _ => {}
+        }
+    }
+    for c in comments.iter().filter(|c| c.span.end.line > c.span.start.line) {
+        ranges.push(fold(c.span.start.line, c.span.end.line, Some(FoldingRangeKind::Comment)));
+    }
+    ranges.sort_by_key(|r| r.start_line);
+    ranges
+}
+
+/// A fold over 1-based lines `start..=end`.
+fn fold(start: usize, end: usize, kind: Option<FoldingRangeKind>) -> FoldingRange {
+    FoldingRange {
+        start_line: (start - 1) as u32,
+        end_line: (end - 1) as u32,
+        kind,
+        ..Default::default()
+    }
+}
+
+// ── tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn outline_follows_the_widget_tree() {
+        let src = "import \"side.mkml\" as Side\nColumn {\n  id: main\n  Text \"hi\"\n  if busy { Side { } } else { Text \"idle\" }\n}";
+        let doc = marduk_mkml::parse_str(src).unwrap();
+        let symbols = document_symbols(src, &doc, &Schema::new());
+        assert_eq!(symbols.iter().map(|s| s.name.as_str()).collect::<Vec<_>>(), ["Side", "Column"]);
+
+        let column = &symbols[1];
+        assert_eq!(column.detail.as_deref(), Some("#main"));
+        let children = column.children.as_ref().unwrap();
+        assert_eq!(children[0].detail.as_deref(), Some("\"hi\""));
+        assert_eq!(children[1].name, "if busy");
+        let branches = children[1].children.as_ref().unwrap();
+        assert_eq!((branches[0].kind, branches[1].kind), (SymbolKind::MODULE, SymbolKind::CLASS));
+    }
+
+    #[test]
+    fn folds_multi_line_blocks_even_with_errors() {
+        let src = "Column {\n  Row { gap: 1 }\n  Row {\n    gap: 2\n  }\n/* a\n b */\n  Text 5\n}";
+        let lines: Vec<_> = folding_ranges(src).iter().map(|r| (r.start_line, r.end_line)).collect();
+        assert_eq!(lines, [(0, 7), (2, 3), (5, 6)]);
+    }
+}
@@ -0,0 +1,224 @@
+//! Semantic tokens: highlighting from the real lexer.
+//!
+//! Every token is classified by what it is in the parsed document — a
+//! widget or a component, a property key, an event name, a binding path.
+//! While the document has a parse error, tokens fall back to what their
+//! neighbours suggest (`ident :` is a property, `ident {` a widget).
+
+use std::collections::HashMap;
+
+use marduk_mkml::ast::{Binding, Comment, DslDocument, Span, Value};
+use marduk_mkml::lexer::{Lexer, Token};
+use marduk_mkml::schema::{PropKind, Schema};
+use tower_lsp::lsp_types::{SemanticToken, SemanticTokenType, SemanticTokensLegend};
+
+use crate::navigate::{events, is_component, position_at, walk};
+
+// ── Legend ────────────────────────────────────────────────────────────────────
+
+/// Token classes, in legend order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Widget,
+    Component,
+    Property,
+    Event,
+    Variable,
+    EnumMember,
+    Keyword,
+    String,
+    Number,
+    Color,
+    Comment,
+}
+
+/// Editors without a `color` token type fall back to the default colour;
+/// the VS Code extension maps it to `constant.other.color`.
+const COLOR: SemanticTokenType = SemanticTokenType::new("color");
+
+pub fn legend() -> SemanticTokensLegend {
+    SemanticTokensLegend {
+        token_types: vec![
+            SemanticTokenType::CLASS,
+            SemanticTokenType::TYPE,
+            SemanticTokenType::PROPERTY,
+            SemanticTokenType::EVENT,
+            SemanticTokenType::VARIABLE,
+            SemanticTokenType::ENUM_MEMBER,
+            SemanticTokenType::KEYWORD,
+            SemanticTokenType::STRING,
+            SemanticTokenType::NUMBER,
+            COLOR,
+            SemanticTokenType::COMMENT,
+        ],
+        token_modifiers: vec![],
+    }
+}
+
+// ── Tokens ────────────────────────────────────────────────────────────────────
+
+/// Encoded semantic tokens for `text`, or `None` if it does not even lex.
+pub fn semantic_tokens(text: &str, schema: &Schema) -> Option<Vec<SemanticToken>> {
+    let (tokens, comments) = Lexer::new(text).tokenize_with_comments().ok()?;
+    let doc = marduk_mkml::parse_str(text).ok();
+    let known = doc.as_ref().map(|d| classify(d, schema)).unwrap_or_default();
+
+    let mut out: Vec<(Span, Kind)> = comments.iter().map(|c: &Comment| (c.span, Kind::Comment)).collect();
+    // Inside `for x in list` / `if cond`: the names are data paths.
+    let mut control_header = false;
+    for (i, t) in tokens.iter().enumerate() {
+        let next = tokens.get(i + 1).map(|t| &t.token);
+        let kind = match &t.token {
+            Token::Str(_) | Token::Template(_) => Some(Kind::String),
+            Token::Number(_)               => Some(Kind::Number),
+            Token::Color(_)                => Some(Kind::Color),
+            Token::ThemeRef(_)             => Some(Kind::Variable),
+            Token::Import | Token::As      => Some(Kind::Keyword),
+            Token::LBrace => {
+                control_header = false;
+                None
+            }
+            Token::Ident(word) => known.names.get(&t.span.start.offset).copied().or_else(|| {
+                if known.bindings.iter().any(|b| b.contains(t.span.start.offset)) {
+                    return Some(Kind::Variable);
+                }
+                match (word.as_str(), next) {
+                    ("for" | "if", _) => {
+                        control_header = true;
+                        Some(Kind::Keyword)
+                    }
+                    ("in" | "else" | "slot" | "param", _) => Some(Kind::Keyword),
+                    _ if control_header => Some(Kind::Variable),
+                    (_, Some(Token::Colon)) => Some(Kind::Property),
+                    (_, Some(Token::LBrace | Token::Str(_) | Token::Template(_))) => Some(Kind::Widget),
+                    _ => None,
+                }
+            }),
+            _ => None,
+        };
+        if let Some(kind) = kind {
+            out.push((t.span, kind));
+        }
+    }
+    out.sort_by_key(|(span, _)| span.start.offset);
+    Some(encode(text, &out))
+}
+
+/// What the parsed document says about the tokens it names.
+#[derive(Default)]
+struct Known {
+    /// Token start offset → class.
+    names:    HashMap<usize, Kind>,
+    /// `{path}` values; every name inside is a data path.
+    bindings: Vec<Span>,
+}
+
+fn classify(doc: &DslDocument, schema: &Schema) -> Known {
+    let mut known = Known::default();
+    for import in &doc.imports {
+        known.names.insert(import.alias_span.start.offset, Kind::Component);
+    }
+    walk(&doc.root, &mut |node| {
+        let kind = if is_component(doc, schema, node) { Kind::Component } else { Kind::Widget };
+        known.names.insert(node.name_span.start.offset, kind);
+        for prop in &node.props {
+            known.names.insert(prop.key_span.start.offset, Kind::Property);
+            match &prop.value {
+                Value::Binding(Binding::Path(_)) => known.bindings.push(prop.value_span),
+                Value::Ident(_) if schema.prop(&node.widget, &prop.key).is_some_and(|p| {
+                    matches!(p.kind, PropKind::Enum(_) | PropKind::Size)
+                }) => {
+                    known.names.insert(prop.value_span.start.offset, Kind::EnumMember);
+                }
+                _ => {}
+            }
+        }
+        for (_, span) in events(schema, node) {
+            known.names.insert(span.start.offset, Kind::Event);
+        }
+    });
+    known
+}
+
+/// Delta-encodes `spans` (sorted), splitting multi-line ones per line.
+fn encode(text: &str, spans: &[(Span, Kind)]) -> Vec<SemanticToken> {
+    let mut out = Vec::new();
+    let (mut prev_line, mut prev_col) = (0, 0);
+    for &(span, kind) in spans {
+        let pieces = text[span.start.offset..span.end.offset].split('\n');
+        let first = position_at(text, span.start);
+        for (line, (i, piece)) in (first.line..).zip(pieces.enumerate()) {
+            let col = if i == 0 { first.character } else { 0 };
+            let length = piece.trim_end_matches('\r').encode_utf16().count() as u32;
+            if length > 0 {
+                let delta_start = if line == prev_line { col - prev_col } else { col };
+                out.push(SemanticToken {
+                    delta_line: line - prev_line,
+                    delta_start,
+                    length,
+                    token_type: kind as u32,
+                    token_modifiers_bitset: 0,
+                });
+                (prev_line, prev_col) = (line, col);
+            }
+        }
+    }
+    out
+}
+
+// ── tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `(line, col, length, kind)` with absolute positions.
+    fn decode(src: &str) -> Vec<(u32, u32, u32, Kind)> {
+        let kinds = [
+            Kind::Widget, Kind::Component, Kind::Property, Kind::Event, Kind::Variable, Kind::EnumMember,
+            Kind::Keyword, Kind::String, Kind::Number, Kind::Color, Kind::Comment,
+        ];
+        let (mut line, mut col) = (0, 0);
+        semantic_tokens(src, &Schema::new())
+            .unwrap()
+            .into_iter()
+            .map(|t| {
+                if t.delta_line > 0 {
+                    col = 0;
+                }
+                line += t.delta_line;
+                col += t.delta_start;
+                (line, col, t.length, kinds[t.token_type as usize])
+            })
+            .collect()
+    }
+
+    #[test]
+    fn classifies_from_the_document() {
+        let src = "import \"s.mkml\" as Side\nColumn { align: center  // c\n  Button \"Go\" { on_click: launch  bg: #ff0000ff }\n  for t in trucks { Side { v: {t.name} } }\n}";
+        use Kind::*;
+        assert_eq!(decode(src), [
+            (0, 0, 6, Keyword), (0, 7, 8, String), (0, 16, 2, Keyword), (0, 19, 4, Component),
+            (1, 0, 6, Widget), (1, 9, 5, Property), (1, 16, 6, EnumMember), (1, 24, 4, Comment),
+            (2, 2, 6, Widget), (2, 9, 4, String), (2, 16, 8, Property), (2, 26, 6, Event),
+            (2, 34, 2, Property), (2, 38, 9, Color),
+            (3, 2, 3, Keyword), (3, 6, 1, Variable), (3, 8, 2, Keyword), (3, 11, 6, Variable),
+            (3, 20, 4, Component), (3, 27, 1, Property), (3, 31, 1, Variable), (3, 33, 4, Variable),
+        ]);
+    }
+
+    #[test]
+    fn falls_back_to_neighbours_on_parse_error() {
+        use Kind::*;
+        assert_eq!(decode("Column {\n  gap: 8\n  Text \"a\" {\n"), [
+            (0, 0, 6, Widget), (1, 2, 3, Property), (1, 7, 1, Number), (2, 2, 4, Widget), (2, 7, 3, String),
+        ]);
+    }
+
+    #[test]
+    fn multi_line_comments_are_split() {
+        assert_eq!(decode("/* a\nbc */ Row { }"), [
+            (0, 0, 4, Kind::Comment), (1, 0, 5, Kind::Comment), (1, 6, 3, Kind::Widget),
+        ]);
+    }
+}
@@ -0,0 +1,175 @@
+//! Widgets an application registers from Rust, described for the server.
+//!
+//! `Application::widget_schema` and `DslLoader::register_schema` only reach
+//! the running application. So that documents using those widgets check
+//! clean in the editor too, list them in `mkml-widgets.json` at a workspace
+//! root:
+//!
+//! ```json
+//! [
+//!   { "name": "Gauge", "doc": "A round dial.", "props": [
+//!     { "name": "value", "kind": "number", "doc": "Needle position.", "default": "0", "range": ["0", "1"] },
+//!     { "name": "style", "kind": "enum", "values": ["arc", "full"] }
+//!   ] }
+//! ]
+//! ```
+//!
+//! Keys follow [`WidgetSchema`] and [`PropSchema`]: a widget may also set
+//! `content` and `children`, a property `alias_of` and `deprecated`. Kinds
+//! are `number`, `color`, `bool`, `string`, `event`, `font`, `size` and
+//! `enum`. The file is read once, when the server starts.
+
+use std::path::PathBuf;
+
+use marduk_mkml::schema::{PropKind, PropSchema, Schema, WidgetSchema};
+use serde_json::{Map, Value};
+
+/// The file, at a workspace root, listing the application's widgets.
+pub const FILE: &str = "mkml-widgets.json";
+
+/// The built-in widgets plus those listed in [`FILE`] under any of `roots`,
+/// and a message for each file that could not be used.
+pub fn load(roots: &[PathBuf]) -> (Schema, Vec<String>) {
+    let mut schema = Schema::new();
+    let mut errors = Vec::new();
+    for path in roots.iter().map(|root| root.join(FILE)).filter(|p| p.is_file()) {
+        let parsed = std::fs::read_to_string(&path).map_err(|e| e.to_string()).and_then(|s| parse(&s));
+        match parsed {
+            Ok(widgets) => widgets.into_iter().for_each(|w| schema.register(w)),
+            Err(e) => errors.push(format!("{}: {e}", path.display())),
+        }
+    }
+    (schema, errors)
+}
+
+/// The widgets a [`FILE`] lists.
+///
+/// Schemas borrow `'static` names and docs, so these are leaked; the file
+/// is only read once per server.
+pub fn parse(json: &str) -> Result<Vec<WidgetSchema>, String> {
+    let value: Value = serde_json::from_str(json).map_err(|e| e.to_string())?;
+    let Value::Array(widgets) = value else { return Err("expected a list of widgets".into()) };
+    widgets
+        .iter()
+        .enumerate()
+        .map(|(i, w)| widget(w).map_err(|e| format!("widget {}: {e}", i + 1)))
+        .collect()
+}
+
+fn widget(value: &Value) -> Result<WidgetSchema, String> {
+    let fields = object(value)?;
+    let name = string(fields, "name")?.ok_or("missing `name`")?;
+    let mut widget = WidgetSchema::new(name, string(fields, "doc")?.unwrap_or(""));
+    if flag(fields, "content")? {
+        widget = widget.content();
+    }
+    if flag(fields, "children")? {
+        widget = widget.children();
+    }
+    let props = match fields.get("props") {
+        None => Vec::new(),
+        Some(Value::Array(props)) => props
+            .iter()
+            .map(|p| prop(p).map_err(|e| format!("`{name}`: {e}")))
+            .collect::<Result<_, _>>()?,
+        Some(_) => return Err(format!("`{name}`: `props` must be a list")),
+    };
+    Ok(widget.props(props.leak()))
+}
+
+fn prop(value: &Value) -> Result<PropSchema, String> {
+    let fields = object(value)?;
+    let name = string(fields, "name")?.ok_or("property without a `name`")?;
+    let kind = match string(fields, "kind")?.unwrap_or("string") {
+        "number" => PropKind::Number,
+        "color"  => PropKind::Color,
+        "bool"   => PropKind::Bool,
+        "string" => PropKind::Str,
+        "event"  => PropKind::Event,
+        "font"   => PropKind::Font,
+        "size"   => PropKind::Size,
+        "enum"   => PropKind::Enum(strings(fields, "values")?.leak()),
+        other    => return Err(format!("`{name}`: unknown kind `{other}`")),
+    };
+    let mut prop = PropSchema::new(name, kind, string(fields, "doc")?.unwrap_or(""));
+    if let Some(default) = string(fields, "default")? {
+        prop = prop.defaults_to(default);
+    }
+    if fields.contains_key("range") {
+        let [min, max] = strings(fields, "range")?[..] else {
+            return Err(format!("`{name}`: `range` must be `[min, max]`"));
+        };
+        prop = prop.range(min, max);
+    }
+    if let Some(canonical) = string(fields, "alias_of")? {
+        prop = prop.alias_of(canonical);
+    }
+    if flag(fields, "deprecated")? {
+        prop = prop.deprecated();
+    }
+    Ok(prop)
+}
+
+fn object(value: &Value) -> Result<&Map<String, Value>, String> {
+    value.as_object().ok_or_else(|| "expected an object".to_string())
+}
+
+fn string(fields: &Map<String, Value>, key: &str) -> Result<Option<&'static str>, String> {
+    match fields.get(key) {
+        None => Ok(None),
+        Some(Value::String(s)) => Ok(Some(s.clone().leak())),
+        Some(_) => Err(format!("`{key}` must be a string")),
+    }
+}
+
+fn strings(fields: &Map<String, Value>, key: &str) -> Result<Vec<&'static str>, String> {
+    let values = fields.get(key).and_then(Value::as_array);
+    values
+        .and_then(|v| v.iter().map(|s| s.as_str().map(|s| &*s.to_string().leak())).collect())
+        .ok_or_else(|| format!("`{key}` must be a list of strings"))
+}
+
+fn flag(fields: &Map<String, Value>, key: &str) -> Result<bool, String> {
+    match fields.get(key) {
+        None => Ok(false),
+        Some(Value::Bool(b)) => Ok(*b),
+        Some(_) => Err(format!("`{key}` must be `true` or `false`")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::diagnostics;
+
+    const GAUGE: &str = r#"[
+        { "name": "Gauge", "doc": "A round dial.", "props": [
+            { "name": "value", "kind": "number", "default": "0", "range": ["0", "1"] },
+            { "name": "style", "kind": "enum", "values": ["arc", "full"] }
+        ] }
+    ]"#;
+
+    fn messages(text: &str, schema: &Schema) -> Vec<String> {
+        diagnostics(text, schema).into_iter().map(|d| d.message).collect()
+    }
+
+    #[test]
+    fn registered_widgets_check_clean() {
+        let src = "Column { Gauge { value: 0.5  style: arc } }";
+        assert_eq!(messages(src, &Schema::new()), ["unknown widget `Gauge`"]);
+
+        let mut schema = Schema::new();
+        parse(GAUGE).unwrap().into_iter().for_each(|w| schema.register(w));
+        assert!(messages(src, &schema).is_empty());
+        assert_eq!(messages("Gauge { style: ring }", &schema), [
+            "invalid value `ring` for `style`; expected one of `arc`, `full`",
+        ]);
+    }
+
+    #[test]
+    fn reports_where_a_file_is_wrong() {
+        assert_eq!(parse("{}").unwrap_err(), "expected a list of widgets");
+        let bad_kind = r#"[{ "name": "A" }, { "name": "Gauge", "props": [{ "name": "v", "kind": "nubmer" }] }]"#;
+        assert_eq!(parse(bad_kind).unwrap_err(), "widget 2: `Gauge`: `v`: unknown kind `nubmer`");
+    }
+}
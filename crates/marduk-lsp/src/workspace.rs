@@ -0,0 +1,302 @@
+//! Workspace index: every `.mkml` file under the workspace roots and the
+//! imports between them.
+//!
+//! Open documents hold the editor's text; the rest are read from disk and
+//! re-read when the client reports a change. Imports resolve relative to
+//! the importing file, so `import "sidebar.mkml"` in `ui/main.mkml` names
+//! `ui/sidebar.mkml`.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+
+use marduk_mkml::ast::Span;
+use tower_lsp::lsp_types::{TextDocumentContentChangeEvent, Url};
+
+use crate::navigate::offset_at;
+
+// ── Workspace ─────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Default)]
+pub struct Workspace {
+    roots: Vec<PathBuf>,
+    files: HashMap<Url, File>,
+}
+
+#[derive(Debug)]
+struct File {
+    text:    String,
+    /// Owned by the editor: disk changes are ignored until it is closed.
+    open:    bool,
+    /// From the last version that parsed, so a typo does not drop them.
+    imports: Vec<Link>,
+}
+
+/// One `import "…" as …`, resolved.
+#[derive(Debug, Clone)]
+struct Link {
+    target: Url,
+    /// The path as written.
+    path:   String,
+    /// Source range of the quoted path.
+    span:   Span,
+}
+
+impl Workspace {
+    pub fn set_roots(&mut self, roots: Vec<PathBuf>) {
+        self.roots = roots;
+    }
+
+    /// Loads every `.mkml` file under the roots, keeping open documents.
+    pub fn scan(&mut self) {
+        let paths: Vec<PathBuf> = self.roots.iter().flat_map(|root| mkml_files(root)).collect();
+        for path in paths {
+            if let Ok(uri) = Url::from_file_path(&path)
+                && !self.is_open(&uri)
+            {
+                self.reload(&uri);
+            }
+        }
+    }
+
+    pub fn open(&mut self, uri: Url, text: String) {
+        self.set(uri, text, true);
+    }
+
+    /// Applies edits from `textDocument/didChange`, in order.
+    pub fn edit(&mut self, uri: &Url, changes: Vec<TextDocumentContentChangeEvent>) {
+        let Some(file) = self.files.get(uri) else { return };
+        let mut text = file.text.clone();
+        for change in changes {
+            apply_change(&mut text, change);
+        }
+        self.set(uri.clone(), text, true);
+    }
+
+    /// Hands the file back to the disk.
+    pub fn close(&mut self, uri: &Url) {
+        if let Some(file) = self.files.get_mut(uri) {
+            file.open = false;
+        }
+        self.reload(uri);
+    }
+
+    /// Re-reads a file that is not open, dropping it if it no longer exists.
+    pub fn reload(&mut self, uri: &Url) {
+        if self.is_open(uri) {
+            return;
+        }
+        match uri.to_file_path().ok().and_then(|p| std::fs::read_to_string(p).ok()) {
+            Some(text) => self.set(uri.clone(), text, false),
+            None => {
+                self.files.remove(uri);
+            }
+        }
+    }
+
+    pub fn text(&self, uri: &Url) -> Option<&str> {
+        self.files.get(uri).map(|f| f.text.as_str())
+    }
+
+    pub fn is_open(&self, uri: &Url) -> bool {
+        self.files.get(uri).is_some_and(|f| f.open)
+    }
+
+    /// Every indexed file with its current text.
+    pub fn files(&self) -> impl Iterator<Item = (&Url, &str)> {
+        self.files.iter().map(|(uri, f)| (uri, f.text.as_str()))
+    }
+
+    /// Every indexed file.
+    pub fn uris(&self) -> impl Iterator<Item = &Url> {
+        self.files.keys()
+    }
+
+    /// Files that import `uri`, directly or through other files.
+    pub fn dependants(&self, uri: &Url) -> Vec<Url> {
+        let mut found: Vec<Url> = Vec::new();
+        let mut queue = VecDeque::from([uri.clone()]);
+        while let Some(target) = queue.pop_front() {
+            for (importer, file) in &self.files {
+                if file.imports.iter().any(|l| l.target == target)
+                    && importer != uri
+                    && !found.contains(importer)
+                {
+                    found.push(importer.clone());
+                    queue.push_back(importer.clone());
+                }
+            }
+        }
+        found
+    }
+
+    /// Imports of `uri` that name a missing file or lead back to `uri`.
+    pub fn import_issues(&self, uri: &Url) -> Vec<(Span, String)> {
+        let Some(file) = self.files.get(uri) else { return vec![] };
+        let mut out = Vec::new();
+        for link in &file.imports {
+            if !self.exists(&link.target) {
+                out.push((link.span, format!("cannot find `{}`", link.path)));
+            } else if let Some(cycle) = self.path(&link.target, uri) {
+                let names: Vec<&str> = std::iter::once(uri).chain(&cycle).map(file_name).collect();
+                out.push((link.span, format!("circular import: {}", names.join(" → "))));
+            }
+        }
+        out
+    }
+
+    // ── internal ──────────────────────────────────────────────────────────────
+
+    fn set(&mut self, uri: Url, text: String, open: bool) {
+        let imports = match marduk_mkml::parse_str(&text) {
+            Ok(doc) => doc
+                .imports
+                .iter()
+                .filter_map(|i| {
+                    let target = uri.join(&i.path).ok()?;
+                    Some(Link { target, path: i.path.clone(), span: i.path_span })
+                })
+                .collect(),
+            Err(_) => self.files.get(&uri).map(|f| f.imports.clone()).unwrap_or_default(),
+        };
+        self.files.insert(uri, File { text, open, imports });
+    }
+
+    /// Indexed, or on disk outside the roots.
+    fn exists(&self, uri: &Url) -> bool {
+        self.files.contains_key(uri) || uri.to_file_path().is_ok_and(|p| p.is_file())
+    }
+
+    /// The import chain from `from` to `to` (both included), if there is one.
+    fn path(&self, from: &Url, to: &Url) -> Option<Vec<Url>> {
+        let mut came_from: HashMap<&Url, &Url> = HashMap::new();
+        let mut seen: HashSet<&Url> = HashSet::from([from]);
+        let mut queue = VecDeque::from([from]);
+        while let Some(at) = queue.pop_front() {
+            if at == to {
+                let mut chain = vec![at.clone()];
+                let mut cur = at;
+                while let Some(prev) = came_from.get(cur) {
+                    chain.push((*prev).clone());
+                    cur = prev;
+                }
+                chain.reverse();
+                return Some(chain);
+            }
+            for link in self.files.get(at).map_or(&[][..], |f| &f.imports) {
+                if seen.insert(&link.target) {
+                    came_from.insert(&link.target, at);
+                    queue.push_back(&link.target);
+                }
+            }
+        }
+        None
+    }
+}
+
+// ── Helpers ───────────────────────────────────────────────────────────────────
+
+/// Applies one `didChange` entry; one without a range replaces the text.
+fn apply_change(text: &mut String, change: TextDocumentContentChangeEvent) {
+    match change.range {
+        Some(range) => {
+            let start = offset_at(text, &range.start);
+            let end = offset_at(text, &range.end).max(start);
+            text.replace_range(start..end, &change.text);
+        }
+        None => *text = change.text,
+    }
+}
+
+/// `.mkml` files under `dir`, skipping hidden directories and `target`.
+fn mkml_files(dir: &Path) -> Vec<PathBuf> {
+    let mut out = Vec::new();
+    let Ok(entries) = std::fs::read_dir(dir) else { return out };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if path.is_dir() {
+            if !name.starts_with('.') && name != "target" {
+                out.extend(mkml_files(&path));
+            }
+        } else if path.extension().is_some_and(|e| e == "mkml") {
+            out.push(path);
+        }
+    }
+    out
+}
+
+fn file_name(uri: &Url) -> &str {
+    uri.path_segments().and_then(|mut s| s.next_back()).unwrap_or(uri.as_str())
+}
+
+// ── tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tower_lsp::lsp_types::{Position, Range};
+
+    fn uri(name: &str) -> Url {
+        Url::parse(&format!("file:///nonexistent-marduk-ws/ui/{name}")).unwrap()
+    }
+
+    fn messages(ws: &Workspace, name: &str) -> Vec<String> {
+        ws.import_issues(&uri(name)).into_iter().map(|(_, m)| m).collect()
+    }
+
+    #[test]
+    fn resolves_imports_relative_to_the_importer() {
+        let mut ws = Workspace::default();
+        ws.open(uri("main.mkml"), "import \"parts/side.mkml\" as Side\nSide { }".into());
+        assert_eq!(messages(&ws, "main.mkml"), ["cannot find `parts/side.mkml`"]);
+
+        ws.open(uri("parts/side.mkml"), "Column { }".into());
+        assert!(messages(&ws, "main.mkml").is_empty());
+        assert_eq!(ws.dependants(&uri("parts/side.mkml")), [uri("main.mkml")]);
+    }
+
+    #[test]
+    fn reports_cycles() {
+        let mut ws = Workspace::default();
+        ws.open(uri("a.mkml"), "import \"b.mkml\" as B\nB { }".into());
+        ws.open(uri("b.mkml"), "import \"c.mkml\" as C\nC { }".into());
+        ws.open(uri("c.mkml"), "import \"a.mkml\" as A\nA { }".into());
+        assert_eq!(messages(&ws, "a.mkml"), ["circular import: a.mkml → b.mkml → c.mkml → a.mkml"]);
+
+        let mut dependants = ws.dependants(&uri("a.mkml"));
+        dependants.sort();
+        assert_eq!(dependants, [uri("b.mkml"), uri("c.mkml")]);
+    }
+
+    #[test]
+    fn incremental_edits() {
+        let mut ws = Workspace::default();
+        ws.open(uri("a.mkml"), "Column {\n  gap: 8\n}".into());
+        let change = |range: Option<Range>, text: &str| TextDocumentContentChangeEvent {
+            range,
+            range_length: None,
+            text: text.into(),
+        };
+        ws.edit(&uri("a.mkml"), vec![
+            change(Some(Range::new(Position::new(1, 7), Position::new(1, 8))), "12"),
+            change(Some(Range::new(Position::new(0, 0), Position::new(0, 6))), "Row"),
+        ]);
+        assert_eq!(ws.text(&uri("a.mkml")), Some("Row {\n  gap: 12\n}"));
+        ws.edit(&uri("a.mkml"), vec![change(None, "Text \"x\"")]);
+        assert_eq!(ws.text(&uri("a.mkml")), Some("Text \"x\""));
+
+        // Columns count UTF-16 units: `🚀` is two, so `x` is at 9, not 8.
+        ws.open(uri("b.mkml"), "Text \"🚀 x\"".into());
+        ws.edit(&uri("b.mkml"), vec![change(Some(Range::new(Position::new(0, 9), Position::new(0, 10))), "y")]);
+        assert_eq!(ws.text(&uri("b.mkml")), Some("Text \"🚀 y\""));
+    }
+
+    #[test]
+    fn parse_errors_keep_the_last_imports() {
+        let mut ws = Workspace::default();
+        ws.open(uri("a.mkml"), "import \"b.mkml\" as B\nB { }".into());
+        ws.open(uri("a.mkml"), "import \"b.mkml\" as B\nB {".into());
+        assert_eq!(messages(&ws, "a.mkml"), ["cannot find `b.mkml`"]);
+    }
+}
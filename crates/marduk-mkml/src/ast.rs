@@ -1,6 +1,54 @@
+// ── Span ──────────────────────────────────────────────────────────────────
+
+/// A position in the source text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Pos {
+    /// Byte offset from the start of the source.
+    pub offset: usize,
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column number, in characters.
+    pub col: usize,
+}
+
+impl std::fmt::Display for Pos {
+    /// `line:col`, as used in diagnostics.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.line, self.col)
+    }
+}
+
+/// A half-open range of source text, `start..end`.
+///
+/// Nodes built by a host rather than parsed (e.g. expanded `for` bodies keep
+/// their template's spans; synthesized nodes use `Span::default()`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Span {
+    pub start: Pos,
+    pub end: Pos,
+}
+
+impl Span {
+    pub fn new(start: Pos, end: Pos) -> Self {
+        Self { start, end }
+    }
+
+    /// The smallest span covering both `self` and `other`.
+    pub fn to(self, other: Span) -> Span {
+        let start = if other.start.offset < self.start.offset { other.start } else { self.start };
+        let end = if other.end.offset > self.end.offset { other.end } else { self.end };
+        Span { start, end }
+    }
+
+    /// Whether the byte `offset` falls inside the span.
+    pub fn contains(&self, offset: usize) -> bool {
+        self.start.offset <= offset && offset < self.end.offset
+    }
+}
+
 // ── Value ─────────────────────────────────────────────────────────────────
 
-/// A literal value in a property.
+/// A property value: a literal, or a [`Binding`] resolved by the host at build time.
 #[derive(Debug, Clone, PartialEq)]
 pub enum Value {
     /// Quoted string: `"hello"`
@@ -15,15 +63,77 @@ pub enum Value {
     Color([u8; 4]),
     /// Unquoted identifier: used for font names, event names, enum variants
     Ident(String),
+    /// `{path}` or a string with `{path}` holes: `"{fleet.count} trucks"`.
+    Binding(Binding),
+    /// `$accent`: a token of the active [`Theme`](crate::theme::Theme),
+    /// replaced by its value before the widget is built.
+    ThemeRef(String),
+}
+
+// ── Binding ───────────────────────────────────────────────────────────────
+
+/// A reference into application data, resolved when the widget is built.
+///
+/// Paths are dot-separated identifiers (`fuel_level`, `fleet.count`); their
+/// meaning is up to the host application.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Binding {
+    /// Bare `{path}`: takes the type of the bound value (`value: {fuel_level}`).
+    Path(String),
+    /// Quoted string with at least one `{path}` hole: always a string.
+    /// Write `\{` / `\}` for literal braces.
+    Template(Vec<TemplatePart>),
+}
+
+/// One piece of a [`Binding::Template`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum TemplatePart {
+    Text(String),
+    Path(String),
+}
+
+impl Binding {
+    /// Every path the binding reads, in source order.
+    pub fn paths(&self) -> Vec<&str> {
+        match self {
+            Binding::Path(p) => vec![p.as_str()],
+            Binding::Template(parts) => parts
+                .iter()
+                .filter_map(|p| match p {
+                    TemplatePart::Path(p) => Some(p.as_str()),
+                    TemplatePart::Text(_) => None,
+                })
+                .collect(),
+        }
+    }
 }
 
 // ── Prop ──────────────────────────────────────────────────────────────────
 
 /// A single `key: value` property inside a widget block.
-#[derive(Debug, Clone, PartialEq)]
+///
+/// Equality compares key and value only; spans are ignored.
+#[derive(Debug, Clone)]
 pub struct Prop {
     pub key: String,
     pub value: Value,
+    /// Source range of `key`.
+    pub key_span: Span,
+    /// Source range of `value`.
+    pub value_span: Span,
+}
+
+impl Prop {
+    /// Source range of the whole `key: value`.
+    pub fn span(&self) -> Span {
+        self.key_span.to(self.value_span)
+    }
+}
+
+impl PartialEq for Prop {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key && self.value == other.value
+    }
 }
 
 // ── Node ──────────────────────────────────────────────────────────────────
@@ -36,22 +146,90 @@ pub struct Prop {
 ///     bg: #4c6ef5ff
 /// }
 /// ```
-#[derive(Debug, Clone, PartialEq)]
+///
+/// Equality compares the tree's content; spans are ignored, so documents that
+/// differ only in layout or comments compare equal.
+#[derive(Debug, Clone)]
 pub struct Node {
     /// Widget type name or component alias: `"Column"`, `"Text"`, `"Sidebar"`.
     pub widget: String,
     /// Optional inline string content (e.g. the text label for `Text` or `Button`).
-    pub content: Option<String>,
+    ///
+    /// Either a [`Value::Str`] or, when it has `{path}` holes, a [`Value::Binding`].
+    pub content: Option<Value>,
     /// Properties inside the block (`key: value` lines).
     pub props: Vec<Prop>,
     /// Nested child widget nodes inside the block.
+    ///
+    /// For a `for` / `if` node this is the loop body / the `if` branch.
     pub children: Vec<Node>,
+    /// Set when this is a `for` or `if` construct rather than a widget.
+    pub control: Option<Control>,
+    /// Source range of the whole node, through its closing `}`.
+    pub span: Span,
+    /// Source range of the widget name (or `for` / `if` / `slot` keyword).
+    pub name_span: Span,
+    /// Source range of `content`.
+    pub content_span: Option<Span>,
+}
+
+impl PartialEq for Node {
+    fn eq(&self, other: &Self) -> bool {
+        self.widget == other.widget
+            && self.content == other.content
+            && self.props == other.props
+            && self.children == other.children
+            && self.control == other.control
+    }
+}
+
+// ── Control ───────────────────────────────────────────────────────────────
+
+/// Control flow in a child list, expanded by the host against its data
+/// before the surrounding widget is built.
+///
+/// ```mkml
+/// Column {
+///     for truck in fleet.trucks {
+///         Text "{truck.name}"
+///     }
+///     if has_alerts { AlertList { } } else { Text "All clear" }
+/// }
+/// ```
+///
+/// A control node has `widget` set to `"for"` / `"if"` and no content or props.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Control {
+    /// `for item in list { ... }`: the node's `children` are repeated once
+    /// per list element, with `item` bound to that element.
+    For { item: String, list: String },
+    /// `if cond { ... } else { ... }`: the node's `children` when `cond` is
+    /// truthy, otherwise `otherwise` (empty without an `else`; a single
+    /// nested `if` node for `else if`).
+    If { cond: String, otherwise: Vec<Node> },
+    /// `slot` in a component body: replaced by the children written at the
+    /// use site, or by the node's own `children` (`slot { ... }`) when
+    /// there are none.
+    Slot,
 }
 
 impl Node {
+    /// The inline content if it is a plain string (not a binding).
+    pub fn content_str(&self) -> Option<&str> {
+        match self.content.as_ref()? {
+            Value::Str(s) => Some(s.as_str()),
+            _ => None,
+        }
+    }
+
     /// Look up a property value by key.
     pub fn prop(&self, key: &str) -> Option<&Value> {
-        self.props.iter().find(|p| p.key == key).map(|p| &p.value)
+        self.find_prop(key).map(|p| &p.value)
+    }
+
+    /// Look up a property (with its spans) by key.
+    pub fn find_prop(&self, key: &str) -> Option<&Prop> {
+        self.props.iter().find(|p| p.key == key)
     }
 
     /// Get a property as `f32` if it is a `Number`.
@@ -82,17 +260,79 @@ impl Node {
 // ── Import ────────────────────────────────────────────────────────────────
 
 /// `import "path/to/file.mkml" as Alias`
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone)]
 pub struct Import {
     pub path: String,
     pub alias: String,
+    /// Source range of the whole declaration.
+    pub span: Span,
+    /// Source range of the quoted path, quotes included.
+    pub path_span: Span,
+    /// Source range of the alias.
+    pub alias_span: Span,
+}
+
+impl PartialEq for Import {
+    fn eq(&self, other: &Self) -> bool {
+        self.path == other.path && self.alias == other.alias
+    }
+}
+
+// ── Param ─────────────────────────────────────────────────────────────────
+
+/// `param title: "Untitled"` — a parameter of a component document.
+///
+/// Inside the component `{title}` refers to the value written at the use
+/// site (`Card { title: "Fleet" }`), or to `default` when there is none.
+#[derive(Debug, Clone)]
+pub struct Param {
+    pub name: String,
+    pub default: Option<Value>,
+    /// Source range of the whole declaration.
+    pub span: Span,
+}
+
+impl PartialEq for Param {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.default == other.default
+    }
+}
+
+// ── Comment ───────────────────────────────────────────────────────────────
+
+/// A `// line` or `/* block */` comment, kept as trivia for the formatter.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comment {
+    /// The comment as written, delimiters included, trailing whitespace trimmed.
+    pub text: String,
+    pub span: Span,
 }
 
 // ── DslDocument ───────────────────────────────────────────────────────────
 
 /// The top-level parse result for a `.mkml` source file.
-#[derive(Debug, Clone, PartialEq)]
+///
+/// Equality ignores `comments`, like it ignores spans.
+#[derive(Debug, Clone)]
 pub struct DslDocument {
     pub imports: Vec<Import>,
+    /// Parameters declared with `param`, in source order.
+    pub params: Vec<Param>,
     pub root: Node,
+    /// Every comment in the source, in order. The tree does not reference
+    /// them; [`format`](crate::format) places them back by position.
+    pub comments: Vec<Comment>,
+}
+
+impl PartialEq for DslDocument {
+    fn eq(&self, other: &Self) -> bool {
+        self.imports == other.imports && self.params == other.params && self.root == other.root
+    }
+}
+
+impl DslDocument {
+    /// Look up a declared parameter by name.
+    pub fn param(&self, name: &str) -> Option<&Param> {
+        self.params.iter().find(|p| p.name == name)
+    }
 }
@@ -0,0 +1,393 @@
+//! Semantic checks on a parsed document against a [`Schema`].
+//!
+//! The parser only knows the grammar. Whether `Buton` is a widget, or
+//! `align: middle` a valid alignment, is decided here.
+
+use crate::ast::{Control, DslDocument, Node, Prop, Span, Value};
+use crate::schema::{PropKind, PropSchema, Schema, WidgetSchema, STACK_CHILD_PROPS};
+use crate::theme::THEME_ROOT;
+
+// ── Issue ─────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// The builder would reject or misread the value.
+    Error,
+    /// The builder would ignore it, or render a placeholder.
+    Warning,
+    /// Works, but there is a preferred spelling.
+    Hint,
+}
+
+/// One problem found by [`check`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Issue {
+    pub severity: Severity,
+    pub span:     Span,
+    pub message:  String,
+    /// Text that fixes the issue when written over `span`: the closest
+    /// known name for a typo, the canonical name for a deprecated alias.
+    pub replacement: Option<String>,
+}
+
+// ── Entry point ───────────────────────────────────────────────────────────
+
+/// Everything in `doc` the builder would reject or ignore.
+///
+/// Widgets named by the document's imports or listed in `components` are
+/// components: their properties are parameters declared elsewhere, so only
+/// their children are checked.
+///
+/// A [theme](crate::theme) document is checked as one: its tokens are free,
+/// and each style's properties are checked against the widget it styles.
+pub fn check(doc: &DslDocument, schema: &Schema, components: &[&str]) -> Vec<Issue> {
+    let mut c = Checker {
+        schema,
+        components: doc.imports.iter().map(|i| i.alias.as_str()).chain(components.iter().copied()).collect(),
+        out:        Vec::new(),
+    };
+    if doc.root.widget == THEME_ROOT && doc.root.control.is_none() {
+        c.theme(&doc.root);
+    } else {
+        c.node(&doc.root, None);
+    }
+    c.out
+}
+
+// ── Checker ───────────────────────────────────────────────────────────────
+
+struct Checker<'d> {
+    schema:     &'d Schema,
+    components: Vec<&'d str>,
+    out:        Vec<Issue>,
+}
+
+impl<'d> Checker<'d> {
+    /// `parent` is the nearest enclosing widget; `for`/`if`/`slot` are
+    /// transparent, so a `for` directly inside a `Stack` yields Stack children.
+    fn node(&mut self, node: &'d Node, parent: Option<&'d str>) {
+        if let Some(control) = &node.control {
+            if let Control::If { otherwise, .. } = control {
+                self.children(otherwise, parent);
+            }
+            self.children(&node.children, parent);
+            return;
+        }
+
+        // Children of a component fill its slot, whose parent is not known here.
+        if self.components.contains(&node.widget.as_str()) {
+            self.children(&node.children, None);
+            return;
+        }
+
+        let Some(widget) = self.widget(node) else {
+            self.children(&node.children, None);
+            return;
+        };
+
+        let in_stack = parent == Some("Stack");
+        for prop in &node.props {
+            self.prop(widget, prop, in_stack);
+        }
+
+        if let Some(span) = node.content_span
+            && !widget.has_content
+        {
+            self.push(span, Severity::Warning, format!("`{}` does not take inline content", widget.name));
+        }
+        if let Some(first) = node.children.first()
+            && !widget.has_children
+        {
+            self.push(first.name_span, Severity::Warning, format!("`{}` does not take child widgets", widget.name));
+        }
+
+        self.children(&node.children, Some(node.widget.as_str()));
+    }
+
+    /// The styles of a `Theme` root: one widget each, its content a class name.
+    fn theme(&mut self, root: &'d Node) {
+        for style in &root.children {
+            if style.control.is_some() {
+                self.push(style.name_span, Severity::Error, "a theme holds only widget styles".to_string());
+                continue;
+            }
+            let Some(widget) = self.widget(style) else { continue };
+            for prop in &style.props {
+                self.prop(widget, prop, false);
+            }
+            if let Some(first) = style.children.first() {
+                self.push(first.name_span, Severity::Error, "a style takes no child widgets".to_string());
+            }
+        }
+    }
+
+    /// The schema of `node`'s widget, or a warning (with the closest known
+    /// name) when there is none.
+    fn widget(&mut self, node: &Node) -> Option<&'d WidgetSchema> {
+        let widget = self.schema.widget(&node.widget);
+        if widget.is_none() {
+            let known = self.schema.widgets().map(|w| w.name).chain(self.components.iter().copied());
+            let closest = closest(&node.widget, known);
+            let message = format!("unknown widget `{}`{}", node.widget, did_you_mean(closest));
+            self.push_fix(node.name_span, Severity::Warning, message, closest);
+        }
+        widget
+    }
+
+    fn children(&mut self, children: &'d [Node], parent: Option<&'d str>) {
+        for child in children {
+            self.node(child, parent);
+        }
+    }
+
+    fn prop(&mut self, widget: &WidgetSchema, prop: &Prop, in_stack: bool) {
+        let stack: &[PropSchema] = if in_stack { STACK_CHILD_PROPS } else { &[] };
+        let candidates = || widget.all_props().chain(stack);
+
+        let Some(info) = candidates().find(|p| p.name == prop.key) else {
+            let closest = closest(&prop.key, candidates().map(|p| p.name));
+            let message = format!("unknown property `{}` on `{}`{}", prop.key, widget.name, did_you_mean(closest));
+            self.push_fix(prop.key_span, Severity::Warning, message, closest);
+            return;
+        };
+
+        if let (true, Some(canonical)) = (info.deprecated, info.alias_of) {
+            self.push_fix(
+                prop.key_span,
+                Severity::Hint,
+                format!("`{}` is deprecated; use `{canonical}`", prop.key),
+                Some(canonical),
+            );
+        }
+
+        // Bound values and theme tokens are only known at runtime.
+        if matches!(prop.value, Value::Binding(_) | Value::ThemeRef(_))
+            || candidates().any(|p| p.name == prop.key && accepts(p.kind, &prop.value))
+        {
+            return;
+        }
+
+        let (message, fix) = match (info.kind, &prop.value) {
+            (PropKind::Enum(variants), Value::Ident(s) | Value::Str(s)) => (
+                format!(
+                    "invalid value `{s}` for `{}`; expected one of {}",
+                    prop.key,
+                    variants.iter().map(|v| format!("`{v}`")).collect::<Vec<_>>().join(", "),
+                ),
+                closest(s, variants.iter().copied()),
+            ),
+            (PropKind::Bool, value) => (
+                format!("`{}` expects `0` or `1`, found {}", prop.key, found(value)),
+                Some(bool_fix(value)),
+            ),
+            (kind, value) => (format!("`{}` expects {}, found {}", prop.key, expected(kind), found(value)), None),
+        };
+        self.push_fix(prop.value_span, Severity::Error, message, fix);
+    }
+
+    fn push(&mut self, span: Span, severity: Severity, message: String) {
+        self.push_fix(span, severity, message, None);
+    }
+
+    fn push_fix(&mut self, span: Span, severity: Severity, message: String, replacement: Option<&str>) {
+        let replacement = replacement.map(str::to_string);
+        self.out.push(Issue { severity, span, message, replacement });
+    }
+}
+
+// ── Value kinds ───────────────────────────────────────────────────────────
+
+/// Whether a literal `value` is valid for a property of `kind`.
+pub fn accepts(kind: PropKind, value: &Value) -> bool {
+    match (kind, value) {
+        (PropKind::Number, Value::Number(_)) => true,
+        (PropKind::Bool, Value::Number(n)) => *n == 0.0 || *n == 1.0,
+        (PropKind::Color, Value::Color(_)) => true,
+        (PropKind::Str | PropKind::Event | PropKind::Font, Value::Ident(_) | Value::Str(_)) => true,
+        (PropKind::Enum(variants), Value::Ident(s) | Value::Str(s)) => variants.contains(&s.as_str()),
+        (PropKind::Size, Value::Number(_)) => true,
+        (PropKind::Size, Value::Ident(s) | Value::Str(s)) => s == "fill" || s == "natural",
+        _ => false,
+    }
+}
+
+fn expected(kind: PropKind) -> &'static str {
+    match kind {
+        PropKind::Number  => "a number",
+        PropKind::Color   => "a color (`#rrggbbaa`)",
+        PropKind::Bool    => "`0` or `1`",
+        PropKind::Enum(_) => "a name",
+        PropKind::Str     => "a string",
+        PropKind::Event   => "an event name",
+        PropKind::Font    => "a font name",
+        PropKind::Size    => "`fill`, `natural` or a number",
+    }
+}
+
+/// The `0` or `1` a mistyped boolean most likely means: `0` for zero,
+/// `false`, `no` and `off`, `1` for anything else.
+fn bool_fix(value: &Value) -> &'static str {
+    match value {
+        Value::Number(n) if *n == 0.0 => "0",
+        Value::Ident(s) | Value::Str(s) if matches!(s.as_str(), "false" | "no" | "off") => "0",
+        _ => "1",
+    }
+}
+
+fn found(value: &Value) -> String {
+    match value {
+        Value::Str(s)     => format!("string \"{s}\""),
+        Value::Number(n)  => format!("number `{n}`"),
+        Value::Color(_)   => "a color".to_string(),
+        Value::Ident(s)   => format!("`{s}`"),
+        Value::Binding(_) => "a binding".to_string(),
+        Value::ThemeRef(name) => format!("`${name}`"),
+    }
+}
+
+// ── Suggestions ───────────────────────────────────────────────────────────
+
+/// The closest of `known` to `word`, if any is close enough to be a
+/// plausible typo.
+pub(crate) fn closest<'a>(word: &str, known: impl Iterator<Item = &'a str>) -> Option<&'a str> {
+    let limit = (word.chars().count() / 3).max(1);
+    known
+        .map(|k| (edit_distance(word, k), k))
+        .filter(|(d, _)| *d <= limit)
+        .min_by_key(|(d, _)| *d)
+        .map(|(_, k)| k)
+}
+
+/// `"; did you mean `X`?"`, or nothing without a suggestion.
+fn did_you_mean(closest: Option<&str>) -> String {
+    closest.map(|k| format!("; did you mean `{k}`?")).unwrap_or_default()
+}
+
+/// Levenshtein distance, in characters.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diag = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let next = (diag + usize::from(ca != *cb)).min(row[j] + 1).min(row[j + 1] + 1);
+            diag = row[j + 1];
+            row[j + 1] = next;
+        }
+    }
+    row[b.len()]
+}
+
+// ── tests ─────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_str;
+    use crate::schema::WidgetSchema;
+
+    fn messages(src: &str) -> Vec<String> {
+        check(&parse_str(src).unwrap(), &Schema::new(), &[])
+            .into_iter()
+            .map(|i| i.message)
+            .collect()
+    }
+
+    #[test]
+    fn valid_document_is_clean() {
+        let src = r#"
+            import "card.mkml" as Card
+            Stack {
+                Column { gap: 8  align: center  bg: #202020ff  id: main
+                    Text "hi" { color: {theme.fg} }
+                    for t in trucks { Button "{t.name}" { on_click: open } }
+                }
+                Card { title: "x"  left: 4 }
+                Container { left: 10  width: fill }
+            }
+        "#;
+        assert_eq!(messages(src), Vec::<String>::new());
+    }
+
+    #[test]
+    fn unknown_widget_and_property() {
+        let src = "Column { Buton { }  Container { corner_radus: 4 } }";
+        assert_eq!(messages(src), [
+            "unknown widget `Buton`; did you mean `Button`?",
+            "unknown property `corner_radus` on `Container`; did you mean `corner_radius`?",
+        ]);
+    }
+
+    #[test]
+    fn bad_values() {
+        let src = "Column { align: middle  bg: 12  gap: \"wide\" }";
+        assert_eq!(messages(src), [
+            "invalid value `middle` for `align`; expected one of `start`, `center`, `end`, `stretch`",
+            "`bg` expects a color (`#rrggbbaa`), found number `12`",
+            "`gap` expects a number, found string \"wide\"",
+        ]);
+    }
+
+    #[test]
+    fn booleans_are_zero_or_one() {
+        assert!(messages("Toggle { checked: 1 }").is_empty());
+        let issues = check(&parse_str("Column { Toggle { checked: 7 }  Checkbox { checked: false } }").unwrap(), &Schema::new(), &[]);
+        let found: Vec<_> = issues.iter().map(|i| (i.message.as_str(), i.replacement.as_deref())).collect();
+        assert_eq!(found, [
+            ("`checked` expects `0` or `1`, found number `7`", Some("1")),
+            ("`checked` expects `0` or `1`, found `false`", Some("0")),
+        ]);
+    }
+
+    #[test]
+    fn content_and_children_where_not_allowed() {
+        let issues = check(&parse_str("Column \"x\" { Toggle { Text \"y\" } }").unwrap(), &Schema::new(), &[]);
+        let messages: Vec<_> = issues.iter().map(|i| i.message.as_str()).collect();
+        assert_eq!(messages, [
+            "`Column` does not take inline content",
+            "`Toggle` does not take child widgets",
+        ]);
+        assert_eq!((issues[1].span.start.line, issues[1].span.start.col), (1, 23));
+    }
+
+    #[test]
+    fn stack_child_props_only_under_stack() {
+        assert!(messages("Stack { Text \"a\" { top: 4 } }").is_empty());
+        assert_eq!(messages("Column { Text \"a\" { top: 4 } }"), ["unknown property `top` on `Text`"]);
+    }
+
+    #[test]
+    fn deprecated_aliases_are_hints() {
+        let issues = check(&parse_str("Row { spacing: 4 }").unwrap(), &Schema::new(), &[]);
+        assert_eq!(issues[0].severity, Severity::Hint);
+        assert_eq!(issues[0].message, "`spacing` is deprecated; use `gap`");
+        assert_eq!(issues[0].replacement.as_deref(), Some("gap"));
+    }
+
+    #[test]
+    fn typos_carry_their_replacement() {
+        let issues = check(&parse_str("Column { Buton { }  align: centre  Text { colr: 1 } }").unwrap(), &Schema::new(), &[]);
+        let fixes: Vec<_> = issues.iter().map(|i| i.replacement.as_deref()).collect();
+        assert_eq!(fixes, [Some("center"), Some("Button"), Some("color")]);
+    }
+
+    #[test]
+    fn theme_styles_are_checked_against_their_widget() {
+        let src = "Theme { accent: #638cffff  Button \"primary\" { bg: $accent  bgg: 1 }  Buton { } }";
+        let issues = check(&parse_str(src).unwrap(), &Schema::new(), &[]);
+        let messages: Vec<_> = issues.iter().map(|i| i.message.as_str()).collect();
+        assert_eq!(messages, [
+            "unknown property `bgg` on `Button`; did you mean `bg`?",
+            "unknown widget `Buton`; did you mean `Button`?",
+        ]);
+    }
+
+    #[test]
+    fn registered_widgets_and_components() {
+        let mut schema = Schema::new();
+        schema.register(WidgetSchema::new("Gauge", "A dial."));
+        let doc = parse_str("Column { Gauge { }  Sidebar { width: 3 } }").unwrap();
+        assert!(check(&doc, &schema, &["Sidebar"]).is_empty());
+    }
+}
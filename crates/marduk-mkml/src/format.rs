@@ -0,0 +1,514 @@
+//! Canonical pretty-printer: turns a [`DslDocument`] back into source.
+//!
+//! The output is the same for any two documents that differ only in
+//! layout, so it can be enforced from an editor or a pre-commit hook:
+//!
+//! ```rust
+//! use marduk_mkml::format::{format_str, is_formatted, FormatOptions};
+//!
+//! let src = "Column{gap:8 Text \"hi\"{color:#ffffff}}";
+//! let pretty = format_str(src, &FormatOptions::default()).unwrap();
+//! assert_eq!(pretty, "Column {\n    gap: 8\n    Text \"hi\" {\n        color: #ffffffff\n    }\n}\n");
+//! assert!(is_formatted(&pretty, &FormatOptions::default()).unwrap());
+//! ```
+//!
+//! The rules:
+//!
+//! - one property or child per line, indented by [`FormatOptions::indent`];
+//!   with [`inline_leaves`](FormatOptions::inline_leaves), a widget without
+//!   children that fits in [`max_width`](FormatOptions::max_width) goes on
+//!   one line instead (`Text "hi" { size: 14  color: #ffffffff }`), and a
+//!   run of such lines for the same widget is aligned on its `{`;
+//! - properties and children keep their source order, and runs of blank
+//!   lines between them collapse to one;
+//! - colors are written `#rrggbbaa` in lowercase, numbers without a
+//!   trailing `.0`;
+//! - the `as` of consecutive imports is aligned;
+//! - comments stay where they were: on their own line before the item that
+//!   follows them, or at the end of the line they ended.
+
+use std::fmt::Write as _;
+
+use crate::ast::{Binding, Comment, Control, DslDocument, Node, Pos, Prop, Span, TemplatePart, Value};
+use crate::error::ParseError;
+use crate::parser::parse_str;
+
+// ── Options ───────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormatOptions {
+    /// Spaces per nesting level.
+    pub indent:        usize,
+    /// Line width inline widgets must fit in, indentation included.
+    pub max_width:     usize,
+    /// Put widgets without children on one line when they fit.
+    pub inline_leaves: bool,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self { indent: 4, max_width: 100, inline_leaves: false }
+    }
+}
+
+// ── Entry points ──────────────────────────────────────────────────────────
+
+/// The canonical source for `doc`, ending in a newline.
+pub fn format(doc: &DslDocument, options: &FormatOptions) -> String {
+    let mut p = Printer::new(&doc.comments, 0, options);
+    p.document(doc);
+    p.out
+}
+
+/// Parse `src` and [`format`] it.
+pub fn format_str(src: &str, options: &FormatOptions) -> Result<String, ParseError> {
+    Ok(format(&parse_str(src)?, options))
+}
+
+/// Whether `src` is already in canonical form.
+pub fn is_formatted(src: &str, options: &FormatOptions) -> Result<bool, ParseError> {
+    Ok(format_str(src, options)? == src)
+}
+
+/// Formats the smallest widget enclosing the byte range `start..end`.
+///
+/// Returns the source range to replace and its replacement. The range
+/// starts at the beginning of the widget's line when only indentation
+/// precedes it there. A selection outside the root widget formats the
+/// whole document.
+pub fn format_range(
+    src: &str,
+    start: usize,
+    end: usize,
+    options: &FormatOptions,
+) -> Result<(Span, String), ParseError> {
+    let doc = parse_str(src)?;
+    let Some((node, depth)) = innermost(&doc.root, 0, start, end) else {
+        return Ok((Span::new(Pos { offset: 0, line: 1, col: 1 }, end_pos(src)), format(&doc, options)));
+    };
+
+    let first = doc.comments.partition_point(|c| c.span.start.offset < node.span.start.offset);
+    let mut p = Printer::new(&doc.comments, first, options);
+    let line_start = src[..node.span.start.offset].rfind('\n').map_or(0, |i| i + 1);
+    let mut from = node.span.start;
+    if src[line_start..node.span.start.offset].trim().is_empty() {
+        from = Pos { offset: line_start, line: node.span.start.line, col: 1 };
+        p.indent(depth);
+    }
+    p.node_body(node, depth);
+    Ok((Span::new(from, node.span.end), p.out))
+}
+
+// ── Printer ───────────────────────────────────────────────────────────────
+
+struct Printer<'d> {
+    options:   &'d FormatOptions,
+    comments:  &'d [Comment],
+    /// Index of the first comment not printed yet.
+    next:      usize,
+    out:       String,
+    /// Source line the last printed item ended on, for blank-line keeping.
+    last_line: usize,
+    /// Nothing printed yet in the current block: no blank line before it.
+    fresh:     bool,
+    /// Width to pad widget headers to, inside a run of aligned leaves.
+    align:     usize,
+}
+
+impl<'d> Printer<'d> {
+    fn new(comments: &'d [Comment], next: usize, options: &'d FormatOptions) -> Self {
+        Self { options, comments, next, out: String::new(), last_line: 0, fresh: true, align: 0 }
+    }
+
+    fn document(&mut self, doc: &DslDocument) {
+        let width = doc.imports.iter().map(|i| quoted(&i.path).chars().count()).max().unwrap_or(0);
+        for import in &doc.imports {
+            self.start_item(import.span, 0);
+            let path = quoted(&import.path);
+            let _ = write!(self.out, "import {path:<width$} as {}", import.alias);
+            self.end_line(import.span.end.line);
+        }
+        for param in &doc.params {
+            self.start_item(param.span, 0);
+            let _ = write!(self.out, "param {}", param.name);
+            if let Some(default) = &param.default {
+                let _ = write!(self.out, ": {}", value(default));
+            }
+            self.end_line(param.span.end.line);
+        }
+        self.node(&doc.root, 0);
+        self.leading(usize::MAX, 0);
+    }
+
+    /// A child widget or control node, on its own line(s).
+    fn node(&mut self, node: &Node, depth: usize) {
+        self.start_item(node.span, depth);
+        self.node_body(node, depth);
+        self.end_line(node.span.end.line);
+    }
+
+    /// `node` from its name through its closing `}`, without the final newline.
+    fn node_body(&mut self, node: &Node, depth: usize) {
+        let header_line = node.content_span.unwrap_or(node.name_span).end.line;
+        match &node.control {
+            None => {
+                let mut header = node.widget.clone();
+                if let Some(content) = &node.content {
+                    let _ = write!(header, " {}", value(content));
+                }
+                let _ = write!(self.out, "{header:<width$}", width = self.align);
+                // `Text "hi"` needs no braces; `Sidebar { }` keeps them.
+                let bare = node.content.is_some();
+                self.block(node, &items(&node.props, &node.children), node.span.end.offset, header_line, depth, bare);
+            }
+            Some(Control::For { item, list }) => {
+                let _ = write!(self.out, "for {item} in {list}");
+                self.block(node, &items(&[], &node.children), node.span.end.offset, header_line, depth, false);
+            }
+            Some(Control::Slot) => {
+                self.out.push_str("slot");
+                self.block(node, &items(&[], &node.children), node.span.end.offset, header_line, depth, true);
+            }
+            Some(Control::If { cond, otherwise }) => {
+                let _ = write!(self.out, "if {cond}");
+                let branch_end = otherwise.first().map_or(node.span.end.offset, |n| n.span.start.offset);
+                self.block(node, &items(&[], &node.children), branch_end, header_line, depth, false);
+                match otherwise.as_slice() {
+                    [] => {}
+                    [chained] if matches!(chained.control, Some(Control::If { .. })) => {
+                        self.out.push_str(" else ");
+                        self.node_body(chained, depth);
+                    }
+                    nodes => {
+                        self.out.push_str(" else");
+                        let line = nodes[0].span.start.line.saturating_sub(1);
+                        self.block(node, &items(&[], nodes), node.span.end.offset, line, depth, false);
+                    }
+                }
+            }
+        }
+    }
+
+    /// ` { … }` holding `items`, whose closing brace is at `end`; nothing
+    /// for an empty block when `bare` is allowed.
+    fn block(&mut self, node: &Node, items: &[Item], end: usize, header_line: usize, depth: usize, bare: bool) {
+        if items.is_empty() && !self.comment_before(end) {
+            if !bare {
+                self.out.push_str(" { }");
+            }
+            return;
+        }
+        if let Some(inline) = self.inline(node, items, end) {
+            self.out.push_str(&inline);
+            return;
+        }
+
+        // Drop the alignment padding of a header that did not go inline.
+        self.out.truncate(self.out.trim_end_matches(' ').len());
+        self.out.push_str(" {");
+        self.end_line(header_line);
+        self.fresh = true;
+        let outer_align = std::mem::take(&mut self.align);
+        let mut run_end = 0;
+        for (i, item) in items.iter().enumerate() {
+            match item {
+                Item::Prop(prop) => {
+                    self.start_item(prop.span(), depth + 1);
+                    let _ = write!(self.out, "{}: {}", prop.key, value(&prop.value));
+                    self.end_line(prop.value_span.end.line);
+                }
+                Item::Node(child) => {
+                    if i >= run_end {
+                        (self.align, run_end) = self.aligned_run(items, i);
+                    }
+                    self.node(child, depth + 1);
+                }
+            }
+        }
+        self.align = outer_align;
+        self.leading(end, depth + 1);
+        self.indent(depth);
+        self.out.push('}');
+    }
+
+    /// For a run of same-named leaf widgets on consecutive lines starting at
+    /// `items[start]`, the header width that lines up their ` { … }`, and
+    /// where the run ends. Only with `inline_leaves`.
+    fn aligned_run(&self, items: &[Item], start: usize) -> (usize, usize) {
+        let leaf = |i: usize| match items.get(i) {
+            Some(Item::Node(n)) if n.control.is_none() && n.children.is_empty() && !n.props.is_empty() => Some(*n),
+            _ => None,
+        };
+        let Some(first) = leaf(start).filter(|_| self.options.inline_leaves) else {
+            return (0, start + 1);
+        };
+        let mut end = start + 1;
+        let mut prev = first;
+        while let Some(next) = leaf(end)
+            && next.widget == first.widget
+            && next.span.start.line == prev.span.end.line + 1
+        {
+            prev = next;
+            end += 1;
+        }
+        if end - start < 2 {
+            return (0, end);
+        }
+        let width = (start..end)
+            .filter_map(leaf)
+            .map(|n| n.widget.chars().count() + n.content.as_ref().map_or(0, |c| 1 + value(c).chars().count()))
+            .max()
+            .unwrap_or(0);
+        (width, end)
+    }
+
+    /// ` { a: 1  b: 2 }` when `inline_leaves` is on and it fits.
+    fn inline(&self, node: &Node, items: &[Item], end: usize) -> Option<String> {
+        let props: Vec<String> = items
+            .iter()
+            .map(|item| match item {
+                Item::Prop(p) => Some(format!("{}: {}", p.key, value(&p.value))),
+                Item::Node(_) => None,
+            })
+            .collect::<Option<_>>()?;
+        let text = format!(" {{ {} }}", props.join("  "));
+        let column = self.out.len() - self.out.rfind('\n').map_or(0, |i| i + 1);
+        let fits = column + text.chars().count() <= self.options.max_width;
+        let commented = self.comment_before(end.max(node.span.end.offset));
+        (self.options.inline_leaves && node.control.is_none() && fits && !commented).then_some(text)
+    }
+
+    // ── Lines and comments ────────────────────────────────────────────────
+
+    /// Leading comments, a kept blank line, and indentation for an item.
+    fn start_item(&mut self, span: Span, depth: usize) {
+        self.leading(span.start.offset, depth);
+        self.blank_line_before(span.start.line);
+        self.indent(depth);
+    }
+
+    /// Ends the current line, pulling up a comment that started on `line`.
+    fn end_line(&mut self, line: usize) {
+        self.last_line = self.last_line.max(line);
+        if let Some(c) = self.comments.get(self.next)
+            && c.span.start.line == line
+        {
+            let _ = write!(self.out, "  {}", c.text);
+            self.last_line = c.span.end.line;
+            self.next += 1;
+        }
+        self.out.push('\n');
+    }
+
+    /// Every unprinted comment before byte `offset`, each on its own line.
+    fn leading(&mut self, offset: usize, depth: usize) {
+        while let Some(c) = self.comments.get(self.next).filter(|c| c.span.start.offset < offset) {
+            self.next += 1;
+            self.blank_line_before(c.span.start.line);
+            self.indent(depth);
+            self.out.push_str(&c.text);
+            self.out.push('\n');
+            self.last_line = c.span.end.line;
+        }
+    }
+
+    fn comment_before(&self, offset: usize) -> bool {
+        self.comments.get(self.next).is_some_and(|c| c.span.start.offset < offset)
+    }
+
+    /// Keeps one blank line where the source had at least one.
+    fn blank_line_before(&mut self, line: usize) {
+        if !self.fresh && line > self.last_line + 1 {
+            self.out.push('\n');
+        }
+        self.fresh = false;
+    }
+
+    fn indent(&mut self, depth: usize) {
+        self.out.extend(std::iter::repeat_n(' ', depth * self.options.indent));
+    }
+}
+
+// ── Items ─────────────────────────────────────────────────────────────────
+
+/// A line inside a block.
+enum Item<'d> {
+    Prop(&'d Prop),
+    Node(&'d Node),
+}
+
+/// Props and children merged back into source order.
+fn items<'d>(props: &'d [Prop], children: &'d [Node]) -> Vec<Item<'d>> {
+    let mut items: Vec<Item> = props.iter().map(Item::Prop).chain(children.iter().map(Item::Node)).collect();
+    items.sort_by_key(|item| match item {
+        Item::Prop(p) => p.key_span.start.offset,
+        Item::Node(n) => n.span.start.offset,
+    });
+    items
+}
+
+/// The innermost node whose span covers `start..end`, with its depth.
+///
+/// An `else if` continues the line of its `if`, so it shares its depth.
+fn innermost(node: &Node, depth: usize, start: usize, end: usize) -> Option<(&Node, usize)> {
+    if !(node.span.start.offset <= start && end <= node.span.end.offset) {
+        return None;
+    }
+    let otherwise: &[Node] = match &node.control {
+        Some(Control::If { otherwise, .. }) => otherwise,
+        _ => &[],
+    };
+    let chained = matches!(otherwise, [n] if matches!(n.control, Some(Control::If { .. })));
+    node.children
+        .iter()
+        .map(|c| (c, depth + 1))
+        .chain(otherwise.iter().map(|c| (c, if chained { depth } else { depth + 1 })))
+        .find_map(|(c, d)| innermost(c, d, start, end))
+        .or(Some((node, depth)))
+}
+
+// ── Values ────────────────────────────────────────────────────────────────
+
+fn value(value: &Value) -> String {
+    match value {
+        Value::Str(s)    => quoted(s),
+        Value::Number(n) => format!("{n}"),
+        Value::Color([r, g, b, a]) => format!("#{r:02x}{g:02x}{b:02x}{a:02x}"),
+        Value::Ident(s)  => s.clone(),
+        Value::ThemeRef(name) => format!("${name}"),
+        Value::Binding(Binding::Path(path)) => format!("{{{path}}}"),
+        Value::Binding(Binding::Template(parts)) => {
+            let mut out = String::from("\"");
+            for part in parts {
+                match part {
+                    TemplatePart::Text(text) => escape_into(&mut out, text),
+                    TemplatePart::Path(path) => { let _ = write!(out, "{{{path}}}"); }
+                }
+            }
+            out.push('"');
+            out
+        }
+    }
+}
+
+fn quoted(s: &str) -> String {
+    let mut out = String::from("\"");
+    escape_into(&mut out, s);
+    out.push('"');
+    out
+}
+
+/// Escapes `s` for a string literal; braces too, so text is not read as a hole.
+fn escape_into(out: &mut String, s: &str) {
+    for c in s.chars() {
+        match c {
+            '"'  => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            '{'  => out.push_str("\\{"),
+            '}'  => out.push_str("\\}"),
+            c    => out.push(c),
+        }
+    }
+}
+
+/// Position just past the end of `src`.
+fn end_pos(src: &str) -> Pos {
+    let line = src.matches('\n').count() + 1;
+    let col = src.rsplit('\n').next().map_or(0, |l| l.chars().count()) + 1;
+    Pos { offset: src.len(), line, col }
+}
+
+// ── tests ─────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fmt(src: &str) -> String {
+        format_str(src, &FormatOptions::default()).unwrap()
+    }
+
+    #[test]
+    fn canonical_layout() {
+        let src = r#"import "a.mkml" as A
+import "components/long.mkml" as Long
+param title: "Untitled"
+Column { gap: 8.0   bg: #FFFFFF
+  Text "{title}: \{raw\}" { size: 14 }
+  for t in trucks { Button "{t.name}" { on_click: open } }
+  if busy { Spinner { } } else if idle { Text "idle" } else { A { } }
+  slot
+}"#;
+        assert_eq!(fmt(src), r#"import "a.mkml"               as A
+import "components/long.mkml" as Long
+param title: "Untitled"
+Column {
+    gap: 8
+    bg: #ffffffff
+    Text "{title}: \{raw\}" {
+        size: 14
+    }
+    for t in trucks {
+        Button "{t.name}" {
+            on_click: open
+        }
+    }
+    if busy {
+        Spinner { }
+    } else if idle {
+        Text "idle"
+    } else {
+        A { }
+    }
+    slot
+}
+"#);
+    }
+
+    #[test]
+    fn keeps_comments_and_blank_lines() {
+        let src = "// header\nColumn {  // root\n    gap: 8   /* tight */\n\n\n    // the label\n    Text \"hi\"\n    /* end */\n}\n// footer\n";
+        assert_eq!(fmt(src), "// header\nColumn {  // root\n    gap: 8  /* tight */\n\n    // the label\n    Text \"hi\"\n    /* end */\n}\n// footer\n");
+    }
+
+    #[test]
+    fn output_is_stable_and_equivalent() {
+        let src = "import \"x.mkml\" as X Stack { Row { gap: 4 /* a */ X { } } // b\n Text \"t\" { top: 1 } }";
+        let once = fmt(src);
+        assert_eq!(fmt(&once), once);
+        assert_eq!(parse_str(&once).unwrap(), parse_str(src).unwrap());
+        assert_eq!(parse_str(&once).unwrap().comments.len(), 2);
+    }
+
+    #[test]
+    fn inline_leaves_when_they_fit() {
+        let options = FormatOptions { inline_leaves: true, max_width: 45, ..Default::default() };
+        let src = "Column { Text \"hi\" { size: 14  color: #ffffffff } Text \"a considerably longer label\" { size: 14 } }";
+        assert_eq!(
+            format_str(src, &options).unwrap(),
+            "Column {\n    Text \"hi\" { size: 14  color: #ffffffff }\n    Text \"a considerably longer label\" {\n        size: 14\n    }\n}\n"
+        );
+
+        let options = FormatOptions { inline_leaves: true, ..Default::default() };
+        let src = "Radio {\n  RadioOption \"Autopilot\" { value: autopilot }\n  RadioOption \"Drift\" { value: drift }\n}";
+        assert_eq!(
+            format_str(src, &options).unwrap(),
+            "Radio {\n    RadioOption \"Autopilot\" { value: autopilot }\n    RadioOption \"Drift\"     { value: drift }\n}\n"
+        );
+    }
+
+    #[test]
+    fn range_formats_the_enclosing_widget() {
+        let src = "Column {\n    gap: 8\n      Row {   gap:4 }\n}\n";
+        let at = src.find("gap:4").unwrap();
+        let (span, text) = format_range(src, at, at + 1, &FormatOptions::default()).unwrap();
+        assert_eq!((span.start.offset, span.end.offset), (src.find("      Row").unwrap(), src.find(" }\n}").unwrap() + 2));
+        assert_eq!(text, "    Row {\n        gap: 4\n    }");
+
+        let (span, text) = format_range(src, 0, src.len(), &FormatOptions::default()).unwrap();
+        assert_eq!((span.start.offset, span.end.offset), (0, src.len()));
+        assert!(text.starts_with("Column {\n"));
+    }
+}
@@ -1,3 +1,4 @@
+use crate::ast::{Comment, Pos, Span, TemplatePart};
 use crate::error::ParseError;
 
 // ── Token ─────────────────────────────────────────────────────────────────
@@ -10,8 +11,13 @@ pub enum Token {
     Number(f32),
     /// Color literal: `[r, g, b, a]` straight-alpha bytes as parsed from `#rrggbbaa`.
     Color([u8; 4]),
+    /// String literal with `{path}` holes.
+    Template(Vec<TemplatePart>),
+    /// Theme token reference: `$accent` (the name, without `$`).
+    ThemeRef(String),
     // Punctuation
     Colon,
+    Dot,
     LBrace,
     RBrace,
     // Keywords
@@ -31,6 +37,8 @@ pub struct TokenWithPos {
     pub line: usize,
     /// 1-based column number of the first character of this token.
     pub col: usize,
+    /// Source range of the token.
+    pub span: Span,
 }
 
 // ── Lexer ─────────────────────────────────────────────────────────────────
@@ -42,11 +50,13 @@ pub struct Lexer<'s> {
     line: usize,
     /// 1-based current column number.
     col: usize,
+    /// Comments skipped so far, in source order.
+    comments: Vec<Comment>,
 }
 
 impl<'s> Lexer<'s> {
     pub fn new(src: &'s str) -> Self {
-        Self { src, pos: 0, line: 1, col: 1 }
+        Self { src, pos: 0, line: 1, col: 1, comments: Vec::new() }
     }
 
     /// Returns the current `(line, col)` position (1-based).
@@ -54,19 +64,31 @@ impl<'s> Lexer<'s> {
         (self.line, self.col)
     }
 
-    pub fn tokenize(mut self) -> Result<Vec<TokenWithPos>, ParseError> {
+    pub fn tokenize(self) -> Result<Vec<TokenWithPos>, ParseError> {
+        self.tokenize_with_comments().map(|(tokens, _)| tokens)
+    }
+
+    /// Like [`tokenize`](Self::tokenize), but also returns the comments
+    /// between tokens, which the parser keeps as trivia for the formatter.
+    pub fn tokenize_with_comments(mut self) -> Result<(Vec<TokenWithPos>, Vec<Comment>), ParseError> {
         let mut tokens = Vec::new();
         loop {
             self.skip_whitespace_and_comments();
             let (line, col) = self.current_pos();
+            let start = self.pos();
             let tok = self.next_token()?;
             let eof = tok == Token::Eof;
-            tokens.push(TokenWithPos { token: tok, line, col });
+            let span = Span::new(start, self.pos());
+            tokens.push(TokenWithPos { token: tok, line, col, span });
             if eof {
                 break;
             }
         }
-        Ok(tokens)
+        Ok((tokens, self.comments))
+    }
+
+    fn pos(&self) -> Pos {
+        Pos { offset: self.pos, line: self.line, col: self.col }
     }
 
     fn peek(&self) -> Option<char> {
@@ -90,6 +112,7 @@ impl<'s> Lexer<'s> {
             while matches!(self.peek(), Some(c) if c.is_whitespace()) {
                 self.advance();
             }
+            let start = self.pos();
             // skip `//` line comments
             if self.src[self.pos..].starts_with("//") {
                 while !matches!(self.peek(), None | Some('\n')) {
@@ -110,6 +133,8 @@ impl<'s> Lexer<'s> {
             } else {
                 break;
             }
+            let text = self.src[start.offset..self.pos].trim_end().to_string();
+            self.comments.push(Comment { text, span: Span::new(start, self.pos()) });
         }
     }
 
@@ -122,10 +147,12 @@ impl<'s> Lexer<'s> {
 
         match ch {
             ':' => { self.advance(); Ok(Token::Colon) }
+            '.' => { self.advance(); Ok(Token::Dot) }
             '{' => { self.advance(); Ok(Token::LBrace) }
             '}' => { self.advance(); Ok(Token::RBrace) }
             '"' => self.lex_string(),
             '#' => self.lex_color(),
+            '$' => self.lex_theme_ref(),
             c if c.is_ascii_digit() || c == '-' => self.lex_number(),
             c if c.is_alphabetic() || c == '_' => self.lex_ident_or_keyword(),
             other => {
@@ -138,6 +165,7 @@ impl<'s> Lexer<'s> {
     fn lex_string(&mut self) -> Result<Token, ParseError> {
         self.advance(); // consume opening `"`
         let mut s = String::new();
+        let mut parts = Vec::new();
         loop {
             let (line, col) = self.current_pos();
             match self.advance() {
@@ -154,10 +182,41 @@ impl<'s> Lexer<'s> {
                         None => return Err(ParseError::new("unterminated escape sequence", el, ec)),
                     }
                 }
+                Some('{') => {
+                    let path = self.lex_hole(line, col)?;
+                    if !s.is_empty() {
+                        parts.push(TemplatePart::Text(std::mem::take(&mut s)));
+                    }
+                    parts.push(TemplatePart::Path(path));
+                }
                 Some(c) => s.push(c),
             }
         }
-        Ok(Token::Str(s))
+        if parts.is_empty() {
+            return Ok(Token::Str(s));
+        }
+        if !s.is_empty() {
+            parts.push(TemplatePart::Text(s));
+        }
+        Ok(Token::Template(parts))
+    }
+
+    /// Lex the `path}` of a `{path}` hole; the `{` at `line:col` is already consumed.
+    fn lex_hole(&mut self, line: usize, col: usize) -> Result<String, ParseError> {
+        let start = self.pos;
+        loop {
+            match self.peek() {
+                Some('}') => break,
+                Some(c) if c.is_alphanumeric() || c == '_' || c == '.' => { self.advance(); }
+                _ => return Err(ParseError::new("unterminated '{' in string (write \\{ for a literal brace)", line, col)),
+            }
+        }
+        let path = self.src[start..self.pos].to_string();
+        self.advance(); // consume `}`
+        if !is_binding_path(&path) {
+            return Err(ParseError::new(format!("invalid binding path {:?}", path), line, col));
+        }
+        Ok(path)
     }
 
     fn lex_color(&mut self) -> Result<Token, ParseError> {
@@ -189,6 +248,20 @@ impl<'s> Lexer<'s> {
         Ok(Token::Color([r, g, b, a]))
     }
 
+    fn lex_theme_ref(&mut self) -> Result<Token, ParseError> {
+        let (line, col) = self.current_pos();
+        self.advance(); // consume `$`
+        let start = self.pos;
+        while matches!(self.peek(), Some(c) if c.is_alphanumeric() || c == '_') {
+            self.advance();
+        }
+        let name = &self.src[start..self.pos];
+        if !name.starts_with(|c: char| c.is_alphabetic() || c == '_') {
+            return Err(ParseError::new("expected a theme token name after '$'", line, col));
+        }
+        Ok(Token::ThemeRef(name.to_string()))
+    }
+
     fn lex_number(&mut self) -> Result<Token, ParseError> {
         let (line, col) = self.current_pos();
         let start = self.pos;
@@ -223,3 +296,11 @@ impl<'s> Lexer<'s> {
         })
     }
 }
+
+/// `ident(.ident)*`, where each identifier starts with a letter or `_`.
+pub fn is_binding_path(path: &str) -> bool {
+    path.split('.').all(|seg| {
+        seg.chars().next().is_some_and(|c| c.is_alphabetic() || c == '_')
+            && seg.chars().all(|c| c.is_alphanumeric() || c == '_')
+    })
+}
@@ -8,10 +8,14 @@
 //!
 //! | Module | Contents |
 //! |--------|----------|
-//! | [`ast`] | `DslDocument`, `Node`, `Prop`, `Value`, `Import` |
+//! | [`ast`] | `DslDocument`, `Node`, `Prop`, `Value`, `Binding`, `Control`, `Import`, `Comment` |
 //! | [`error`] | `ParseError` |
 //! | [`lexer`] | `Lexer`, `Token` |
 //! | [`parser`] | `parse_str` entry point |
+//! | [`schema`] | `Schema`, `WidgetSchema`, `PropSchema`, `PropKind`: the widget vocabulary |
+//! | [`check`] | `check`: validates a document against a `Schema` |
+//! | [`theme`] | `Theme`: `$tokens` and default widget styles |
+//! | [`format`] | `format`, `format_str`, `is_formatted`: the canonical pretty-printer |
 //!
 //! # Quick start
 //!
@@ -30,9 +34,13 @@
 //! ```
 
 pub mod ast;
+pub mod check;
 pub mod error;
+pub mod format;
 pub mod lexer;
 pub mod parser;
+pub mod schema;
+pub mod theme;
 
 pub use ast::DslDocument;
 pub use error::ParseError;
@@ -103,4 +111,95 @@ mod parse_tests {
     #[test] fn err_bad_color() { err("Container { bg: #xyz }"); }
     #[test] fn err_unclosed_string() { err(r#"Text "oops { }"#); }
     #[test] fn err_double_colon() { err("Column { gap: : 8 }"); }
-}
\ No newline at end of file
+
+    use ast::{Binding, TemplatePart, Value};
+
+    #[test] fn bare_binding() {
+        let doc = parse_str("ProgressBar { value: {fleet.fuel_level} }").unwrap();
+        assert_eq!(doc.root.prop("value"), Some(&Value::Binding(Binding::Path("fleet.fuel_level".into()))));
+    }
+    #[test] fn template_content() {
+        let doc = parse_str(r#"Text "{fleet.count} trucks, {idle} idle" { size: 12 }"#).unwrap();
+        assert_eq!(doc.root.content, Some(Value::Binding(Binding::Template(vec![
+            TemplatePart::Path("fleet.count".into()),
+            TemplatePart::Text(" trucks, ".into()),
+            TemplatePart::Path("idle".into()),
+            TemplatePart::Text(" idle".into()),
+        ]))));
+        assert_eq!(doc.root.content_str(), None);
+    }
+    #[test] fn escaped_braces_stay_plain() {
+        let doc = parse_str(r#"Text "\{literal\}""#).unwrap();
+        assert_eq!(doc.root.content_str(), Some("{literal}"));
+    }
+    #[test] fn err_unclosed_hole() { err(r#"Text "{count trucks""#); }
+    #[test] fn err_bad_hole_path() { err(r#"Text "{1st}""#); }
+    #[test] fn err_bare_binding_trailing_dot() { err("Slider { value: {a.} }"); }
+
+    use ast::Control;
+
+    #[test] fn for_loop() {
+        let doc = parse_str(r#"Column { gap: 4  for truck in fleet.trucks { Text "{truck.name}" } }"#).unwrap();
+        let for_node = &doc.root.children[0];
+        assert_eq!(for_node.control, Some(Control::For { item: "truck".into(), list: "fleet.trucks".into() }));
+        assert_eq!(for_node.children[0].widget, "Text");
+    }
+    #[test] fn if_else_if_else() {
+        let doc = parse_str(r#"Column {
+            if alerts.any { Text "alert" } else if idle { Text "idle" } else { Text "ok" }
+        }"#).unwrap();
+        let Some(Control::If { cond, otherwise }) = &doc.root.children[0].control else { panic!() };
+        assert_eq!(cond, "alerts.any");
+        let Some(Control::If { cond, otherwise }) = &otherwise[0].control else { panic!() };
+        assert_eq!(cond, "idle");
+        assert_eq!(otherwise[0].content_str(), Some("ok"));
+    }
+    #[test] fn keywords_still_work_as_props() { ok("Column { if: 1  for: x }"); }
+    #[test] fn err_for_without_in() { err("Column { for x trucks { } }"); }
+    #[test] fn err_prop_in_control_body() { err("Column { if a { gap: 4 } }"); }
+
+    #[test] fn params_and_slot() {
+        let doc = parse_str(r#"
+            import "icon.mkml" as Icon
+            param title: "Untitled"
+            param closable
+            Column { Text "{title}"  slot { Text "empty" } }
+        "#).unwrap();
+        assert_eq!(doc.params.len(), 2);
+        assert_eq!(doc.param("title").unwrap().default, Some(Value::Str("Untitled".into())));
+        assert_eq!(doc.param("closable").unwrap().default, None);
+        assert_eq!(doc.root.children[1].control, Some(Control::Slot));
+        assert_eq!(doc.root.children[1].children.len(), 1);
+    }
+
+    #[test] fn spans_cover_nodes_props_and_values() {
+        let src = "Column {\n    gap: 8\n    Text \"hi\" { }\n}";
+        let doc = parse_str(src).unwrap();
+        let gap = doc.root.find_prop("gap").unwrap();
+        assert_eq!((gap.key_span.start.line, gap.key_span.start.col), (2, 5));
+        assert_eq!(&src[gap.value_span.start.offset..gap.value_span.end.offset], "8");
+        assert_eq!(&src[gap.span().start.offset..gap.span().end.offset], "gap: 8");
+
+        let text = &doc.root.children[0];
+        assert_eq!(&src[text.name_span.start.offset..text.name_span.end.offset], "Text");
+        let content = text.content_span.unwrap();
+        assert_eq!(&src[content.start.offset..content.end.offset], "\"hi\"");
+        assert_eq!(&src[text.span.start.offset..text.span.end.offset], "Text \"hi\" { }");
+        assert_eq!((doc.root.span.start.offset, doc.root.span.end.offset), (0, src.len()));
+
+        let src = "import \"side.mkml\" as Side\nSide { }";
+        let import = &parse_str(src).unwrap().imports[0];
+        assert_eq!(&src[import.path_span.start.offset..import.path_span.end.offset], "\"side.mkml\"");
+        assert_eq!(&src[import.alias_span.start.offset..import.alias_span.end.offset], "Side");
+    }
+    #[test] fn equality_ignores_spans() {
+        let a = parse_str("Row { gap: 4 }").unwrap();
+        let b = parse_str("\n\n   Row {\n  gap:   4\n}").unwrap();
+        assert_ne!(a.root.span, b.root.span);
+        assert_eq!(a.root, b.root);
+    }
+    #[test] fn control_body_error_points_at_prop() {
+        let e = parse_str("Column {\n  if a {\n    gap: 4\n  }\n}").unwrap_err();
+        assert_eq!((e.line, e.col), (3, 5));
+    }
+}
@@ -1,4 +1,4 @@
-use crate::ast::{DslDocument, Import, Node, Prop, Value};
+use crate::ast::{Binding, Comment, Control, DslDocument, Import, Node, Param, Pos, Prop, Span, Value};
 use crate::error::ParseError;
 use crate::lexer::{Lexer, Token, TokenWithPos};
 
@@ -7,11 +7,19 @@ use crate::lexer::{Lexer, Token, TokenWithPos};
 pub struct Parser {
     tokens: Vec<TokenWithPos>,
     pos: usize,
+    /// Carried through to [`DslDocument::comments`].
+    comments: Vec<Comment>,
 }
 
 impl Parser {
     pub fn new(tokens: Vec<TokenWithPos>) -> Self {
-        Self { tokens, pos: 0 }
+        Self { tokens, pos: 0, comments: Vec::new() }
+    }
+
+    /// Attach the comments the lexer skipped, for the formatter.
+    pub fn with_comments(mut self, comments: Vec<Comment>) -> Self {
+        self.comments = comments;
+        self
     }
 
     fn current_pos(&self) -> (usize, usize) {
@@ -22,6 +30,25 @@ impl Parser {
             .unwrap_or((1, 1))
     }
 
+    /// Start of the current (not yet consumed) token.
+    fn start(&self) -> Pos {
+        self.tokens
+            .get(self.pos)
+            .or_else(|| self.tokens.last())
+            .map(|t| t.span.start)
+            .unwrap_or_default()
+    }
+
+    /// Span from `start` to the end of the last consumed token.
+    fn span_from(&self, start: Pos) -> Span {
+        let end = self.pos
+            .checked_sub(1)
+            .and_then(|i| self.tokens.get(i))
+            .map(|t| t.span.end)
+            .unwrap_or(start);
+        Span::new(start, end)
+    }
+
     fn peek(&self) -> &Token {
         self.tokens.get(self.pos).map(|t| &t.token).unwrap_or(&Token::Eof)
     }
@@ -73,37 +100,70 @@ impl Parser {
 
     pub fn parse_document(&mut self) -> Result<DslDocument, ParseError> {
         let mut imports = Vec::new();
+        let mut params = Vec::new();
 
-        // Consume all leading `import` declarations.
-        while self.peek() == &Token::Import {
-            imports.push(self.parse_import()?);
+        // Consume all leading `import` / `param` declarations.
+        loop {
+            match self.peek() {
+                Token::Import => imports.push(self.parse_import()?),
+                Token::Ident(kw) if kw == "param" && matches!(self.peek_ahead(1), Token::Ident(_)) => {
+                    params.push(self.parse_param()?);
+                }
+                _ => break,
+            }
         }
 
         let root = self.parse_node()?;
 
-        Ok(DslDocument { imports, root })
+        let comments = std::mem::take(&mut self.comments);
+        Ok(DslDocument { imports, params, root, comments })
     }
 
     // ── Import ────────────────────────────────────────────────────────────
 
     fn parse_import(&mut self) -> Result<Import, ParseError> {
+        let start = self.start();
         self.advance(); // consume `import`
+        let path_start = self.start();
         let path = self.expect_str()?;
+        let path_span = self.span_from(path_start);
         self.expect_token(&Token::As)?;
+        let alias_start = self.start();
         let alias = self.expect_ident()?;
-        Ok(Import { path, alias })
+        let alias_span = self.span_from(alias_start);
+        Ok(Import { path, alias, span: self.span_from(start), path_span, alias_span })
+    }
+
+    // ── Param ─────────────────────────────────────────────────────────────
+
+    /// `param name` or `param name: default`
+    fn parse_param(&mut self) -> Result<Param, ParseError> {
+        let start = self.start();
+        self.advance(); // consume `param`
+        let name = self.expect_ident()?;
+        let default = if self.peek() == &Token::Colon {
+            self.advance();
+            Some(self.parse_value()?)
+        } else {
+            None
+        };
+        Ok(Param { name, default, span: self.span_from(start) })
     }
 
     // ── Node ──────────────────────────────────────────────────────────────
 
     fn parse_node(&mut self) -> Result<Node, ParseError> {
+        let start = self.start();
         let widget = self.expect_ident()?;
+        let name_span = self.span_from(start);
 
-        // Optional inline string content: `Text "Hello"` or `Button "OK"`
-        let content = if let Token::Str(_) = self.peek() {
-            if let Token::Str(s) = self.advance() { Some(s) } else { None }
-        } else {
-            None
+        // Optional inline string content: `Text "Hello"`, `Text "{n} trucks"`
+        let (content, content_span) = match self.peek() {
+            Token::Str(_) | Token::Template(_) => {
+                let (value, span) = self.parse_spanned_value()?;
+                (Some(value), Some(span))
+            }
+            _ => (None, None),
         };
 
         // Optional block `{ ... }` with properties and/or children mixed freely.
@@ -113,7 +173,16 @@ impl Parser {
             (Vec::new(), Vec::new())
         };
 
-        Ok(Node { widget, content, props, children })
+        Ok(Node {
+            widget,
+            content,
+            props,
+            children,
+            control: None,
+            span: self.span_from(start),
+            name_span,
+            content_span,
+        })
     }
 
     // ── Block ─────────────────────────────────────────────────────────────
@@ -123,6 +192,7 @@ impl Parser {
     ///
     /// Disambiguation: when we see `Ident`, we look one token ahead:
     /// - `Ident ":"` → property
+    /// - `for` / `if` / `slot` → control flow
     /// - `Ident <anything else>` → child widget node
     fn parse_block(&mut self) -> Result<(Vec<Prop>, Vec<Node>), ParseError> {
         self.advance(); // consume `{`
@@ -133,13 +203,13 @@ impl Parser {
             match self.peek() {
                 Token::RBrace => { self.advance(); break; }
                 Token::Eof    => return Err(self.err("unclosed '{' block")),
-                Token::Ident(_) => {
-                    if self.peek_ahead(1) == &Token::Colon {
-                        props.push(self.parse_prop()?);
-                    } else {
-                        children.push(self.parse_node()?);
-                    }
+                Token::Ident(_) if self.peek_ahead(1) == &Token::Colon => {
+                    props.push(self.parse_prop()?);
                 }
+                Token::Ident(kw) if kw == "for" => children.push(self.parse_for()?),
+                Token::Ident(kw) if kw == "if"  => children.push(self.parse_if()?),
+                Token::Ident(kw) if kw == "slot" => children.push(self.parse_slot()?),
+                Token::Ident(_) => children.push(self.parse_node()?),
                 tok => {
                     return Err(self.err(format!(
                         "unexpected {:?} inside block — expected a property (key: value) or a widget name",
@@ -152,32 +222,155 @@ impl Parser {
         Ok((props, children))
     }
 
+    // ── Control flow ──────────────────────────────────────────────────────
+
+    /// `for item in list.path { children }`
+    fn parse_for(&mut self) -> Result<Node, ParseError> {
+        let start = self.start();
+        self.advance(); // consume `for`
+        let name_span = self.span_from(start);
+        let item = self.expect_ident()?;
+        match self.advance() {
+            Token::Ident(kw) if kw == "in" => {}
+            tok => return Err(self.err(format!("expected `in` after `for {item}`, got {:?}", tok))),
+        }
+        let list = self.parse_path()?;
+        let children = self.parse_control_body("for")?;
+        Ok(Node {
+            widget:   "for".to_string(),
+            content:  None,
+            props:    Vec::new(),
+            children,
+            control:  Some(Control::For { item, list }),
+            span:     self.span_from(start),
+            name_span,
+            content_span: None,
+        })
+    }
+
+    /// `if cond.path { children } [else { children } | else if ...]`
+    fn parse_if(&mut self) -> Result<Node, ParseError> {
+        let start = self.start();
+        self.advance(); // consume `if`
+        let name_span = self.span_from(start);
+        let cond = self.parse_path()?;
+        let children = self.parse_control_body("if")?;
+        let otherwise = match self.peek() {
+            Token::Ident(kw) if kw == "else" => {
+                self.advance();
+                match self.peek() {
+                    Token::Ident(kw) if kw == "if" => vec![self.parse_if()?],
+                    _ => self.parse_control_body("else")?,
+                }
+            }
+            _ => Vec::new(),
+        };
+        Ok(Node {
+            widget:   "if".to_string(),
+            content:  None,
+            props:    Vec::new(),
+            children,
+            control:  Some(Control::If { cond, otherwise }),
+            span:     self.span_from(start),
+            name_span,
+            content_span: None,
+        })
+    }
+
+    /// `slot` or `slot { fallback children }`
+    fn parse_slot(&mut self) -> Result<Node, ParseError> {
+        let start = self.start();
+        self.advance(); // consume `slot`
+        let name_span = self.span_from(start);
+        let children = if self.peek() == &Token::LBrace {
+            self.parse_control_body("slot")?
+        } else {
+            Vec::new()
+        };
+        Ok(Node {
+            widget:   "slot".to_string(),
+            content:  None,
+            props:    Vec::new(),
+            children,
+            control:  Some(Control::Slot),
+            span:     self.span_from(start),
+            name_span,
+            content_span: None,
+        })
+    }
+
+    /// The `{ ... }` after `for` / `if` / `else`: widgets only, no properties.
+    fn parse_control_body(&mut self, keyword: &str) -> Result<Vec<Node>, ParseError> {
+        if self.peek() != &Token::LBrace {
+            return Err(self.err(format!("expected '{{' after `{keyword}`, got {:?}", self.peek())));
+        }
+        let (props, children) = self.parse_block()?;
+        if let Some(prop) = props.first() {
+            let at = prop.key_span.start;
+            return Err(ParseError::new(
+                format!("property '{}' is not allowed directly inside `{keyword}` — put it on a widget", prop.key),
+                at.line,
+                at.col,
+            ));
+        }
+        Ok(children)
+    }
+
     // ── Prop ──────────────────────────────────────────────────────────────
 
     fn parse_prop(&mut self) -> Result<Prop, ParseError> {
+        let start = self.start();
         let key = self.expect_ident()?;
+        let key_span = self.span_from(start);
         self.advance(); // consume `:`
-        let value = self.parse_value()?;
-        Ok(Prop { key, value })
+        let (value, value_span) = self.parse_spanned_value()?;
+        Ok(Prop { key, value, key_span, value_span })
     }
 
     // ── Value ─────────────────────────────────────────────────────────────
 
+    fn parse_spanned_value(&mut self) -> Result<(Value, Span), ParseError> {
+        let start = self.start();
+        let value = self.parse_value()?;
+        Ok((value, self.span_from(start)))
+    }
+
     fn parse_value(&mut self) -> Result<Value, ParseError> {
         match self.advance() {
             Token::Str(s)    => Ok(Value::Str(s)),
             Token::Number(n) => Ok(Value::Number(n)),
             Token::Color(c)  => Ok(Value::Color(c)),
             Token::Ident(s)  => Ok(Value::Ident(s)),
+            Token::ThemeRef(s) => Ok(Value::ThemeRef(s)),
+            Token::Template(parts) => Ok(Value::Binding(Binding::Template(parts))),
+            Token::LBrace    => Ok(Value::Binding(Binding::Path(self.parse_binding_path()?))),
             tok => Err(self.err(format!("expected a value, got {:?}", tok))),
         }
     }
+
+    /// Parse `path }` after the `{` of a bare binding.
+    fn parse_binding_path(&mut self) -> Result<String, ParseError> {
+        let path = self.parse_path()?;
+        self.expect_token(&Token::RBrace)?;
+        Ok(path)
+    }
+
+    /// Parse a dotted data path: `ident(.ident)*`.
+    fn parse_path(&mut self) -> Result<String, ParseError> {
+        let mut path = self.expect_ident()?;
+        while self.peek() == &Token::Dot {
+            self.advance();
+            path.push('.');
+            path.push_str(&self.expect_ident()?);
+        }
+        Ok(path)
+    }
 }
 
 // ── Public parse entry point ──────────────────────────────────────────────
 
 /// Parse a `.mkml` source string into a [`DslDocument`].
 pub fn parse_str(src: &str) -> Result<DslDocument, ParseError> {
-    let tokens = Lexer::new(src).tokenize()?;
-    Parser::new(tokens).parse_document()
+    let (tokens, comments) = Lexer::new(src).tokenize_with_comments()?;
+    Parser::new(tokens).with_comments(comments).parse_document()
 }
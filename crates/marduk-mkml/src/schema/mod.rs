@@ -0,0 +1,238 @@
+//! The widget vocabulary of `.mkml`: which widgets exist, which properties
+//! each accepts, and what kind of value each property takes.
+//!
+//! One [`Schema`] drives both sides of the language: the UI builder checks
+//! documents against it, and the language server uses it for hover,
+//! completion, and diagnostics. Applications that add their own widgets
+//! [`register`](Schema::register) an entry for each so they are checked and
+//! documented like the built-ins.
+//!
+//! ```rust
+//! use marduk_mkml::schema::{PropKind, PropSchema, Schema, WidgetSchema};
+//!
+//! static GAUGE_PROPS: &[PropSchema] = &[
+//!     PropSchema::new("value", PropKind::Number, "Needle position in [0, 1].").defaults_to("0"),
+//! ];
+//!
+//! let mut schema = Schema::new();
+//! schema.register(WidgetSchema::new("Gauge", "A round dial.").props(GAUGE_PROPS));
+//! assert!(schema.prop("Gauge", "value").is_some());
+//! assert!(schema.prop("Gauge", "id").is_some()); // common to every widget
+//! ```
+
+mod widgets;
+
+pub use widgets::{BUILTIN_WIDGETS, COMMON_PROPS, STACK_CHILD_PROPS};
+
+// ── Property kinds ────────────────────────────────────────────────────────
+
+/// The kind of value a property takes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PropKind {
+    /// Numeric literal (`16`, `1.5`, …).
+    Number,
+    /// Color literal (`#rrggbbaa`).
+    Color,
+    /// Boolean expressed as `0` or `1`.
+    Bool,
+    /// One of a fixed set of identifier strings.
+    Enum(&'static [&'static str]),
+    /// Free text: a string or identifier.
+    Str,
+    /// Any identifier or string: names an application event.
+    Event,
+    /// Any identifier: names a font registered in the bindings.
+    Font,
+    /// `fill`, `natural`, or a number of logical pixels.
+    Size,
+}
+
+impl PropKind {
+    /// Short human-readable description, for hover and diagnostics.
+    pub fn describe(&self) -> &'static str {
+        match self {
+            PropKind::Number  => "number",
+            PropKind::Color   => "color (`#rrggbbaa`)",
+            PropKind::Bool    => "bool (`0` or `1`)",
+            PropKind::Enum(_) => "enum",
+            PropKind::Str     => "string",
+            PropKind::Event   => "event name",
+            PropKind::Font    => "font name",
+            PropKind::Size    => "size (`fill`, `natural` or px)",
+        }
+    }
+}
+
+// ── Property schema ───────────────────────────────────────────────────────
+
+/// One property a widget accepts.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PropSchema {
+    pub name:       &'static str,
+    pub kind:       PropKind,
+    pub doc:        &'static str,
+    /// The value used when the property is absent, as written in source.
+    pub default:    Option<&'static str>,
+    /// Inclusive bounds the value is clamped to. Each is a number or the
+    /// name of a sibling property: `("min", "max")`.
+    pub range:      Option<(&'static str, &'static str)>,
+    /// The canonical property this one is an alternative spelling of.
+    pub alias_of:   Option<&'static str>,
+    /// Still accepted, but should be replaced by [`alias_of`](Self::alias_of).
+    pub deprecated: bool,
+}
+
+impl PropSchema {
+    pub const fn new(name: &'static str, kind: PropKind, doc: &'static str) -> Self {
+        Self { name, kind, doc, default: None, range: None, alias_of: None, deprecated: false }
+    }
+
+    pub const fn defaults_to(mut self, value: &'static str) -> Self {
+        self.default = Some(value);
+        self
+    }
+
+    pub const fn range(mut self, min: &'static str, max: &'static str) -> Self {
+        self.range = Some((min, max));
+        self
+    }
+
+    pub const fn alias_of(mut self, canonical: &'static str) -> Self {
+        self.alias_of = Some(canonical);
+        self
+    }
+
+    pub const fn deprecated(mut self) -> Self {
+        self.deprecated = true;
+        self
+    }
+}
+
+// ── Widget schema ─────────────────────────────────────────────────────────
+
+/// One widget and the properties it accepts.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WidgetSchema {
+    pub name:         &'static str,
+    pub doc:          &'static str,
+    /// Whether the widget accepts an inline string: `Text "Hello"`.
+    pub has_content:  bool,
+    /// Whether the widget accepts child widget blocks.
+    pub has_children: bool,
+    pub props:        &'static [PropSchema],
+}
+
+impl WidgetSchema {
+    /// A widget without content, children, or properties of its own.
+    pub const fn new(name: &'static str, doc: &'static str) -> Self {
+        Self { name, doc, has_content: false, has_children: false, props: &[] }
+    }
+
+    pub const fn content(mut self) -> Self {
+        self.has_content = true;
+        self
+    }
+
+    pub const fn children(mut self) -> Self {
+        self.has_children = true;
+        self
+    }
+
+    pub const fn props(mut self, props: &'static [PropSchema]) -> Self {
+        self.props = props;
+        self
+    }
+
+    /// The widget's own property `name`, or one of [`COMMON_PROPS`].
+    pub fn prop(&self, name: &str) -> Option<&'static PropSchema> {
+        self.all_props().find(|p| p.name == name)
+    }
+
+    /// The widget's own properties followed by [`COMMON_PROPS`].
+    pub fn all_props(&self) -> impl Iterator<Item = &'static PropSchema> + use<> {
+        self.props.iter().chain(COMMON_PROPS)
+    }
+}
+
+// ── Registry ──────────────────────────────────────────────────────────────
+
+/// The built-in widgets plus any registered by the application.
+#[derive(Debug, Clone, Default)]
+pub struct Schema {
+    custom: Vec<WidgetSchema>,
+}
+
+impl Schema {
+    /// A schema holding only the built-in widgets.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a widget, replacing any entry (built-in or custom) of the same name.
+    pub fn register(&mut self, widget: WidgetSchema) {
+        self.custom.retain(|w| w.name != widget.name);
+        self.custom.push(widget);
+    }
+
+    pub fn widget(&self, name: &str) -> Option<&WidgetSchema> {
+        self.custom.iter().chain(BUILTIN_WIDGETS).find(|w| w.name == name)
+    }
+
+    /// Every widget, custom entries shadowing built-ins of the same name.
+    pub fn widgets(&self) -> impl Iterator<Item = &WidgetSchema> {
+        let builtin = BUILTIN_WIDGETS
+            .iter()
+            .filter(|b| !self.custom.iter().any(|c| c.name == b.name));
+        self.custom.iter().chain(builtin)
+    }
+
+    /// Property `prop` of `widget`, including [`COMMON_PROPS`].
+    pub fn prop(&self, widget: &str, prop: &str) -> Option<&'static PropSchema> {
+        self.widget(widget)?.prop(prop)
+    }
+}
+
+// ── tests ─────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aliases_point_at_existing_props() {
+        for w in BUILTIN_WIDGETS {
+            for p in w.props {
+                if let Some(canonical) = p.alias_of {
+                    assert!(w.prop(canonical).is_some(), "{}.{} -> {canonical}", w.name, p.name);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn ranges_and_defaults_are_well_formed() {
+        for w in BUILTIN_WIDGETS {
+            for p in w.props {
+                for bound in p.range.into_iter().flat_map(|(lo, hi)| [lo, hi]) {
+                    assert!(
+                        bound.parse::<f32>().is_ok() || w.prop(bound).is_some(),
+                        "{}.{} bound {bound}", w.name, p.name,
+                    );
+                }
+                if let (PropKind::Color, Some(default)) = (p.kind, p.default) {
+                    let tokens = crate::lexer::Lexer::new(default).tokenize().unwrap();
+                    assert!(matches!(tokens[0].token, crate::lexer::Token::Color(_)), "{}.{}", w.name, p.name);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn custom_entries_shadow_builtins() {
+        let mut schema = Schema::new();
+        schema.register(WidgetSchema::new("Text", "Replaced.").content());
+        assert_eq!(schema.widget("Text").unwrap().doc, "Replaced.");
+        assert_eq!(schema.widgets().filter(|w| w.name == "Text").count(), 1);
+        assert!(schema.prop("Text", "size").is_none());
+    }
+}
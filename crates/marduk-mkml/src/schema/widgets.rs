@@ -0,0 +1,459 @@
+//! The built-in widget table: every widget the UI builder constructs and
+//! every property it reads.
+
+use super::{PropKind, PropSchema, WidgetSchema};
+
+const ALIGN: PropKind = PropKind::Enum(&["start", "center", "end", "stretch"]);
+
+// ── Shared prop sets (referenced by multiple widgets) ─────────────────────
+
+const PADDING_PROPS: &[PropSchema] = &[
+    PropSchema::new("padding",        PropKind::Number, "Uniform padding on all sides (logical px).").defaults_to("0"),
+    PropSchema::new("padding_top",    PropKind::Number, "Top padding (logical px)."),
+    PropSchema::new("padding_right",  PropKind::Number, "Right padding (logical px)."),
+    PropSchema::new("padding_bottom", PropKind::Number, "Bottom padding (logical px)."),
+    PropSchema::new("padding_left",   PropKind::Number, "Left padding (logical px)."),
+];
+
+const BORDER_PROPS: &[PropSchema] = &[
+    PropSchema::new("border_width", PropKind::Number, "Border stroke width (logical px)."),
+    PropSchema::new("border_color", PropKind::Color,  "Border stroke color (`#rrggbbaa`)."),
+];
+
+const STATE_PROPS: &[PropSchema] = &[
+    PropSchema::new("state_key", PropKind::Str,   "Key used to persist this widget's value across frame rebuilds. Defaults to `id`."),
+    PropSchema::new("on_change", PropKind::Event, "Event fired when the value changes. Also used as the state key if `id` and `state_key` are absent."),
+];
+
+const FONT_PROPS: &[PropSchema] = &[
+    PropSchema::new("font",      PropKind::Font,   "Font name (must be registered in `DslBindings`)."),
+    PropSchema::new("font_size", PropKind::Number, "Font size in logical pixels."),
+];
+
+/// Props for widgets that animate their state changes.
+const TRANSITION_PROPS: &[PropSchema] = &[
+    PropSchema::new("transition", PropKind::Number, "Duration of state changes — hover, toggling, opening — in milliseconds. `0` is instant.").defaults_to("0"),
+    PropSchema::new("easing",     PropKind::Enum(&["linear", "ease_in", "ease_out", "ease_in_out", "spring"]), "Curve of the transition; `spring` overshoots slightly and settles in about `transition` ms.").defaults_to("ease_out"),
+];
+
+/// Props for `Column` / `Row`; decoration props wrap the flex in a `Container`.
+const FLEX_PROPS: &[PropSchema] = &[
+    PropSchema::new("gap",           PropKind::Number, "Space between children (logical px).").defaults_to("0"),
+    PropSchema::new("spacing",       PropKind::Number, "Space between children (logical px).").alias_of("gap").deprecated(),
+    PropSchema::new("align",         ALIGN,            "Cross-axis alignment of children.").defaults_to("stretch"),
+    PropSchema::new("cross_align",   ALIGN,            "Cross-axis alignment of children.").alias_of("align").deprecated(),
+    PropSchema::new("bg",            PropKind::Color,  "Background color — wraps the flex in a Container automatically."),
+    PropSchema::new("corner_radius", PropKind::Number, "Corner radius (requires `bg` or a border)."),
+    PropSchema::new("radius",        PropKind::Number, "Corner radius (requires `bg` or a border).").alias_of("corner_radius"),
+    PADDING_PROPS[0], PADDING_PROPS[1], PADDING_PROPS[2], PADDING_PROPS[3], PADDING_PROPS[4],
+    BORDER_PROPS[0],  BORDER_PROPS[1],
+];
+
+// ── Common props ──────────────────────────────────────────────────────────
+
+/// Properties every widget accepts.
+pub static COMMON_PROPS: &[PropSchema] = &[
+    PropSchema::new("id", PropKind::Str, "Stable identity: keeps the widget (and its state) when siblings are added, removed or reordered."),
+    PropSchema::new("class", PropKind::Str, "Theme classes, separated by spaces: `class: \"primary small\"`. Their styles fill in unset properties, later classes first."),
+];
+
+/// Properties valid on `Stack` child items (anchor/size positioning).
+pub static STACK_CHILD_PROPS: &[PropSchema] = &[
+    PropSchema::new("left",   PropKind::Number, "Distance from the Stack's left edge (logical px)."),
+    PropSchema::new("top",    PropKind::Number, "Distance from the Stack's top edge (logical px)."),
+    PropSchema::new("right",  PropKind::Number, "Distance from the Stack's right edge (logical px)."),
+    PropSchema::new("bottom", PropKind::Number, "Distance from the Stack's bottom edge (logical px)."),
+    PropSchema::new("width",  PropKind::Size,   "Child width hint: `fill`, `natural`, or logical px.").defaults_to("natural"),
+    PropSchema::new("height", PropKind::Size,   "Child height hint: `fill`, `natural`, or logical px.").defaults_to("natural"),
+];
+
+// ── Widget registry ───────────────────────────────────────────────────────
+
+pub static BUILTIN_WIDGETS: &[WidgetSchema] = &[
+    // ── Text ──────────────────────────────────────────────────────────────
+    WidgetSchema {
+        name: "Text",
+        doc: "Renders a single line of text.\n\nInline content is the displayed string: `Text \"Hello\"`.",
+        has_content: true,
+        has_children: false,
+        props: &[
+            PropSchema::new("font",  PropKind::Font,   "Font name."),
+            PropSchema::new("size",  PropKind::Number, "Font size in logical pixels.").defaults_to("14"),
+            PropSchema::new("color", PropKind::Color,  "Text color (`#rrggbbaa`).").defaults_to("#ffffffff"),
+        ],
+    },
+
+    // ── Container ─────────────────────────────────────────────────────────
+    WidgetSchema {
+        name: "Container",
+        doc: "A box that holds one child widget.\n\nSupports padding, background, border, and rounded corners.",
+        has_content: false,
+        has_children: true,
+        props: &[
+            PropSchema::new("bg",            PropKind::Color,  "Background color (`#rrggbbaa`)."),
+            PropSchema::new("corner_radius", PropKind::Number, "Corner radius in logical pixels.").defaults_to("0"),
+            PropSchema::new("radius",        PropKind::Number, "Corner radius in logical pixels.").alias_of("corner_radius"),
+            PADDING_PROPS[0], PADDING_PROPS[1], PADDING_PROPS[2], PADDING_PROPS[3], PADDING_PROPS[4],
+            BORDER_PROPS[0],  BORDER_PROPS[1],
+        ],
+    },
+
+    // ── Column ────────────────────────────────────────────────────────────
+    WidgetSchema {
+        name: "Column",
+        doc: "Arranges children vertically.\n\nWrap in a `Container` (or use `bg`) for backgrounds and borders.",
+        has_content: false,
+        has_children: true,
+        props: FLEX_PROPS,
+    },
+
+    // ── Row ───────────────────────────────────────────────────────────────
+    WidgetSchema {
+        name: "Row",
+        doc: "Arranges children horizontally.",
+        has_content: false,
+        has_children: true,
+        props: FLEX_PROPS,
+    },
+
+    // ── Button ────────────────────────────────────────────────────────────
+    WidgetSchema {
+        name: "Button",
+        doc: "A pressable button.\n\nInline content is the label text. Or nest a child widget for a custom layout.",
+        has_content: true,
+        has_children: true,
+        props: &[
+            PropSchema::new("on_click",      PropKind::Event,  "Event fired on click."),
+            PropSchema::new("bg",            PropKind::Color,  "Default background color.").defaults_to("#00000000"),
+            PropSchema::new("hover_bg",      PropKind::Color,  "Background color while hovered.").defaults_to("#00000000"),
+            PropSchema::new("press_bg",      PropKind::Color,  "Background color while pressed.").defaults_to("#00000000"),
+            PropSchema::new("text_color",    PropKind::Color,  "Label text color.").defaults_to("#ffffffff"),
+            PropSchema::new("corner_radius", PropKind::Number, "Corner radius in logical pixels.").defaults_to("0"),
+            FONT_PROPS[0], FONT_PROPS[1],
+            PADDING_PROPS[0], PADDING_PROPS[1], PADDING_PROPS[2], PADDING_PROPS[3], PADDING_PROPS[4],
+            BORDER_PROPS[0], BORDER_PROPS[1],
+            TRANSITION_PROPS[0], TRANSITION_PROPS[1],
+        ],
+    },
+
+    // ── Checkbox ──────────────────────────────────────────────────────────
+    WidgetSchema {
+        name: "Checkbox",
+        doc: "A labelled checkbox.\n\nInline content is the label text.",
+        has_content: true,
+        has_children: false,
+        props: &[
+            PropSchema::new("checked",       PropKind::Bool,   "Initial checked state (`0` or `1`).").defaults_to("0"),
+            PropSchema::new("label",         PropKind::Str,    "Label text, when no inline content is given."),
+            PropSchema::new("label_color",   PropKind::Color,  "Label text color.").defaults_to("#d9d9e6ff"),
+            PropSchema::new("color",         PropKind::Color,  "Label text color.").alias_of("label_color"),
+            PropSchema::new("checked_color", PropKind::Color,  "Fill color when checked.").defaults_to("#33a6ffff"),
+            PropSchema::new("accent",        PropKind::Color,  "Fill color when checked.").alias_of("checked_color"),
+            PropSchema::new("box_size",      PropKind::Number, "Size of the checkbox square (logical px).").defaults_to("16"),
+            PropSchema::new("corner_radius", PropKind::Number, "Corner radius of the checkbox box.").defaults_to("3"),
+            PropSchema::new("border_color",  PropKind::Color,  "Border color of the checkbox box.").defaults_to("#597399ff"),
+            FONT_PROPS[0], FONT_PROPS[1].defaults_to("13"),
+            STATE_PROPS[0], STATE_PROPS[1],
+        ],
+    },
+
+    // ── Toggle ────────────────────────────────────────────────────────────
+    WidgetSchema {
+        name: "Toggle",
+        doc: "An on/off toggle switch.",
+        has_content: false,
+        has_children: false,
+        props: &[
+            PropSchema::new("checked",     PropKind::Bool,   "Initial state (`0` or `1`).").defaults_to("0"),
+            PropSchema::new("width",       PropKind::Number, "Toggle track width (logical px).").defaults_to("46"),
+            PropSchema::new("height",      PropKind::Number, "Toggle track height (logical px).").defaults_to("24"),
+            PropSchema::new("on_color",    PropKind::Color,  "Track color when on.").defaults_to("#1ab273ff"),
+            PropSchema::new("off_color",   PropKind::Color,  "Track color when off.").defaults_to("#334059ff"),
+            PropSchema::new("thumb_color", PropKind::Color,  "Thumb (knob) color.").defaults_to("#ffffffff"),
+            STATE_PROPS[0], STATE_PROPS[1],
+            TRANSITION_PROPS[0], TRANSITION_PROPS[1],
+        ],
+    },
+
+    // ── Slider ────────────────────────────────────────────────────────────
+    WidgetSchema {
+        name: "Slider",
+        doc: "A horizontal range slider.",
+        has_content: false,
+        has_children: false,
+        props: &[
+            PropSchema::new("min",           PropKind::Number, "Minimum value.").defaults_to("0"),
+            PropSchema::new("max",           PropKind::Number, "Maximum value.").defaults_to("1"),
+            PropSchema::new("value",         PropKind::Number, "Initial value (clamped to [min, max]).").defaults_to("min").range("min", "max"),
+            PropSchema::new("track_height",  PropKind::Number, "Track bar height (logical px).").defaults_to("4"),
+            PropSchema::new("thumb_radius",  PropKind::Number, "Thumb circle radius (logical px).").defaults_to("8"),
+            PropSchema::new("track_color",   PropKind::Color,  "Track background color.").defaults_to("#26334cff"),
+            PropSchema::new("fill_color",    PropKind::Color,  "Fill color left of the thumb.").defaults_to("#3399ffff"),
+            PropSchema::new("accent",        PropKind::Color,  "Fill color left of the thumb.").alias_of("fill_color"),
+            PropSchema::new("thumb_color",   PropKind::Color,  "Thumb color.").defaults_to("#ffffffff"),
+            PropSchema::new("corner_radius", PropKind::Number, "Track corner radius.").defaults_to("2"),
+            STATE_PROPS[0], STATE_PROPS[1],
+        ],
+    },
+
+    // ── RadioGroup ────────────────────────────────────────────────────────
+    WidgetSchema {
+        name: "RadioGroup",
+        doc: "A group of mutually-exclusive radio buttons.\n\nChildren must be `RadioOption` nodes.",
+        has_content: false,
+        has_children: true,
+        props: &[
+            PropSchema::new("default",      PropKind::Str,    "Initially selected option value."),
+            PropSchema::new("selected",     PropKind::Str,    "Initially selected option value.").alias_of("default"),
+            PropSchema::new("label_color",  PropKind::Color,  "Option label color.").defaults_to("#d9d9e6ff"),
+            PropSchema::new("color",        PropKind::Color,  "Option label color.").alias_of("label_color"),
+            PropSchema::new("accent",       PropKind::Color,  "Selected option fill color.").defaults_to("#33a6ffff"),
+            PropSchema::new("border_color", PropKind::Color,  "Radio button border color.").defaults_to("#597399ff"),
+            PropSchema::new("dot_radius",   PropKind::Number, "Inner dot radius (logical px).").defaults_to("8"),
+            PropSchema::new("item_gap",     PropKind::Number, "Vertical gap between options (logical px).").defaults_to("10"),
+            FONT_PROPS[0], FONT_PROPS[1].defaults_to("13"),
+            STATE_PROPS[0], STATE_PROPS[1],
+        ],
+    },
+
+    // ── RadioOption ───────────────────────────────────────────────────────
+    WidgetSchema {
+        name: "RadioOption",
+        doc: "A single option inside a `RadioGroup`.\n\nInline content is the display label.",
+        has_content: true,
+        has_children: false,
+        props: &[
+            PropSchema::new("value", PropKind::Str, "The string value this option represents. Defaults to the label."),
+        ],
+    },
+
+    // ── ProgressBar ───────────────────────────────────────────────────────
+    WidgetSchema {
+        name: "ProgressBar",
+        doc: "A non-interactive horizontal progress bar.",
+        has_content: false,
+        has_children: false,
+        props: &[
+            PropSchema::new("value",         PropKind::Number, "Fill fraction in [0, 1].").defaults_to("0").range("0", "1"),
+            PropSchema::new("height",        PropKind::Number, "Bar height (logical px).").defaults_to("6"),
+            PropSchema::new("track_color",   PropKind::Color,  "Track background color.").defaults_to("#26334cff"),
+            PropSchema::new("fill_color",    PropKind::Color,  "Fill color.").defaults_to("#33a6ffff"),
+            PropSchema::new("accent",        PropKind::Color,  "Fill color.").alias_of("fill_color"),
+            PropSchema::new("corner_radius", PropKind::Number, "Track corner radius.").defaults_to("3"),
+        ],
+    },
+
+    // ── TextBox ───────────────────────────────────────────────────────────
+    WidgetSchema {
+        name: "TextBox",
+        doc: "A single-line text input field.\n\nClick to focus, type to edit, Backspace to delete.",
+        has_content: true,
+        has_children: false,
+        props: &[
+            PropSchema::new("placeholder",          PropKind::Str,    "Placeholder text shown when the field is empty."),
+            PropSchema::new("text",                 PropKind::Str,    "Initial text content."),
+            PropSchema::new("text_color",           PropKind::Color,  "Input text color.").defaults_to("#e6ebf2ff"),
+            PropSchema::new("color",                PropKind::Color,  "Input text color.").alias_of("text_color"),
+            PropSchema::new("placeholder_color",    PropKind::Color,  "Placeholder text color.").defaults_to("#59738cff"),
+            PropSchema::new("bg",                   PropKind::Color,  "Background color when unfocused.").defaults_to("#0f1a29ff"),
+            PropSchema::new("focused_bg",           PropKind::Color,  "Background color when focused.").defaults_to("#122438ff"),
+            PropSchema::new("border_color",         PropKind::Color,  "Border color when unfocused.").defaults_to("#2e476bff"),
+            PropSchema::new("focused_border_color", PropKind::Color,  "Border color when focused.").defaults_to("#00abffff"),
+            PropSchema::new("accent",               PropKind::Color,  "Border color when focused.").alias_of("focused_border_color"),
+            PropSchema::new("selection_color",      PropKind::Color,  "Background color of selected text.").defaults_to("#1a66e666"),
+            PropSchema::new("corner_radius",        PropKind::Number, "Corner radius.").defaults_to("4"),
+            PropSchema::new("on_submit",            PropKind::Event,  "Event fired when the user presses Enter."),
+            FONT_PROPS[0], FONT_PROPS[1].defaults_to("13"),
+            PADDING_PROPS[0],
+            STATE_PROPS[0], STATE_PROPS[1],
+        ],
+    },
+
+    // ── ScrollView ────────────────────────────────────────────────────────
+    WidgetSchema {
+        name: "ScrollView",
+        doc: "A scrollable container with a single child.\n\nSupports mouse wheel and a thin scrollbar.",
+        has_content: false,
+        has_children: true,
+        props: &[
+            PropSchema::new("line_height",    PropKind::Number, "Scroll distance per mouse-wheel tick (logical px).").defaults_to("24"),
+            PropSchema::new("show_scrollbar", PropKind::Bool,   "Whether to draw the scrollbar (`0` or `1`).").defaults_to("1"),
+            PropSchema::new("offset",         PropKind::Number, "Initial scroll offset (logical px from top).").defaults_to("0"),
+            PropSchema::new("on_scroll",      PropKind::Event,  "Event fired when the scroll offset changes."),
+            STATE_PROPS[0],
+        ],
+    },
+
+    // ── Stack ─────────────────────────────────────────────────────────────
+    WidgetSchema {
+        name: "Stack",
+        doc: "Overlays children on top of each other.\n\nEach child can be anchored with `left`, `top`, `right`, `bottom`, `width`, `height`.",
+        has_content: false,
+        has_children: true,
+        props: &[
+            PropSchema::new("width",  PropKind::Size,  "Stack width hint: `fill`, `natural`, or logical px.").defaults_to("fill"),
+            PropSchema::new("height", PropKind::Size,  "Stack height hint: `fill`, `natural`, or logical px.").defaults_to("fill"),
+            PropSchema::new("bg",     PropKind::Color, "Background color."),
+        ],
+    },
+
+    // ── Image ─────────────────────────────────────────────────────────────
+    WidgetSchema {
+        name: "Image",
+        doc: "Draws an image registered in `DslBindings` under the `src` name.",
+        has_content: false,
+        has_children: false,
+        props: &[
+            PropSchema::new("src",           PropKind::Str,    "Image name (must be registered in `DslBindings`)."),
+            PropSchema::new("fit",           PropKind::Enum(&["contain", "fill", "cover", "none"]), "How the image is scaled into its box.").defaults_to("contain"),
+            PropSchema::new("corner_radius", PropKind::Number, "Corner radius in logical pixels.").defaults_to("0"),
+            PropSchema::new("radius",        PropKind::Number, "Corner radius in logical pixels.").alias_of("corner_radius"),
+            PropSchema::new("tint",          PropKind::Color,  "Color multiplied into the image.").defaults_to("#ffffffff"),
+        ],
+    },
+
+    // ── Tabs ──────────────────────────────────────────────────────────────
+    WidgetSchema {
+        name: "Tabs",
+        doc: "A tab bar with one page per child.\n\nChildren must be `Tab` nodes.",
+        has_content: false,
+        has_children: true,
+        props: &[
+            PropSchema::new("selected",        PropKind::Number, "Initially selected tab index.").defaults_to("0"),
+            PropSchema::new("tab_height",      PropKind::Number, "Height of the tab bar (logical px).").defaults_to("36"),
+            PropSchema::new("active_color",    PropKind::Color,  "Background of the selected tab.").defaults_to("#2e2e38ff"),
+            PropSchema::new("inactive_color",  PropKind::Color,  "Background of the other tabs.").defaults_to("#1f1f26ff"),
+            PropSchema::new("indicator_color", PropKind::Color,  "Selected-tab underline color.").defaults_to("#638cffff"),
+            PropSchema::new("accent",          PropKind::Color,  "Selected-tab underline color.").alias_of("indicator_color"),
+            FONT_PROPS[0], FONT_PROPS[1].defaults_to("14"),
+            STATE_PROPS[0], STATE_PROPS[1],
+            TRANSITION_PROPS[0], TRANSITION_PROPS[1],
+        ],
+    },
+
+    // ── Tab ───────────────────────────────────────────────────────────────
+    WidgetSchema {
+        name: "Tab",
+        doc: "A single page inside `Tabs`.\n\nInline content is the tab label; the first child is the page.",
+        has_content: true,
+        has_children: true,
+        props: &[],
+    },
+
+    // ── Splitter ──────────────────────────────────────────────────────────
+    WidgetSchema {
+        name: "Splitter",
+        doc: "Two panes separated by a draggable handle.\n\nThe first two children are the panes.",
+        has_content: false,
+        has_children: true,
+        props: &[
+            PropSchema::new("direction",   PropKind::Enum(&["horizontal", "vertical", "v"]), "Split direction.").defaults_to("horizontal"),
+            PropSchema::new("ratio",       PropKind::Number, "Initial size of the first pane, in [0, 1].").defaults_to("0.5").range("0", "1"),
+            PropSchema::new("min_first",   PropKind::Number, "Minimum size of the first pane (logical px).").defaults_to("0"),
+            PropSchema::new("min_second",  PropKind::Number, "Minimum size of the second pane (logical px).").defaults_to("0"),
+            PropSchema::new("handle_size", PropKind::Number, "Thickness of the drag handle (logical px).").defaults_to("4"),
+            STATE_PROPS[0], STATE_PROPS[1],
+        ],
+    },
+
+    // ── NumberInput ───────────────────────────────────────────────────────
+    WidgetSchema {
+        name: "NumberInput",
+        doc: "A numeric field with step buttons.",
+        has_content: false,
+        has_children: false,
+        props: &[
+            PropSchema::new("value",                PropKind::Number, "Initial value.").defaults_to("0").range("min", "max"),
+            PropSchema::new("min",                  PropKind::Number, "Minimum value. Unbounded when absent."),
+            PropSchema::new("max",                  PropKind::Number, "Maximum value. Unbounded when absent."),
+            PropSchema::new("step",                 PropKind::Number, "Increment per step.").defaults_to("1"),
+            PropSchema::new("decimals",             PropKind::Number, "Digits shown after the decimal point.").defaults_to("0"),
+            PropSchema::new("width",                PropKind::Number, "Field width (logical px).").defaults_to("120"),
+            PropSchema::new("height",               PropKind::Number, "Field height (logical px).").defaults_to("32"),
+            PropSchema::new("text_color",           PropKind::Color,  "Text color.").defaults_to("#ffffffff"),
+            PropSchema::new("color",                PropKind::Color,  "Text color.").alias_of("text_color"),
+            PropSchema::new("bg",                   PropKind::Color,  "Background color.").defaults_to("#26262eff"),
+            PropSchema::new("border_color",         PropKind::Color,  "Border color when unfocused.").defaults_to("#4c4c59ff"),
+            PropSchema::new("focused_border_color", PropKind::Color,  "Border color when focused.").defaults_to("#638cffff"),
+            PropSchema::new("accent",               PropKind::Color,  "Border color when focused.").alias_of("focused_border_color"),
+            PropSchema::new("corner_radius",        PropKind::Number, "Corner radius.").defaults_to("4"),
+            FONT_PROPS[0], FONT_PROPS[1].defaults_to("14"),
+            STATE_PROPS[0], STATE_PROPS[1],
+        ],
+    },
+
+    // ── Tooltip ───────────────────────────────────────────────────────────
+    WidgetSchema {
+        name: "Tooltip",
+        doc: "Shows a text bubble while its child is hovered.\n\nInline content is the tooltip text.",
+        has_content: true,
+        has_children: true,
+        props: &[
+            PropSchema::new("text",       PropKind::Str,    "Tooltip text, when no inline content is given."),
+            PropSchema::new("delay_ms",   PropKind::Number, "Hover time before the tooltip appears (ms).").defaults_to("400"),
+            PropSchema::new("text_color", PropKind::Color,  "Text color.").defaults_to("#f2f2f2ff"),
+            PropSchema::new("color",      PropKind::Color,  "Text color.").alias_of("text_color"),
+            PropSchema::new("bg",         PropKind::Color,  "Bubble background color.").defaults_to("#1f1f26f7"),
+            FONT_PROPS[0], FONT_PROPS[1].defaults_to("12"),
+            STATE_PROPS[0],
+        ],
+    },
+
+    // ── Modal ─────────────────────────────────────────────────────────────
+    WidgetSchema {
+        name: "Modal",
+        doc: "A dialog drawn over a dimmed backdrop.\n\nThe first child is the dialog body.",
+        has_content: false,
+        has_children: true,
+        props: &[
+            PropSchema::new("open",           PropKind::Bool,   "Initially open (`0` or `1`).").defaults_to("0"),
+            PropSchema::new("title",          PropKind::Str,    "Dialog title."),
+            PropSchema::new("max_width",      PropKind::Number, "Maximum dialog width (logical px).").defaults_to("480"),
+            PropSchema::new("bg",             PropKind::Color,  "Dialog background color.").defaults_to("#292933ff"),
+            PropSchema::new("backdrop_color", PropKind::Color,  "Color of the dimmed backdrop.").defaults_to("#00000099"),
+            PropSchema::new("corner_radius",  PropKind::Number, "Dialog corner radius.").defaults_to("8"),
+            PropSchema::new("on_dismiss",     PropKind::Event,  "Event fired when the dialog is dismissed."),
+            FONT_PROPS[0], FONT_PROPS[1].defaults_to("16"),
+            STATE_PROPS[0],
+            TRANSITION_PROPS[0], TRANSITION_PROPS[1],
+        ],
+    },
+
+    // ── Combobox ──────────────────────────────────────────────────────────
+    WidgetSchema {
+        name: "Combobox",
+        doc: "A drop-down list of options.\n\nChildren must be `Option` nodes.",
+        has_content: false,
+        has_children: true,
+        props: &[
+            PropSchema::new("selected",             PropKind::Str,    "Initially selected option value."),
+            PropSchema::new("value",                PropKind::Str,    "Initially selected option value.").alias_of("selected"),
+            PropSchema::new("placeholder",          PropKind::Str,    "Text shown when nothing is selected.").defaults_to("\"Select…\""),
+            PropSchema::new("width",                PropKind::Number, "Field width (logical px).").defaults_to("160"),
+            PropSchema::new("height",               PropKind::Number, "Field height (logical px).").defaults_to("32"),
+            PropSchema::new("max_dropdown_height",  PropKind::Number, "Maximum height of the open list (logical px).").defaults_to("200"),
+            PropSchema::new("text_color",           PropKind::Color,  "Text color.").defaults_to("#ffffffff"),
+            PropSchema::new("color",                PropKind::Color,  "Text color.").alias_of("text_color"),
+            PropSchema::new("bg",                   PropKind::Color,  "Background color.").defaults_to("#26262eff"),
+            PropSchema::new("border_color",         PropKind::Color,  "Border color when closed.").defaults_to("#4c4c59ff"),
+            PropSchema::new("focused_border_color", PropKind::Color,  "Border color when open.").defaults_to("#638cffff"),
+            PropSchema::new("accent",               PropKind::Color,  "Border color when open.").alias_of("focused_border_color"),
+            PropSchema::new("item_bg",              PropKind::Color,  "Option background color.").defaults_to("#212129ff"),
+            PropSchema::new("item_hover_bg",        PropKind::Color,  "Option background color while hovered.").defaults_to("#383845ff"),
+            PropSchema::new("corner_radius",        PropKind::Number, "Corner radius.").defaults_to("4"),
+            FONT_PROPS[0], FONT_PROPS[1].defaults_to("14"),
+            STATE_PROPS[0], STATE_PROPS[1],
+        ],
+    },
+
+    // ── Option ────────────────────────────────────────────────────────────
+    WidgetSchema {
+        name: "Option",
+        doc: "A single choice inside a `Combobox`.\n\nInline content is the display label.",
+        has_content: true,
+        has_children: false,
+        props: &[
+            PropSchema::new("value", PropKind::Str, "The string value this option represents. Defaults to the label."),
+        ],
+    },
+];
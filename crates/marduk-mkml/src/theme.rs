@@ -0,0 +1,371 @@
+//! Themes: named tokens and default styles shared by every document.
+//!
+//! A theme is written in `.mkml` syntax with a `Theme` root. Its properties
+//! are tokens; each child is the style for one widget, or — with a class
+//! name as its content — for that widget when the class is set on it:
+//!
+//! ```mkml
+//! Theme {
+//!     surface: #1a1a2aff
+//!     accent:  #638cffff
+//!     text:    #e6ebf2ff
+//!
+//!     Button { bg: $surface  corner_radius: 4  text_color: $text  font: body }
+//!     Button "primary" { bg: $accent }
+//! }
+//! ```
+//!
+//! Documents refer to tokens as `$accent` and pick class styles with
+//! `class: primary` (several separated by spaces). [`Theme::apply`] fills in
+//! what a node leaves unset — from its class styles, later classes first,
+//! then its widget's style — and replaces its `$tokens` with their values.
+//! Whatever the theme does not set keeps the widget's built-in default.
+
+use std::collections::HashMap;
+
+use crate::ast::{DslDocument, Node, Prop, Span, Value};
+use crate::check::{accepts, closest, Issue, Severity};
+use crate::error::ParseError;
+use crate::parser::parse_str;
+use crate::schema::Schema;
+
+/// Widget name of a theme document's root.
+pub const THEME_ROOT: &str = "Theme";
+
+// ── Theme ─────────────────────────────────────────────────────────────────
+
+/// Tokens and widget styles; see the [module docs](self).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Theme {
+    /// Token values, references to other tokens already followed.
+    tokens: HashMap<String, Value>,
+    styles: Vec<Style>,
+}
+
+/// The properties a theme sets on `widget`, or on `widget` with `class`.
+#[derive(Debug, Clone, PartialEq)]
+struct Style {
+    widget: String,
+    class:  Option<String>,
+    /// Token references already replaced.
+    props:  Vec<Prop>,
+}
+
+impl Theme {
+    /// A theme with no tokens or styles: [`apply`](Self::apply) only drops
+    /// `$token` properties.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse a theme document.
+    pub fn parse(src: &str) -> Result<Self, ParseError> {
+        Self::from_document(&parse_str(src)?)
+    }
+
+    /// The theme a parsed document describes. Fails when the root is not
+    /// `Theme`, a style has child widgets, or a token is unknown or refers
+    /// back to itself.
+    pub fn from_document(doc: &DslDocument) -> Result<Self, ParseError> {
+        let root = &doc.root;
+        if root.widget != THEME_ROOT || root.control.is_some() {
+            return Err(error(root.name_span, format!("a theme's root must be `{THEME_ROOT}`")));
+        }
+
+        let mut theme = Self::new();
+        for prop in &root.props {
+            let value = resolve_token(root, prop, &mut Vec::new())?;
+            theme.tokens.insert(prop.key.clone(), value);
+        }
+
+        for node in &root.children {
+            if node.control.is_some() {
+                return Err(error(node.name_span, "a theme holds only widget styles"));
+            }
+            if let Some(child) = node.children.first() {
+                return Err(error(child.name_span, "a style takes no child widgets"));
+            }
+            let class = match &node.content {
+                None => None,
+                Some(Value::Str(class)) => Some(class.clone()),
+                Some(_) => {
+                    let span = node.content_span.unwrap_or(node.name_span);
+                    return Err(error(span, "a style's class must be a plain string"));
+                }
+            };
+            let mut props = Vec::with_capacity(node.props.len());
+            for prop in &node.props {
+                let mut prop = prop.clone();
+                if let Value::ThemeRef(name) = &prop.value {
+                    prop.value = theme.tokens.get(name).cloned().ok_or_else(|| unknown(&theme, name, prop.value_span))?;
+                }
+                props.push(prop);
+            }
+            // A repeated style extends the earlier one; its values win.
+            match theme.styles.iter_mut().find(|s| s.widget == node.widget && s.class == class) {
+                Some(style) => {
+                    style.props.retain(|p| props.iter().all(|q| q.key != p.key));
+                    style.props.extend(props);
+                }
+                None => theme.styles.push(Style { widget: node.widget.clone(), class, props }),
+            }
+        }
+        Ok(theme)
+    }
+
+    /// The value of token `name`.
+    pub fn token(&self, name: &str) -> Option<&Value> {
+        self.tokens.get(name)
+    }
+
+    /// Every token, in no particular order.
+    pub fn tokens(&self) -> impl Iterator<Item = (&str, &Value)> {
+        self.tokens.iter().map(|(name, value)| (name.as_str(), value))
+    }
+
+    /// Whether [`apply`](Self::apply) would change `node`.
+    pub fn applies_to(&self, node: &Node) -> bool {
+        node.control.is_none()
+            && (node.props.iter().any(|p| matches!(p.value, Value::ThemeRef(_)))
+                || self.styles.iter().any(|s| s.widget == node.widget))
+    }
+
+    /// Fill in the properties `node` leaves unset from its styles, and
+    /// replace its `$tokens` with their values. A token the theme does not
+    /// define removes the property, so the widget falls back to its default.
+    ///
+    /// Only `node` itself is styled, not its children.
+    pub fn apply(&self, node: &mut Node) {
+        if node.control.is_some() {
+            return;
+        }
+        let classes: Vec<String> = node
+            .prop_str("class")
+            .map(|c| c.split_whitespace().map(str::to_string).collect())
+            .unwrap_or_default();
+        let styles = classes
+            .iter()
+            .rev()
+            .filter_map(|class| self.style(&node.widget, Some(class)))
+            .chain(self.style(&node.widget, None));
+        let mut inherited = Vec::new();
+        for style in styles {
+            for prop in &style.props {
+                let unset = node.find_prop(&prop.key).is_none()
+                    && inherited.iter().all(|p: &Prop| p.key != prop.key);
+                if unset {
+                    inherited.push(prop.clone());
+                }
+            }
+        }
+        node.props.extend(inherited);
+        node.props.retain_mut(|p| match &p.value {
+            Value::ThemeRef(name) => match self.tokens.get(name) {
+                Some(value) => {
+                    p.value = value.clone();
+                    true
+                }
+                None => false,
+            },
+            _ => true,
+        });
+    }
+
+    /// What the theme sets on a `widget` with `class`: a node with no
+    /// properties of its own, [`apply`](Self::apply)-ed. For styling widgets
+    /// built without a document.
+    pub fn style_of(&self, widget: &str, class: Option<&str>) -> Node {
+        let mut node = Node {
+            widget:       widget.to_string(),
+            content:      None,
+            props:        Vec::new(),
+            children:     Vec::new(),
+            control:      None,
+            span:         Span::default(),
+            name_span:    Span::default(),
+            content_span: None,
+        };
+        if let Some(class) = class {
+            node.props.push(Prop {
+                key:        "class".to_string(),
+                value:      Value::Str(class.to_string()),
+                key_span:   Span::default(),
+                value_span: Span::default(),
+            });
+        }
+        self.apply(&mut node);
+        node
+    }
+
+    /// `$token` references in `doc` the theme does not define, and tokens
+    /// whose value the property they are used for does not accept.
+    pub fn check(&self, doc: &DslDocument, schema: &Schema) -> Vec<Issue> {
+        let mut out = Vec::new();
+        check_node(self, &doc.root, schema, &mut out);
+        out
+    }
+
+    // ── internal ──────────────────────────────────────────────────────────
+
+    fn style(&self, widget: &str, class: Option<&str>) -> Option<&Style> {
+        self.styles.iter().find(|s| s.widget == widget && s.class.as_deref() == class)
+    }
+}
+
+/// The value of token `prop`, following `$references` to other tokens of
+/// `root`. `seen` holds the tokens being resolved, to catch cycles.
+fn resolve_token(root: &Node, prop: &Prop, seen: &mut Vec<String>) -> Result<Value, ParseError> {
+    let Value::ThemeRef(name) = &prop.value else {
+        return Ok(prop.value.clone());
+    };
+    seen.push(prop.key.clone());
+    if seen.contains(name) {
+        return Err(error(prop.value_span, format!("theme token `${}` refers to itself", prop.key)));
+    }
+    let Some(target) = root.find_prop(name) else {
+        let known = root.props.iter().map(|p| p.key.as_str());
+        return Err(error(prop.value_span, unknown_message(name, closest(name, known))));
+    };
+    resolve_token(root, target, seen)
+}
+
+fn check_node(theme: &Theme, node: &Node, schema: &Schema, out: &mut Vec<Issue>) {
+    for prop in &node.props {
+        let Value::ThemeRef(name) = &prop.value else { continue };
+        match theme.tokens.get(name) {
+            None => {
+                let fix = closest(name, theme.tokens.keys().map(String::as_str));
+                out.push(Issue {
+                    severity:    Severity::Warning,
+                    span:        prop.value_span,
+                    message:     unknown_message(name, fix),
+                    replacement: fix.map(|f| format!("${f}")),
+                });
+            }
+            Some(value) => {
+                if let Some(info) = schema.prop(&node.widget, &prop.key)
+                    && !accepts(info.kind, value)
+                {
+                    out.push(Issue {
+                        severity:    Severity::Error,
+                        span:        prop.value_span,
+                        message:     format!("`{}` expects {}, but `${name}` is not one", prop.key, info.kind.describe()),
+                        replacement: None,
+                    });
+                }
+            }
+        }
+    }
+    let otherwise = match &node.control {
+        Some(crate::ast::Control::If { otherwise, .. }) => otherwise.as_slice(),
+        _ => &[],
+    };
+    for child in node.children.iter().chain(otherwise) {
+        check_node(theme, child, schema, out);
+    }
+}
+
+fn unknown(theme: &Theme, name: &str, span: Span) -> ParseError {
+    error(span, unknown_message(name, closest(name, theme.tokens.keys().map(String::as_str))))
+}
+
+fn unknown_message(name: &str, closest: Option<&str>) -> String {
+    match closest {
+        Some(c) => format!("unknown theme token `${name}`; did you mean `${c}`?"),
+        None => format!("unknown theme token `${name}`"),
+    }
+}
+
+fn error(span: Span, message: impl Into<String>) -> ParseError {
+    ParseError::new(message, span.start.line, span.start.col)
+}
+
+// ── tests ─────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DARK: &str = r#"
+        Theme {
+            surface: #1a1a2aff
+            accent:  #638cffff
+            focus:   $accent
+
+            Button { bg: $surface  corner_radius: 4  font: body }
+            Button "primary" { bg: $accent }
+            Button "round" { corner_radius: 12 }
+            TextBox { focused_border: $focus }
+        }
+    "#;
+
+    fn node(src: &str) -> Node {
+        parse_str(src).unwrap().root
+    }
+
+    #[test]
+    fn styles_fill_in_what_nodes_leave_unset() {
+        let theme = Theme::parse(DARK).unwrap();
+        let mut button = node("Button \"Go\" { corner_radius: 2 }");
+        assert!(theme.applies_to(&button));
+        theme.apply(&mut button);
+        assert_eq!(button.prop_color("bg"), Some([0x1a, 0x1a, 0x2a, 0xff]));
+        assert_eq!(button.prop_f32("corner_radius"), Some(2.0));
+        assert_eq!(button.prop_str("font"), Some("body"));
+
+        let mut text = node("Text \"plain\" { }");
+        assert!(!theme.applies_to(&text));
+        theme.apply(&mut text);
+        assert!(text.props.is_empty());
+    }
+
+    #[test]
+    fn later_classes_win_over_earlier_ones_and_the_widget_style() {
+        let theme = Theme::parse(DARK).unwrap();
+        let mut button = node("Button { class: \"round primary\" }");
+        theme.apply(&mut button);
+        assert_eq!(button.prop_color("bg"), Some([0x63, 0x8c, 0xff, 0xff]));
+        assert_eq!(button.prop_f32("corner_radius"), Some(12.0));
+    }
+
+    #[test]
+    fn style_of_matches_an_unstyled_node() {
+        let theme = Theme::parse(DARK).unwrap();
+        let mut button = node("Button { class: \"primary\" }");
+        theme.apply(&mut button);
+        assert_eq!(theme.style_of("Button", Some("primary")).props, button.props);
+        assert_eq!(theme.style_of("Button", None).prop_color("bg"), Some([0x1a, 0x1a, 0x2a, 0xff]));
+        assert!(theme.style_of("Slider", None).props.is_empty());
+    }
+
+    #[test]
+    fn tokens_are_replaced_and_unknown_ones_dropped() {
+        let theme = Theme::parse(DARK).unwrap();
+        assert_eq!(theme.token("focus"), theme.token("accent"));
+
+        let mut container = node("Container { bg: $accent  border_color: $missing }");
+        theme.apply(&mut container);
+        assert_eq!(container.prop_color("bg"), Some([0x63, 0x8c, 0xff, 0xff]));
+        assert!(container.prop("border_color").is_none());
+    }
+
+    #[test]
+    fn malformed_themes_are_rejected() {
+        let err = |src: &str| Theme::parse(src).unwrap_err().message;
+        assert!(err("Column { }").contains("root must be `Theme`"));
+        assert!(err("Theme { a: $b  b: $a }").contains("refers to itself"));
+        assert!(err("Theme { accent: #ffffffff  Button { bg: $acent } }").contains("did you mean `$accent`"));
+        assert!(err("Theme { Button { Text { } } }").contains("no child widgets"));
+    }
+
+    #[test]
+    fn documents_are_checked_against_the_tokens() {
+        let theme = Theme::parse(DARK).unwrap();
+        let doc = parse_str("Column { gap: $accent  Container { bg: $acent } }").unwrap();
+        let issues = theme.check(&doc, &Schema::new());
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].severity, Severity::Error);
+        assert!(issues[0].message.contains("`gap` expects number"), "{}", issues[0].message);
+        assert_eq!(issues[1].replacement.as_deref(), Some("$accent"));
+    }
+}
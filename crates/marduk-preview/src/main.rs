@@ -0,0 +1,161 @@
+//! `marduk-preview` — render a `.mkml` file to PNG without a window.
+//!
+//! ```text
+//! marduk-preview ui/main.mkml --size 800x600 --scale 2 -o main.png
+//! ```
+//!
+//! Editors run it on every save and show the image beside the source; see
+//! `editors/vscode`. Without `-o` the PNG is written to stdout.
+
+mod preview;
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use marduk_ui::prelude::Vec2;
+
+use preview::Preview;
+
+const USAGE: &str = "\
+usage: marduk-preview <file.mkml> [options]
+
+options:
+  -o, --output <file.png>   write the PNG here instead of stdout
+  --size <W>x<H>            viewport in logical pixels (default 800x600)
+  --scale <factor>          physical pixels per logical pixel (default 1)
+  --font <name>=<path>      register a font (repeatable; the first is the default)
+  --image <name>=<path>     register an image (repeatable)
+  --theme <file.mkml>       style the document with this theme
+  --assets <dir>            register <dir>/fonts/* and <dir>/images/* by file stem,
+                            and use <dir>/theme.mkml as the theme (default: the
+                            nearest directory above the document that has any)";
+
+/// Parsed command line.
+struct Args {
+    input:  PathBuf,
+    output: Option<PathBuf>,
+    size:   Vec2,
+    scale:  f32,
+    fonts:  Vec<(String, PathBuf)>,
+    images: Vec<(String, PathBuf)>,
+    theme:  Option<PathBuf>,
+    assets: Vec<PathBuf>,
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Err(e) => {
+            eprintln!("marduk-preview: {e}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let mut preview = Preview::new(args.size).scale(args.scale);
+    if let Some(path) = args.theme {
+        preview = preview.theme(path);
+    }
+    let assets = if args.assets.is_empty() {
+        default_assets(&args.input).into_iter().collect()
+    } else {
+        args.assets
+    };
+    for dir in assets {
+        preview = preview.assets(dir);
+    }
+    for (name, path) in args.fonts {
+        preview = preview.font(name, path);
+    }
+    for (name, path) in args.images {
+        preview = preview.image(name, path);
+    }
+
+    let png = match preview.render(&args.input) {
+        Ok(png) => png,
+        Err(e) => {
+            eprintln!("marduk-preview: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let written = match &args.output {
+        Some(path) => std::fs::write(path, &png),
+        None => std::io::stdout().lock().write_all(&png),
+    };
+    if let Err(e) = written {
+        eprintln!("marduk-preview: cannot write the PNG: {e}");
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
+
+/// `Ok(None)` when help was asked for.
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Option<Args>, String> {
+    let mut input = None;
+    let mut out = Args {
+        input:  PathBuf::new(),
+        output: None,
+        size:   Vec2::new(800.0, 600.0),
+        scale:  1.0,
+        fonts:  Vec::new(),
+        images: Vec::new(),
+        theme:  None,
+        assets: Vec::new(),
+    };
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or_else(|| format!("`{arg}` needs a value"));
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "-o" | "--output" => out.output = Some(value()?.into()),
+            "--size" => {
+                let v = value()?;
+                let (w, h) = v
+                    .split_once('x')
+                    .and_then(|(w, h)| Some((w.parse::<f32>().ok()?, h.parse::<f32>().ok()?)))
+                    .filter(|(w, h)| *w > 0.0 && *h > 0.0)
+                    .ok_or_else(|| format!("`--size {v}`: expected <width>x<height>"))?;
+                out.size = Vec2::new(w, h);
+            }
+            "--scale" => {
+                let v = value()?;
+                out.scale = v
+                    .parse::<f32>()
+                    .ok()
+                    .filter(|s| *s > 0.0)
+                    .ok_or_else(|| format!("`--scale {v}`: expected a positive number"))?;
+            }
+            "--font" => out.fonts.push(named(&value()?)?),
+            "--image" => out.images.push(named(&value()?)?),
+            "--theme" => out.theme = Some(value()?.into()),
+            "--assets" => out.assets.push(value()?.into()),
+            _ if arg.starts_with('-') => return Err(format!("unknown option `{arg}`")),
+            _ if input.is_none() => input = Some(PathBuf::from(arg)),
+            _ => return Err(format!("unexpected argument `{arg}`")),
+        }
+    }
+    out.input = input.ok_or("no input file")?;
+    Ok(Some(out))
+}
+
+/// `name=path`.
+fn named(v: &str) -> Result<(String, PathBuf), String> {
+    match v.split_once('=') {
+        Some((name, path)) if !name.is_empty() && !path.is_empty() => Ok((name.to_string(), path.into())),
+        _ => Err(format!("`{v}`: expected <name>=<path>")),
+    }
+}
+
+/// The nearest directory above `input` with a `fonts/` or `images/` folder
+/// or a `theme.mkml`.
+fn default_assets(input: &Path) -> Option<PathBuf> {
+    let input = std::path::absolute(input).ok()?;
+    input
+        .ancestors()
+        .skip(1)
+        .find(|dir| dir.join("fonts").is_dir() || dir.join("images").is_dir() || dir.join("theme.mkml").is_file())
+        .map(Path::to_path_buf)
+}
@@ -0,0 +1,296 @@
+//! Renders a `.mkml` document, with the files it imports, to a PNG.
+//!
+//! The document goes through the same path as under `Application`: a
+//! [`DslLoader`] with every import registered under its alias, reconciled
+//! by the testing [`Harness`] and drawn by the CPU renderer, so no GPU or
+//! window is needed. Events fire into a queue nobody reads and `{path}`
+//! bindings have no model, so their props fall back to widget defaults.
+//! A theme, when given, styles the document as `Application::theme` does.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use image::{ExtendedColorType, ImageEncoder};
+use marduk_ui::dsl::theme::Theme;
+use marduk_ui::dsl::{DslBindings, DslDocument, DslLoader, ParseError};
+use marduk_ui::prelude::{UiScene, Vec2};
+use marduk_ui::testing::Harness;
+
+/// Fonts tried when none is registered, under the name `body`.
+const SYSTEM_FONTS: &[&str] = &[
+    "/usr/share/fonts/TTF/DejaVuSans.ttf",
+    "/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf",
+    "/usr/share/fonts/dejavu/DejaVuSans.ttf",
+    "/usr/share/fonts/noto/NotoSans-Regular.ttf",
+    "/usr/share/fonts/truetype/noto/NotoSans-Regular.ttf",
+];
+
+const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "bmp", "gif", "ico", "tiff", "webp", "svg"];
+const FONT_EXTENSIONS: &[&str] = &["ttf", "otf"];
+/// Theme picked up by [`Preview::assets`].
+const THEME_FILE: &str = "theme.mkml";
+
+// ── PreviewError ──────────────────────────────────────────────────────────
+
+/// Why a document could not be rendered.
+#[derive(Debug)]
+pub enum PreviewError {
+    /// A document, font or image could not be read or decoded.
+    Io { path: PathBuf, message: String },
+    /// A document (the previewed one, an import or the theme) does not parse.
+    Parse { path: PathBuf, error: ParseError },
+}
+
+impl fmt::Display for PreviewError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io { path, message } => write!(f, "{}: {message}", path.display()),
+            Self::Parse { path, error } => {
+                write!(f, "{}:{}:{}: {}", path.display(), error.line, error.col, error.message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PreviewError {}
+
+fn io_err(path: &Path, e: impl fmt::Display) -> PreviewError {
+    PreviewError::Io { path: path.to_path_buf(), message: e.to_string() }
+}
+
+// ── Preview ───────────────────────────────────────────────────────────────
+
+/// Renders documents at a fixed viewport with a set of named assets.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let png = Preview::new(Vec2::new(800.0, 600.0))
+///     .scale(2.0)
+///     .font("body", "assets/Inter.ttf")
+///     .assets("crates/marduk-studio/ui")
+///     .render(Path::new("crates/marduk-studio/ui/main.mkml"))?;
+/// ```
+pub struct Preview {
+    viewport: Vec2,
+    scale:    f32,
+    fonts:    Vec<(String, PathBuf)>,
+    images:   Vec<(String, PathBuf)>,
+    theme:    Option<PathBuf>,
+}
+
+impl Preview {
+    /// A preview of `viewport` logical pixels at scale `1.0`.
+    pub fn new(viewport: Vec2) -> Self {
+        Self { viewport, scale: 1.0, fonts: Vec::new(), images: Vec::new(), theme: None }
+    }
+
+    /// Physical pixels per logical pixel.
+    pub fn scale(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Register the font file at `path` as `font: name`. The first font
+    /// registered is the default.
+    pub fn font(mut self, name: impl Into<String>, path: impl Into<PathBuf>) -> Self {
+        self.fonts.push((name.into(), path.into()));
+        self
+    }
+
+    /// Register the image file at `path` as `src: name`.
+    pub fn image(mut self, name: impl Into<String>, path: impl Into<PathBuf>) -> Self {
+        self.images.push((name.into(), path.into()));
+        self
+    }
+
+    /// Style documents with the theme file at `path`.
+    pub fn theme(mut self, path: impl Into<PathBuf>) -> Self {
+        self.theme = Some(path.into());
+        self
+    }
+
+    /// Register everything in `dir/fonts` and `dir/images` under its file stem:
+    /// `images/truck_icon.svg` is `src: truck_icon`. `dir/theme.mkml` is the
+    /// theme unless one was already set.
+    pub fn assets(mut self, dir: impl AsRef<Path>) -> Self {
+        let dir = dir.as_ref();
+        self.fonts.extend(files_in(&dir.join("fonts"), FONT_EXTENSIONS));
+        self.images.extend(files_in(&dir.join("images"), IMAGE_EXTENSIONS));
+        if self.theme.is_none() && dir.join(THEME_FILE).is_file() {
+            self.theme = Some(dir.join(THEME_FILE));
+        }
+        self
+    }
+
+    /// Render the document at `path` to PNG bytes.
+    pub fn render(&self, path: &Path) -> Result<Vec<u8>, PreviewError> {
+        let mut loader = DslLoader::new();
+        let src = load(&mut loader, path, &mut Vec::new())?.1;
+        if let Some(path) = &self.theme {
+            let src = std::fs::read_to_string(path).map_err(|e| io_err(path, e))?;
+            let theme = Theme::parse(&src).map_err(|error| PreviewError::Parse { path: path.clone(), error })?;
+            loader.set_theme(theme);
+        }
+
+        let mut scene = UiScene::new();
+        let mut bindings = DslBindings::new();
+        self.load_assets(&mut scene, &mut bindings)?;
+
+        let mut harness = Harness::mkml(scene, loader, &src, bindings, self.viewport)
+            .map_err(|error| PreviewError::Parse { path: path.to_path_buf(), error })?
+            .scale(self.scale);
+        let pixmap = harness.render();
+
+        let mut png = Vec::new();
+        image::codecs::png::PngEncoder::new(&mut png)
+            .write_image(&pixmap.to_rgba8(), pixmap.width(), pixmap.height(), ExtendedColorType::Rgba8)
+            .map_err(|e| io_err(path, e))?;
+        Ok(png)
+    }
+
+    // ── internal ──────────────────────────────────────────────────────────
+
+    fn load_assets(&self, scene: &mut UiScene, bindings: &mut DslBindings) -> Result<(), PreviewError> {
+        for (name, path) in &self.fonts {
+            let bytes = std::fs::read(path).map_err(|e| io_err(path, e))?;
+            let id = scene.load_font(&bytes).map_err(|e| io_err(path, format!("{e:?}")))?;
+            bindings.fonts.insert(name.clone(), id);
+        }
+        if bindings.fonts.is_empty()
+            && let Some(bytes) = SYSTEM_FONTS.iter().find_map(|p| std::fs::read(p).ok())
+            && let Ok(id) = scene.load_font(&bytes)
+        {
+            bindings.fonts.insert("body".to_string(), id);
+        }
+        for (name, path) in &self.images {
+            let bytes = std::fs::read(path).map_err(|e| io_err(path, e))?;
+            // SVGs are rasterized at the output scale, as `Application` does.
+            let id = scene.load_image_bytes(&bytes, self.scale).map_err(|e| io_err(path, e))?;
+            bindings.images.insert(name.clone(), id);
+        }
+        Ok(())
+    }
+}
+
+/// Parses `path` and, depth first, registers everything it imports.
+///
+/// Imports resolve relative to the importing file. `stack` holds the files
+/// being loaded; an import leading back into it is skipped, leaving the
+/// alias unregistered rather than recursing forever.
+fn load(
+    loader: &mut DslLoader,
+    path: &Path,
+    stack: &mut Vec<PathBuf>,
+) -> Result<(DslDocument, String), PreviewError> {
+    let src = std::fs::read_to_string(path).map_err(|e| io_err(path, e))?;
+    let doc = loader.parse(&src).map_err(|error| PreviewError::Parse { path: path.to_path_buf(), error })?;
+
+    stack.push(path.to_path_buf());
+    let dir = path.parent().unwrap_or(Path::new(""));
+    for import in &doc.imports {
+        let target = dir.join(&import.path);
+        if !stack.contains(&target) {
+            let (component, _) = load(loader, &target, stack)?;
+            loader.register(import.alias.clone(), component);
+        }
+    }
+    stack.pop();
+    Ok((doc, src))
+}
+
+/// `(stem, path)` of every file in `dir` with one of `extensions`, sorted.
+fn files_in(dir: &Path, extensions: &[&str]) -> Vec<(String, PathBuf)> {
+    let Ok(entries) = std::fs::read_dir(dir) else { return vec![] };
+    let mut out: Vec<(String, PathBuf)> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| {
+            p.extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| extensions.contains(&e.to_ascii_lowercase().as_str()))
+        })
+        .filter_map(|p| Some((p.file_stem()?.to_str()?.to_string(), p)))
+        .collect();
+    out.sort();
+    out
+}
+
+// ── tests ─────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A scratch directory holding `files`, removed on drop.
+    struct Dir(PathBuf);
+
+    impl Dir {
+        fn new(name: &str, files: &[(&str, &str)]) -> Self {
+            let root = std::env::temp_dir().join(format!("marduk-preview-{name}-{}", std::process::id()));
+            for (path, text) in files {
+                let path = root.join(path);
+                std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+                std::fs::write(path, text).unwrap();
+            }
+            Self(root)
+        }
+    }
+
+    impl Drop for Dir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn pixel(png: &[u8], x: u32, y: u32) -> [u8; 4] {
+        image::load_from_memory(png).unwrap().into_rgba8().get_pixel(x, y).0
+    }
+
+    #[test]
+    fn renders_imports_and_assets() {
+        let dir = Dir::new("imports", &[
+            ("ui/main.mkml", "import \"parts/side.mkml\" as Side\nContainer { bg: #00ff00ff  Side { } }"),
+            ("ui/parts/side.mkml", "Image { src: dot }"),
+            ("ui/images/dot.svg", "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"10\" height=\"10\"><rect width=\"10\" height=\"10\" fill=\"#ff0000\"/></svg>"),
+        ]);
+        let preview = Preview::new(Vec2::new(40.0, 20.0)).scale(2.0).assets(dir.0.join("ui"));
+        let png = preview.render(&dir.0.join("ui/main.mkml")).unwrap();
+        assert_eq!(image::load_from_memory(&png).unwrap().into_rgba8().dimensions(), (80, 40));
+        assert_eq!(pixel(&png, 40, 20), [255, 0, 0, 255]);
+        assert_eq!(pixel(&png, 4, 20), [0, 255, 0, 255]);
+    }
+
+    #[test]
+    fn assets_dir_theme_styles_the_document() {
+        let dir = Dir::new("theme", &[
+            ("ui/main.mkml", "Container { bg: $accent }"),
+            ("ui/theme.mkml", "Theme { accent: #0000ffff }"),
+        ]);
+        let png = Preview::new(Vec2::new(10.0, 10.0)).assets(dir.0.join("ui")).render(&dir.0.join("ui/main.mkml")).unwrap();
+        assert_eq!(pixel(&png, 5, 5), [0, 0, 255, 255]);
+
+        let png = Preview::new(Vec2::new(10.0, 10.0)).render(&dir.0.join("ui/main.mkml")).unwrap();
+        assert_ne!(pixel(&png, 5, 5), [0, 0, 255, 255]);
+    }
+
+    #[test]
+    fn reports_where_an_import_fails_to_parse() {
+        let dir = Dir::new("errors", &[
+            ("main.mkml", "import \"broken.mkml\" as Broken\nBroken { }"),
+            ("broken.mkml", "Row {\n  gap: }"),
+        ]);
+        let err = Preview::new(Vec2::new(10.0, 10.0)).render(&dir.0.join("main.mkml")).unwrap_err();
+        let message = err.to_string();
+        assert!(message.starts_with(&format!("{}:2:", dir.0.join("broken.mkml").display())), "{message}");
+    }
+
+    #[test]
+    fn circular_imports_do_not_recurse() {
+        let dir = Dir::new("cycle", &[
+            ("a.mkml", "import \"b.mkml\" as B\nB { }"),
+            ("b.mkml", "import \"a.mkml\" as A\nColumn { }"),
+        ]);
+        assert!(Preview::new(Vec2::new(10.0, 10.0)).render(&dir.0.join("a.mkml")).is_ok());
+    }
+}
@@ -0,0 +1,387 @@
+//! Transitions: tweened and spring-driven values for widget state changes.
+//!
+//! DSL widgets are rebuilt whenever what they show changes — a toggle that
+//! flips is a new `Toggle` — so an animation cannot live in the widget. It
+//! lives in the [`Animations`] store owned by [`UiScene`](crate::scene::UiScene),
+//! keyed by an [`AnimId`] and a channel name, and widgets read it during
+//! paint:
+//!
+//! ```rust,ignore
+//! fn paint(&self, painter: &mut Painter, rect: Rect) {
+//!     let target = if self.checked { 1.0 } else { 0.0 };
+//!     let t = painter.animate(self.anim_id, "knob", target, self.transition);
+//!     // …draw the knob at `t` of the way across…
+//! }
+//! ```
+//!
+//! The first time a key is seen its value is the target; afterwards a new
+//! target starts a transition from wherever the value currently is, so
+//! interrupting an animation midway never jumps. Keys not read during a
+//! frame are dropped at its end. [`UiScene::is_animating`](crate::scene::UiScene::is_animating)
+//! tells the runtime to keep requesting frames until every value settles.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use marduk_engine::coords::Vec2;
+use marduk_engine::paint::Color;
+
+static NEXT_ANIM_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Spring steps are at most this long, so a slow frame cannot blow up the
+/// integration.
+const SPRING_STEP_MS: u64 = 4;
+
+/// Distance and speed below which a spring counts as settled.
+const SPRING_REST: f32 = 1e-3;
+
+// ── AnimId ────────────────────────────────────────────────────────────────
+
+/// Identifies the widget an animated value belongs to.
+///
+/// [`AnimId::new`] allocates a fresh id, like [`FocusId`](crate::focus::FocusId).
+/// Widgets rebuilt while they animate — e.g. a toggle whose state lives in
+/// [`DslBindings`](crate::dsl::DslBindings) — use [`AnimId::named`] with a
+/// key that survives the rebuild instead.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct AnimId(u64);
+
+impl AnimId {
+    /// Allocate a new, globally unique id.
+    pub fn new() -> Self {
+        AnimId(NEXT_ANIM_ID.fetch_add(1, Ordering::Relaxed))
+    }
+
+    /// The id for `name`; equal names give equal ids.
+    pub fn named(name: &str) -> Self {
+        // FNV-1a, with the top bit set so it never meets an allocated id.
+        let hash = name.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |h, b| {
+            (h ^ u64::from(b)).wrapping_mul(0x0000_0100_0000_01b3)
+        });
+        AnimId(hash | 1 << 63)
+    }
+}
+
+impl Default for AnimId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ── Easing ────────────────────────────────────────────────────────────────
+
+/// Shape of a tween over its duration.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub enum Easing {
+    Linear,
+    EaseIn,
+    /// Fast start, gentle stop — the usual choice for UI feedback.
+    #[default]
+    EaseOut,
+    EaseInOut,
+    /// CSS-style `cubic-bezier(x1, y1, x2, y2)`.
+    CubicBezier(f32, f32, f32, f32),
+}
+
+impl Easing {
+    /// Eased progress for linear progress `t` in `[0, 1]`.
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t * t,
+            Easing::EaseOut => 1.0 - (1.0 - t).powi(3),
+            Easing::EaseInOut => {
+                if t < 0.5 { 4.0 * t * t * t } else { 1.0 - (-2.0 * t + 2.0).powi(3) / 2.0 }
+            }
+            Easing::CubicBezier(x1, y1, x2, y2) => cubic_bezier(x1, y1, x2, y2, t),
+        }
+    }
+}
+
+/// `y` of the curve through `(0,0)`, `(x1,y1)`, `(x2,y2)`, `(1,1)` at `x`.
+fn cubic_bezier(x1: f32, y1: f32, x2: f32, y2: f32, x: f32) -> f32 {
+    let curve = |a: f32, b: f32, s: f32| {
+        let u = 1.0 - s;
+        3.0 * u * u * s * a + 3.0 * u * s * s * b + s * s * s
+    };
+    // Bisection on x(s): monotonic for x1, x2 in [0, 1].
+    let (mut lo, mut hi) = (0.0_f32, 1.0_f32);
+    for _ in 0..24 {
+        let mid = (lo + hi) * 0.5;
+        if curve(x1, x2, mid) < x { lo = mid } else { hi = mid }
+    }
+    curve(y1, y2, (lo + hi) * 0.5)
+}
+
+// ── Spring ────────────────────────────────────────────────────────────────
+
+/// A damped spring pulling a value toward its target.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Spring {
+    /// Pull per unit of distance, per second squared.
+    pub stiffness: f32,
+    /// Drag per unit of velocity, per second.
+    pub damping:   f32,
+}
+
+impl Spring {
+    pub fn new(stiffness: f32, damping: f32) -> Self {
+        Self { stiffness, damping }
+    }
+
+    /// A slightly bouncy spring that settles in roughly `ms` milliseconds.
+    pub fn settling_in(ms: f32) -> Self {
+        let omega = std::f32::consts::TAU / (ms.max(1.0) / 1000.0);
+        Self { stiffness: omega * omega, damping: 2.0 * 0.7 * omega }
+    }
+}
+
+impl Default for Spring {
+    fn default() -> Self {
+        Self::settling_in(300.0)
+    }
+}
+
+// ── Transition ────────────────────────────────────────────────────────────
+
+/// How a widget moves between values when its state changes.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub enum Transition {
+    /// Jump straight to the new value.
+    #[default]
+    None,
+    /// Interpolate over `duration_ms` along `easing`.
+    Tween { duration_ms: u32, easing: Easing },
+    Spring(Spring),
+}
+
+impl Transition {
+    /// A tween of `ms` milliseconds with the default easing; `0` is
+    /// [`Transition::None`].
+    pub fn ms(ms: u32) -> Self {
+        if ms == 0 { Transition::None } else { Transition::Tween { duration_ms: ms, easing: Easing::default() } }
+    }
+}
+
+// ── Animatable ────────────────────────────────────────────────────────────
+
+/// Values an [`Animations`] store can move: up to four `f32` channels.
+pub trait Animatable: Copy {
+    fn to_channels(self) -> [f32; 4];
+    fn from_channels(c: [f32; 4]) -> Self;
+}
+
+impl Animatable for f32 {
+    fn to_channels(self) -> [f32; 4] { [self, 0.0, 0.0, 0.0] }
+    fn from_channels(c: [f32; 4]) -> Self { c[0] }
+}
+
+impl Animatable for Vec2 {
+    fn to_channels(self) -> [f32; 4] { [self.x, self.y, 0.0, 0.0] }
+    fn from_channels(c: [f32; 4]) -> Self { Vec2::new(c[0], c[1]) }
+}
+
+/// Premultiplied channels interpolate without dark fringes between colors
+/// of different alpha.
+impl Animatable for Color {
+    fn to_channels(self) -> [f32; 4] { [self.r, self.g, self.b, self.a] }
+    fn from_channels(c: [f32; 4]) -> Self {
+        let a = c[3].clamp(0.0, 1.0);
+        Color::from_premul(c[0].clamp(0.0, a), c[1].clamp(0.0, a), c[2].clamp(0.0, a), a)
+    }
+}
+
+// ── Animations ────────────────────────────────────────────────────────────
+
+/// One animated value.
+#[derive(Debug, Clone)]
+struct Entry {
+    value:      [f32; 4],
+    velocity:   [f32; 4],
+    /// Where the current tween started, and when.
+    from:       [f32; 4],
+    start_ms:   u64,
+    target:     [f32; 4],
+    /// Time the value was last advanced to.
+    last_ms:    u64,
+    transition: Transition,
+    touched:    bool,
+}
+
+/// Animated values by widget and channel; see the [module docs](self).
+#[derive(Debug, Default)]
+pub struct Animations {
+    entries: HashMap<(AnimId, &'static str), Entry>,
+    /// Some value read this frame has not reached its target.
+    running: bool,
+}
+
+impl Animations {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The value of `channel` of `id` at `now_ms`, moving toward `target`
+    /// with `transition`.
+    pub fn animate<T: Animatable>(
+        &mut self,
+        id: AnimId,
+        channel: &'static str,
+        target: T,
+        transition: Transition,
+        now_ms: u64,
+    ) -> T {
+        let target = target.to_channels();
+        let entry = self.entries.entry((id, channel)).or_insert(Entry {
+            value:      target,
+            velocity:   [0.0; 4],
+            from:       target,
+            start_ms:   now_ms,
+            target,
+            last_ms:    now_ms,
+            transition,
+            touched:    false,
+        });
+        entry.touched = true;
+        if entry.target != target || entry.transition != transition {
+            entry.from = entry.value;
+            entry.start_ms = now_ms;
+            entry.target = target;
+            entry.transition = transition;
+        }
+        let settled = entry.advance(now_ms);
+        self.running |= !settled;
+        T::from_channels(entry.value)
+    }
+
+    /// Whether any value read during the last frame is still moving.
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+
+    /// Called by the scene before a frame is painted.
+    pub(crate) fn begin_frame(&mut self) {
+        self.running = false;
+    }
+
+    /// Called by the scene after a frame is painted: forgets values no
+    /// widget read.
+    pub(crate) fn end_frame(&mut self) {
+        self.entries.retain(|_, e| std::mem::take(&mut e.touched));
+    }
+}
+
+impl Entry {
+    /// Move the value to `now_ms`; `true` once it rests at the target.
+    fn advance(&mut self, now_ms: u64) -> bool {
+        let settled = match self.transition {
+            Transition::None => true,
+            Transition::Tween { duration_ms, easing } => {
+                let t = now_ms.saturating_sub(self.start_ms) as f32 / duration_ms.max(1) as f32;
+                let eased = easing.apply(t);
+                for i in 0..4 {
+                    self.value[i] = self.from[i] + (self.target[i] - self.from[i]) * eased;
+                }
+                t >= 1.0 || self.from == self.target
+            }
+            Transition::Spring(spring) => {
+                let mut time = self.last_ms;
+                while time < now_ms {
+                    let step = (now_ms - time).min(SPRING_STEP_MS);
+                    let dt = step as f32 / 1000.0;
+                    for i in 0..4 {
+                        let accel = spring.stiffness * (self.target[i] - self.value[i])
+                            - spring.damping * self.velocity[i];
+                        self.velocity[i] += accel * dt;
+                        self.value[i] += self.velocity[i] * dt;
+                    }
+                    time += step;
+                }
+                (0..4).all(|i| {
+                    (self.target[i] - self.value[i]).abs() < SPRING_REST && self.velocity[i].abs() < SPRING_REST
+                })
+            }
+        };
+        if settled {
+            self.value = self.target;
+            self.velocity = [0.0; 4];
+        }
+        self.last_ms = now_ms;
+        settled
+    }
+}
+
+// ── tests ─────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_value_is_the_target_and_changes_tween() {
+        let mut anims = Animations::new();
+        let id = AnimId::new();
+        let fade = Transition::Tween { duration_ms: 100, easing: Easing::Linear };
+        assert_eq!(anims.animate(id, "x", 0.0, fade, 0), 0.0);
+        assert!(!anims.is_running());
+
+        assert_eq!(anims.animate(id, "x", 10.0, fade, 1000), 0.0);
+        assert_eq!(anims.animate(id, "x", 10.0, fade, 1050), 5.0);
+        assert!(anims.is_running());
+        assert_eq!(anims.animate(id, "x", 10.0, fade, 1100), 10.0);
+
+        // Interrupted midway: the new tween starts where the value was.
+        anims.animate(id, "x", 0.0, fade, 2000);
+        assert_eq!(anims.animate(id, "x", 0.0, fade, 2050), 5.0);
+        assert_eq!(anims.animate(id, "x", 20.0, fade, 2050), 5.0);
+        assert_eq!(anims.animate(id, "x", 20.0, fade, 2100), 12.5);
+    }
+
+    #[test]
+    fn springs_settle_on_the_target() {
+        let mut anims = Animations::new();
+        let id = AnimId::named("knob");
+        let spring = Transition::Spring(Spring::settling_in(200.0));
+        anims.animate(id, "x", 0.0, spring, 0);
+        let mut now = 0;
+        let mut overshot = false;
+        loop {
+            anims.begin_frame();
+            now += 16;
+            let x = anims.animate(id, "x", 1.0, spring, now);
+            overshot |= x > 1.0;
+            if !anims.is_running() {
+                assert_eq!(x, 1.0);
+                break;
+            }
+            assert!(now < 1000, "spring still moving after a second");
+        }
+        assert!(overshot && now > 100);
+    }
+
+    #[test]
+    fn unread_values_are_forgotten() {
+        let mut anims = Animations::new();
+        let (a, b) = (AnimId::named("a"), AnimId::named("b"));
+        let fade = Transition::ms(100);
+        anims.animate(a, "x", 0.0, fade, 0);
+        anims.animate(b, "x", 0.0, fade, 0);
+        anims.end_frame();
+        anims.animate(a, "x", 0.0, fade, 16);
+        anims.end_frame();
+        // `b` starts over at its target instead of tweening from 0.
+        assert_eq!(anims.animate(a, "x", 1.0, fade, 32), 0.0);
+        assert_eq!(anims.animate(b, "x", 1.0, fade, 32), 1.0);
+    }
+
+    #[test]
+    fn easings_hit_their_ends() {
+        for easing in [Easing::Linear, Easing::EaseIn, Easing::EaseOut, Easing::EaseInOut, Easing::CubicBezier(0.4, 0.0, 0.2, 1.0)] {
+            assert!(easing.apply(0.0).abs() < 1e-4, "{easing:?}");
+            assert!((easing.apply(1.0) - 1.0).abs() < 1e-4, "{easing:?}");
+        }
+        assert!(Easing::EaseOut.apply(0.5) > 0.5 && Easing::EaseIn.apply(0.5) < 0.5);
+        assert_eq!(AnimId::named("tabs"), AnimId::named("tabs"));
+    }
+}
@@ -1,6 +1,9 @@
+use std::any::Any;
 use std::cell::RefCell;
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
+use std::sync::{Arc, Mutex};
 
 use winit::dpi::LogicalSize;
 use winit::window::Fullscreen;
@@ -9,21 +12,24 @@ use marduk_engine::core::{App as EngineApp, AppControl, FrameCtx};
 use marduk_engine::device::GpuInit;
 use marduk_engine::image::ImageId;
 use marduk_engine::input::{Key, MouseButton};
-use marduk_engine::render::shapes::circle::CircleRenderer;
-use marduk_engine::render::shapes::image::ImageRenderer;
-use marduk_engine::render::shapes::rect::RectRenderer;
-use marduk_engine::render::shapes::rounded_rect::RoundedRectRenderer;
-use marduk_engine::render::shapes::text::TextRenderer;
+use marduk_engine::render::BatchRenderer;
+use marduk_engine::scene::DrawList;
 use marduk_engine::text::FontId;
-use marduk_engine::window::{Runtime, RuntimeConfig, WindowMode};
+use marduk_engine::window::{Proxy as EngineProxy, Runtime, RuntimeConfig, WindowMode};
 
 use marduk_engine::coords::Vec2;
 
-use crate::dsl::{DslBindings, DslDocument, DslLoader};
+use crate::dsl::ast::Node;
+use crate::dsl::{BuildCtx, DslBindings, DslDocument, DslLoader, DslTree};
 use crate::dsl::builder::WidgetStateValue;
+use crate::dsl::Model;
+use crate::dsl::schema::WidgetSchema;
+use crate::dsl::theme::Theme;
+use crate::hot_reload::{self, Source, Watcher};
 use crate::image_loader::{decode_image, decode_svg, is_svg};
 use crate::scene::{UiInput, UiScene};
 use crate::widget::Element;
+use crate::widgets::container::Container;
 
 // ── WidgetState ───────────────────────────────────────────────────────────
 
@@ -31,7 +37,7 @@ use crate::widget::Element;
 ///
 /// Widget state is keyed by the `state_key` property set on stateful widgets
 /// (TextBox, Checkbox, Toggle, Slider, RadioGroup). Mutations take effect on
-/// the next frame rebuild.
+/// the next frame, when the widgets that read the key are rebuilt.
 pub struct WidgetState(Rc<RefCell<HashMap<String, WidgetStateValue>>>);
 
 impl WidgetState {
@@ -56,6 +62,135 @@ impl WidgetState {
     }
 }
 
+// ── ActiveTheme ───────────────────────────────────────────────────────────
+
+/// Shared handle selecting which registered theme styles the UI.
+///
+/// Obtained from [`Application::active_theme`]; clone it into event handlers
+/// to switch themes at runtime. The change applies on the next frame, and
+/// only widgets whose themed properties differ are rebuilt.
+#[derive(Clone, Default)]
+pub struct ActiveTheme(Rc<RefCell<Option<String>>>);
+
+impl ActiveTheme {
+    /// Name of the selected theme; `None` until one is [`set`](Self::set),
+    /// meaning the first theme registered.
+    pub fn get(&self) -> Option<String> {
+        self.0.borrow().clone()
+    }
+
+    /// Select the theme registered as `name`.
+    pub fn set(&self, name: impl Into<String>) {
+        *self.0.borrow_mut() = Some(name.into());
+    }
+}
+
+// ── Proxy ─────────────────────────────────────────────────────────────────
+
+/// Handler for messages sent with [`Proxy::send`]; takes the boxed payload.
+type MessageHandler = Box<dyn FnMut(Box<dyn Any + Send>)>;
+
+/// Thread-safe handle for updating the UI from background threads.
+///
+/// Obtained from [`Application::proxy`]. Unlike [`WidgetState`] it is `Send`,
+/// so clones can move into worker threads. Each call queues an update and
+/// wakes the event loop; the queue is applied in order at the start of the
+/// next frame, before the tree is rebuilt.
+///
+/// # Example
+/// ```rust,ignore
+/// let app = Application::new().on_message("sample", move |s: Sample| plot.borrow_mut().push(s));
+/// let proxy = app.proxy();
+/// std::thread::spawn(move || for sample in telemetry_link() {
+///     proxy.set_float("fuel_gauge", sample.fuel);
+///     proxy.set_str("link_status", "LINKED");
+///     proxy.send("sample", sample);
+/// });
+/// app.run_file("ui/main.mkml");
+/// ```
+#[derive(Clone)]
+pub struct Proxy {
+    updates: Arc<Mutex<Vec<Update>>>,
+    waker:   EngineProxy,
+}
+
+/// One queued [`Proxy`] call.
+enum Update {
+    State(String, WidgetStateValue),
+    Clear(String),
+    Message(String, Box<dyn Any + Send>),
+}
+
+impl Proxy {
+    fn new() -> Self {
+        Self { updates: Arc::default(), waker: EngineProxy::new() }
+    }
+
+    /// Remove a widget's state entry (e.g. clear a TextBox).
+    pub fn clear(&self, key: &str) {
+        self.push(Update::Clear(key.to_string()));
+    }
+
+    /// Overwrite a text-valued state entry.
+    pub fn set_str(&self, key: &str, v: impl Into<String>) {
+        self.push(Update::State(key.to_string(), WidgetStateValue::Str(v.into())));
+    }
+
+    /// Overwrite a boolean state entry (Checkbox, Toggle).
+    pub fn set_bool(&self, key: &str, v: bool) {
+        self.push(Update::State(key.to_string(), WidgetStateValue::Bool(v)));
+    }
+
+    /// Overwrite a float state entry (Slider).
+    pub fn set_float(&self, key: &str, v: f32) {
+        self.push(Update::State(key.to_string(), WidgetStateValue::Float(v)));
+    }
+
+    /// Hand `message` to the handler registered with
+    /// [`Application::on_message`] under `name`, on the UI thread.
+    pub fn send<M: Send + 'static>(&self, name: &str, message: M) {
+        self.push(Update::Message(name.to_string(), Box::new(message)));
+    }
+
+    /// Redraw without queueing anything, e.g. after changing data a
+    /// [`Model`] reads through a lock.
+    pub fn wake(&self) {
+        self.waker.wake();
+    }
+
+    fn push(&self, update: Update) {
+        self.updates.lock().unwrap_or_else(|e| e.into_inner()).push(update);
+        self.waker.wake();
+    }
+
+    fn take(&self) -> Vec<Update> {
+        std::mem::take(&mut *self.updates.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+}
+
+/// Apply queued proxy updates to `state` and `handlers`, in order.
+fn apply_updates(
+    updates: Vec<Update>,
+    state: &RefCell<HashMap<String, WidgetStateValue>>,
+    handlers: &mut HashMap<String, MessageHandler>,
+) {
+    for update in updates {
+        match update {
+            Update::State(key, value) => {
+                state.borrow_mut().insert(key, value);
+            }
+            Update::Clear(key) => {
+                state.borrow_mut().remove(&key);
+            }
+            Update::Message(name, message) => {
+                if let Some(handler) = handlers.get_mut(&name) {
+                    handler(message);
+                }
+            }
+        }
+    }
+}
+
 // ── FontMap ───────────────────────────────────────────────────────────────
 
 /// A name-keyed map of loaded font and image handles.
@@ -127,9 +262,28 @@ pub struct Application {
     /// Images: `(name, bytes, svg_scale)`. `svg_scale` is 1.0 for raster formats.
     images:         Vec<(String, Vec<u8>, f32)>,
     components:     Vec<(String, String)>,
+    /// Components loaded from disk and watched for changes: `(alias, path)`.
+    component_files: Vec<(String, PathBuf)>,
     event_handlers: HashMap<String, Box<dyn FnMut()>>,
     /// Shared widget state — created early so `on_event_state` closures can capture it.
     widget_state:   Rc<RefCell<HashMap<String, WidgetStateValue>>>,
+    /// Data for `{path}` bindings in `.mkml` documents.
+    model:          Option<Box<dyn Model>>,
+    /// Schema entries for widgets the application provides.
+    schemas:        Vec<WidgetSchema>,
+    /// Themes registered from source: `(name, src)`.
+    themes:         Vec<(String, String)>,
+    /// Themes loaded from disk and watched for changes: `(name, path)`.
+    theme_files:    Vec<(String, PathBuf)>,
+    /// Which theme is applied; the first registered when unset.
+    active_theme:   ActiveTheme,
+    /// Handlers for messages sent through [`Proxy::send`], by name.
+    message_handlers: HashMap<String, MessageHandler>,
+    /// Queues updates from other threads; connected by the entry points.
+    proxy:          Proxy,
+    /// Builders for widgets the application provides, by name.
+    #[allow(clippy::type_complexity)]
+    widgets:        Vec<(String, Rc<dyn Fn(&Node, &mut BuildCtx<'_>) -> Element>)>,
 }
 
 impl Application {
@@ -143,8 +297,17 @@ impl Application {
             fonts:          Vec::new(),
             images:         Vec::new(),
             components:     Vec::new(),
+            component_files: Vec::new(),
             event_handlers: HashMap::new(),
             widget_state:   Rc::new(RefCell::new(HashMap::new())),
+            model:          None,
+            schemas:        Vec::new(),
+            themes:         Vec::new(),
+            theme_files:    Vec::new(),
+            active_theme:   ActiveTheme::default(),
+            message_handlers: HashMap::new(),
+            proxy:          Proxy::new(),
+            widgets:        Vec::new(),
         }
     }
 
@@ -217,6 +380,81 @@ impl Application {
         self
     }
 
+    /// Load a component from a `.mkml` file and reload it whenever the file
+    /// changes (development mode).
+    ///
+    /// The file is read when the app starts; read and parse errors are shown
+    /// in an in-window overlay instead of aborting. Ship with [`component`]
+    /// and `include_str!` instead.
+    pub fn component_file(mut self, alias: impl Into<String>, path: impl Into<PathBuf>) -> Self {
+        self.component_files.push((alias.into(), path.into()));
+        self
+    }
+
+    /// Register a theme — `$tokens` and default widget styles, written as a
+    /// `Theme { … }` document — under `name`. The first theme registered is
+    /// applied until [`active_theme`](Self::active_theme) selects another.
+    ///
+    /// See [`Theme`](crate::dsl::theme::Theme) for the format.
+    pub fn theme(mut self, name: impl Into<String>, src: impl Into<String>) -> Self {
+        self.themes.push((name.into(), src.into()));
+        self
+    }
+
+    /// Load a theme from a file and reload it whenever the file changes
+    /// (development mode), like [`component_file`](Self::component_file).
+    pub fn theme_file(mut self, name: impl Into<String>, path: impl Into<PathBuf>) -> Self {
+        self.theme_files.push((name.into(), path.into()));
+        self
+    }
+
+    /// Handle selecting the applied theme, for switching at runtime.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let app = Application::new()
+    ///     .theme("dark", include_str!("../ui/themes/dark.mkml"))
+    ///     .theme("light", include_str!("../ui/themes/light.mkml"));
+    /// let theme = app.active_theme();
+    /// app.on_event("toggle_theme", move || {
+    ///     let light = theme.get().as_deref() == Some("light");
+    ///     theme.set(if light { "dark" } else { "light" });
+    /// })
+    /// .run_file("ui/main.mkml");
+    /// ```
+    pub fn active_theme(&self) -> ActiveTheme {
+        self.active_theme.clone()
+    }
+
+    /// Build `name` nodes in `.mkml` documents with `build`, so a widget of
+    /// the application's own can be placed like any built-in. See
+    /// [`DslLoader::register_widget`].
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// Application::new()
+    ///     .widget("Gauge", |node, ctx| {
+    ///         Gauge::new(node.prop_f32("value").unwrap_or(0.0), ctx.font()).into()
+    ///     })
+    ///     .widget_schema(WidgetSchema::new("Gauge", "A round dial.").props(GAUGE_PROPS))
+    ///     .run(r#"Row { Gauge { value: 0.4 } }"#);
+    /// ```
+    pub fn widget<F>(mut self, name: impl Into<String>, build: F) -> Self
+    where
+        F: Fn(&Node, &mut BuildCtx<'_>) -> Element + 'static,
+    {
+        self.widgets.push((name.into(), Rc::new(build)));
+        self
+    }
+
+    /// Describe a widget the application provides, so `.mkml` documents
+    /// using it are checked against its properties instead of being
+    /// reported as unknown.
+    pub fn widget_schema(mut self, widget: WidgetSchema) -> Self {
+        self.schemas.push(widget);
+        self
+    }
+
     /// Register a callback for a named DSL event (e.g. `on_click=quit`).
     pub fn on_event(mut self, name: impl Into<String>, f: impl FnMut() + 'static) -> Self {
         self.event_handlers.insert(name.into(), Box::new(f));
@@ -245,6 +483,62 @@ impl Application {
         self
     }
 
+    /// Expose application data to `{path}` bindings in `.mkml` documents.
+    ///
+    /// Pass an `Rc<RefCell<M>>` and keep a clone to update the data from event
+    /// handlers; bound widgets show the new values on the next frame.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let fleet = Rc::new(RefCell::new(Fleet::default()));
+    /// Application::new()
+    ///     .model(fleet.clone())
+    ///     .on_event("refuel", move || fleet.borrow_mut().fuel_level = 1.0)
+    ///     .run(r#"ProgressBar { value: {fleet.fuel_level} }"#);
+    /// ```
+    pub fn model(mut self, model: impl Model + 'static) -> Self {
+        self.model = Some(Box::new(model));
+        self
+    }
+
+    /// Register a callback for messages of type `M` sent under `name`
+    /// through a [`Proxy`].
+    ///
+    /// The callback runs on the UI thread at the start of the frame after
+    /// the message was sent. A message of another type is dropped with a
+    /// warning.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let fleet = Rc::new(RefCell::new(Fleet::default()));
+    /// let sink = fleet.clone();
+    /// let app = Application::new()
+    ///     .model(fleet)
+    ///     .on_message("truck", move |truck: Truck| sink.borrow_mut().upsert(truck));
+    /// ```
+    pub fn on_message<M, F>(mut self, name: impl Into<String>, mut f: F) -> Self
+    where
+        M: Send + 'static,
+        F: FnMut(M) + 'static,
+    {
+        let name = name.into();
+        let label = name.clone();
+        self.message_handlers.insert(name, Box::new(move |message| match message.downcast::<M>() {
+            Ok(message) => f(*message),
+            Err(_) => log::warn!("message '{label}' is not a {}", std::any::type_name::<M>()),
+        }));
+        self
+    }
+
+    /// A `Send + Clone` handle for updating the UI from other threads; see
+    /// [`Proxy`].
+    ///
+    /// Take it before calling one of the `run` methods. Updates queued
+    /// before the event loop starts are applied on the first frame.
+    pub fn proxy(&self) -> Proxy {
+        self.proxy.clone()
+    }
+
     // ── Entry points ──────────────────────────────────────────────────────
 
     /// Start the event loop using a `.mkml` document as the root widget tree.
@@ -258,7 +552,23 @@ impl Application {
                 std::process::exit(1);
             }
         };
-        let state = UiAppState::new_dsl(self, doc);
+        let state = UiAppState::new_dsl(self, Some(doc), None);
+        Self::launch(state);
+    }
+
+    /// Start the event loop with the `.mkml` document at `path`, reloading it
+    /// (and any [`component_file`]s) whenever it changes on disk. Files it
+    /// `import`s — resolved relative to the importing file — are loaded and
+    /// watched the same way.
+    ///
+    /// Edits are picked up within a fraction of a second without restarting;
+    /// widget state is kept. A document that fails to load leaves the last
+    /// good version on screen and reports the error — with its line and
+    /// column — in an overlay until the file is fixed.
+    ///
+    /// This never returns.
+    pub fn run_file(self, path: impl Into<PathBuf>) -> ! {
+        let state = UiAppState::new_dsl(self, None, Some(path.into()));
         Self::launch(state);
     }
 
@@ -282,7 +592,8 @@ impl Application {
             initial_size: LogicalSize::new(state.width, state.height),
             window_mode:  state.window_mode,
         };
-        Runtime::run(config, GpuInit::default(), state)
+        let waker = state.proxy.waker.clone();
+        Runtime::run_with_proxy(config, GpuInit::default(), state, &waker)
             .unwrap_or_else(|e| {
                 eprintln!("marduk runtime error: {e}");
                 std::process::exit(1);
@@ -316,17 +627,37 @@ struct UiAppState {
     zoom: f32,
 
     // Rendering
-    ui_scene:              UiScene,
-    rect_renderer:         RectRenderer,
-    rounded_rect_renderer: RoundedRectRenderer,
-    circle_renderer:       CircleRenderer,
-    text_renderer:         TextRenderer,
-    image_renderer:        ImageRenderer,
+    ui_scene: UiScene,
+    renderer: BatchRenderer,
+    /// The draw list last presented, to skip frames that would look the same.
+    presented: DrawList,
+    /// Layout viewport of the last built frame.
+    last_viewport: Vec2,
+    /// The tree must be rebuilt even without new input: first frame, state
+    /// changed by the last one, running transitions, or a proxy wake.
+    stale: bool,
+    /// Updates queued from other threads, applied at the start of each frame.
+    proxy: Proxy,
+    message_handlers: HashMap<String, MessageHandler>,
 
     // DSL mode
     loader:   DslLoader,
     doc:      Option<DslDocument>,
     bindings: DslBindings,
+    /// Widgets built from `doc`, retained and reconciled each frame.
+    tree:     DslTree,
+    /// Files reloaded on change (`run_file` / `component_file`).
+    watcher:  Watcher,
+    /// Current load error per watched file, shown in an overlay.
+    load_errors: Vec<(PathBuf, String)>,
+    /// Font of the error overlay: the first registered font that loaded.
+    error_font:  Option<FontId>,
+    /// Registered themes, in registration order.
+    themes:       Vec<(String, Theme)>,
+    /// Which of `themes` should be applied.
+    active_theme: ActiveTheme,
+    /// The theme last handed to `loader`; `None` forces it to be re-applied.
+    applied_theme: Option<String>,
 
     // Widget mode (state persists across frames)
     root: Option<Element>,
@@ -348,30 +679,65 @@ struct UiAppState {
 }
 
 impl UiAppState {
-    fn new_dsl(app: Application, doc: DslDocument) -> Self {
-        let (ui_scene, loader, bindings, svg_sources) = Self::setup_dsl(&app);
-        Self {
+    fn new_dsl(mut app: Application, doc: Option<DslDocument>, main_path: Option<PathBuf>) -> Self {
+        let (ui_scene, loader, mut bindings, svg_sources) = Self::setup_dsl(&app);
+        bindings.model = app.model.take();
+        let error_font = Self::first_font(&app, &bindings);
+        let files: Vec<(Source, PathBuf)> = main_path.map(|p| (Source::Main, p))
+            .into_iter()
+            .chain(app.component_files.drain(..).map(|(alias, p)| (Source::Component(alias), p)))
+            .chain(app.theme_files.drain(..).map(|(name, p)| (Source::Theme(name), p)))
+            .collect();
+        let themes: Vec<(String, Theme)> = app.themes.iter()
+            .filter_map(|(name, src)| match Theme::parse(src) {
+                Ok(theme) => Some((name.clone(), theme)),
+                Err(e) => {
+                    log::warn!("failed to parse theme '{name}': {e}");
+                    None
+                }
+            })
+            .collect();
+        let mut state = Self {
             title:                 app.title,
             width:                 app.width,
             height:                app.height,
             window_mode:           app.window_mode,
             zoom:                  app.zoom,
             ui_scene,
-            rect_renderer:         RectRenderer::new(),
-            rounded_rect_renderer: RoundedRectRenderer::new(),
-            circle_renderer:       CircleRenderer::new(),
-            text_renderer:         TextRenderer::new(),
-            image_renderer:        ImageRenderer::new(),
+            renderer:              BatchRenderer::new(),
+            presented:             DrawList::new(),
+            last_viewport:         Vec2::zero(),
+            stale:                 true,
+            proxy:                 app.proxy,
+            message_handlers:      app.message_handlers,
             loader,
-            doc:                   Some(doc),
+            doc,
             bindings,
+            tree:                  DslTree::new(),
+            watcher:               Watcher::new(),
+            load_errors:           Vec::new(),
+            error_font,
+            themes,
+            active_theme:          app.active_theme,
+            applied_theme:         None,
             root:                  None,
             event_handlers:        app.event_handlers,
             drag_origin:           None,
             svg_sources,
             last_raster_scale:     0.0, // force re-rasterize on first frame
             start_time:            std::time::Instant::now(),
+        };
+        for (source, path) in files {
+            state.load_file(&source, &path);
+            state.watcher.watch(source, path);
         }
+        state.sync_theme();
+        let aliases: Vec<String> = state.loader.component_aliases().map(str::to_string).collect();
+        for alias in aliases {
+            state.report_issues(&Source::Component(alias));
+        }
+        state.report_issues(&Source::Main);
+        state
     }
 
     fn new_widget<F>(app: Application, build: F) -> Self
@@ -384,6 +750,7 @@ impl UiAppState {
             images: bindings.images.clone(),
         };
         let root = build(&font_map);
+        let error_font = Self::first_font(&app, &bindings);
         Self {
             title:                 app.title,
             width:                 app.width,
@@ -391,14 +758,22 @@ impl UiAppState {
             window_mode:           app.window_mode,
             zoom:                  app.zoom,
             ui_scene,
-            rect_renderer:         RectRenderer::new(),
-            rounded_rect_renderer: RoundedRectRenderer::new(),
-            circle_renderer:       CircleRenderer::new(),
-            text_renderer:         TextRenderer::new(),
-            image_renderer:        ImageRenderer::new(),
+            renderer:              BatchRenderer::new(),
+            presented:             DrawList::new(),
+            last_viewport:         Vec2::zero(),
+            stale:                 true,
+            proxy:                 app.proxy,
+            message_handlers:      app.message_handlers,
             loader,
             doc:                   None,
             bindings,
+            tree:                  DslTree::new(),
+            watcher:               Watcher::new(),
+            load_errors:           Vec::new(),
+            error_font,
+            themes:                Vec::new(),
+            active_theme:          app.active_theme,
+            applied_theme:         None,
             root:                  Some(root),
             event_handlers:        app.event_handlers,
             drag_origin:           None,
@@ -447,6 +822,13 @@ impl UiAppState {
         }
 
         let mut loader = DslLoader::new();
+        for widget in &app.schemas {
+            loader.register_schema(*widget);
+        }
+        for (name, build) in &app.widgets {
+            let build = Rc::clone(build);
+            loader.register_widget(name.as_str(), move |node, ctx| build(node, ctx));
+        }
         for (alias, src) in &app.components {
             if let Err(e) = loader.parse_and_register(alias.as_str(), src.as_str()) {
                 log::warn!("failed to parse component '{alias}': {e}");
@@ -456,6 +838,112 @@ impl UiAppState {
         (ui_scene, loader, bindings, svg_sources)
     }
 
+    /// The first font in registration order that loaded. `bindings.fonts`
+    /// is a map, so its order is not stable from run to run.
+    fn first_font(app: &Application, bindings: &DslBindings) -> Option<FontId> {
+        app.fonts.iter().find_map(|(name, _)| bindings.fonts.get(name).copied())
+    }
+
+    /// (Re)load a watched `.mkml` file, recording or clearing its load error.
+    /// Files it imports are loaded and watched too.
+    ///
+    /// On failure the previously loaded document stays in place.
+    fn load_file(&mut self, source: &Source, path: &Path) {
+        let result = match std::fs::read_to_string(path) {
+            Err(e) => Err(e.to_string()),
+            Ok(src) => match source {
+                Source::Main => self.loader.parse(&src).map(|doc| {
+                    self.watch_imports(&doc, path);
+                    self.doc = Some(doc);
+                }),
+                Source::Component(alias) => self.loader.parse(&src).map(|doc| {
+                    self.watch_imports(&doc, path);
+                    self.loader.register(alias.as_str(), doc);
+                }),
+                Source::Theme(name) => Theme::parse(&src).map(|theme| {
+                    self.themes.retain(|(n, _)| n != name);
+                    self.themes.push((name.clone(), theme));
+                    self.applied_theme = None;
+                }),
+            }
+            .map_err(|e| e.to_string()),
+        };
+
+        self.load_errors.retain(|(p, _)| p != path);
+        if let Err(e) = result {
+            let message = format!("{}: {e}", path.display());
+            log::warn!("{message}");
+            #[cfg(debug_assertions)]
+            eprintln!("marduk-ui: {message}");
+            self.load_errors.push((path.to_path_buf(), message));
+        }
+    }
+
+    /// Register each file `doc` imports under its alias and watch it, so
+    /// edits to imported components reload like edits to `path` itself.
+    /// Imports resolve relative to `path`; files already watched (including
+    /// ones that import each other) are left to their own reloads.
+    fn watch_imports(&mut self, doc: &DslDocument, path: &Path) {
+        let dir = path.parent().unwrap_or(Path::new(""));
+        for import in &doc.imports {
+            let target = dir.join(&import.path);
+            if self.watcher.watches(&target) {
+                continue;
+            }
+            let source = Source::Component(import.alias.clone());
+            self.watcher.watch(source.clone(), &target);
+            self.load_file(&source, &target);
+        }
+    }
+
+    /// Hand the selected theme to the loader when the selection or the theme
+    /// itself changed; the tree restyles on its next build.
+    fn sync_theme(&mut self) {
+        let wanted = self.active_theme.get().or_else(|| self.themes.first().map(|(n, _)| n.clone()));
+        if wanted.is_none() || wanted == self.applied_theme {
+            return;
+        }
+        match self.themes.iter().find(|(n, _)| Some(n) == wanted.as_ref()) {
+            Some((_, theme)) => self.loader.set_theme(theme.clone()),
+            None => log::warn!("unknown theme '{}'", wanted.as_deref().unwrap_or_default()),
+        }
+        self.applied_theme = wanted;
+    }
+
+    /// Ask the runtime to wake this window when a widget changes with time
+    /// alone (see [`UiScene::next_frame_at`]) and for the next file poll.
+    fn schedule(&self, ctx: &mut FrameCtx<'_, '_>) {
+        let widget = self.ui_scene.next_frame_at()
+            .map(|ms| self.start_time + std::time::Duration::from_millis(ms));
+        let poll = self.watcher.next_poll();
+        if let Some(at) = widget.into_iter().chain(poll).min() {
+            ctx.runtime.request_redraw_at(ctx.window.id, at);
+        }
+    }
+
+    /// Log what [`DslLoader::check`] finds in a loaded document at `warn`,
+    /// echoing it to stderr in debug builds. Hints are left to the language
+    /// server.
+    fn report_issues(&self, source: &Source) {
+        use crate::dsl::check::Severity;
+
+        let (doc, label) = match source {
+            Source::Main => (self.doc.as_ref(), "main document".to_string()),
+            Source::Component(alias) => (self.loader.component(alias), format!("component '{alias}'")),
+            // Parse errors are reported when loading; the language server checks styles.
+            Source::Theme(_) => return,
+        };
+        let Some(doc) = doc else { return };
+        for issue in self.loader.check(doc) {
+            if issue.severity != Severity::Hint {
+                let message = format!("{label} {}: {}", issue.span.start, issue.message);
+                log::warn!("{message}");
+                #[cfg(debug_assertions)]
+                eprintln!("marduk-ui: {message}");
+            }
+        }
+    }
+
     /// Re-rasterize all SVG images at `scale` and update the image store.
     fn rerasterize_svgs(&mut self, scale: f32) {
         for (id, bytes) in &self.svg_sources {
@@ -470,9 +958,22 @@ impl UiAppState {
 }
 
 impl EngineApp for UiAppState {
+    fn on_wake(&mut self) {
+        // Something another thread owns changed; rebuild on the next frame.
+        // Queued proxy updates are applied by `on_frame` itself.
+        self.stale = true;
+    }
+
     fn on_frame(&mut self, ctx: &mut FrameCtx<'_, '_>) -> AppControl {
         let (w, h) = ctx.window.logical_size();
 
+        // ── Updates from other threads ────────────────────────────────────
+        let updates = self.proxy.take();
+        if !updates.is_empty() {
+            apply_updates(updates, &self.bindings.widget_state, &mut self.message_handlers);
+            self.stale = true;
+        }
+
         // ── F11 → toggle fullscreen ───────────────────────────────────────
         if ctx.input_frame.keys_pressed.contains(&Key::F11) {
             let is_fullscreen = ctx.window.window.fullscreen().is_some();
@@ -532,65 +1033,180 @@ impl EngineApp for UiAppState {
         self.ui_scene.pixel_ratio = raster_scale;
 
         // ── Re-rasterize SVGs at current physical scale if it changed ─────
-        if raster_scale != self.last_raster_scale && !self.svg_sources.is_empty() {
+        let rescaled = raster_scale != self.last_raster_scale;
+        if rescaled {
             self.rerasterize_svgs(raster_scale);
             self.last_raster_scale = raster_scale;
         }
 
-        // ── Layout + paint ────────────────────────────────────────────────
-        match (&self.doc, &mut self.root) {
-            (Some(doc), _) => {
-                let root = self.loader.build(doc, &self.bindings);
-                let _ = self.ui_scene.frame(root, ui_viewport, &ui_input);
+        // ── Hot reload ────────────────────────────────────────────────────
+        let polled = self.watcher.next_poll().is_some_and(|t| t <= std::time::Instant::now());
+        let reloaded = self.watcher.poll();
+        for (source, path) in &reloaded {
+            self.load_file(source, path);
+            self.report_issues(source);
+        }
+        self.sync_theme();
+
+        // ── Skip frames where nothing can have changed ────────────────────
+        let had_input = !ctx.input_frame.events.is_empty();
+        let resized = ui_viewport != self.last_viewport || rescaled;
+        let due = self.ui_scene.next_frame_at().is_some_and(|t| ui_input.time_ms >= t);
+        let rebuild = self.stale || had_input || resized || due || !reloaded.is_empty();
+        if !rebuild {
+            self.schedule(ctx);
+            // Wake-ups the app scheduled itself (file polls) and the frames
+            // the runtime keeps requesting while a key or button is held need
+            // no new pixels. Any other redraw was asked for by the OS and gets
+            // the last frame again.
+            let held = !ctx.input.keys_down.is_empty() || !ctx.input.buttons_down.is_empty();
+            if polled || held {
+                return AppControl::Continue;
             }
-            (None, Some(root)) => {
-                let _ = self.ui_scene.frame_ref(root, ui_viewport, &ui_input);
+        } else {
+            // Keep what is on screen to compare the new frame against.
+            std::mem::swap(&mut self.ui_scene.draw_list, &mut self.presented);
+            self.last_viewport = ui_viewport;
+
+            // ── Layout + paint ────────────────────────────────────────────
+            let errors: Vec<String> = self.load_errors.iter().map(|(_, e)| e.clone()).collect();
+            let error_font = self.error_font;
+            match (&self.doc, &mut self.root) {
+                (Some(doc), _) => {
+                    let root = self.tree.build(&self.loader, doc, &self.bindings);
+                    let root = hot_reload::with_error_overlay(root, &errors, error_font);
+                    let _ = self.ui_scene.frame(root, ui_viewport, &ui_input);
+                }
+                (None, None) if !errors.is_empty() => {
+                    let root = hot_reload::with_error_overlay(Container::new().into(), &errors, error_font);
+                    let _ = self.ui_scene.frame(root, ui_viewport, &ui_input);
+                }
+                (None, Some(root)) => {
+                    let _ = self.ui_scene.frame_ref(root, ui_viewport, &ui_input);
+                }
+                _ => {}
+            }
+
+            // Apply the cursor shape requested by widgets this frame.
+            ctx.window.set_cursor(self.ui_scene.current_cursor().into());
+
+            // Keep frames coming until every transition has settled.
+            let animating = self.ui_scene.is_animating();
+            if animating {
+                ctx.window.request_redraw();
             }
-            _ => {}
-        }
 
-        // Apply the cursor shape requested by widgets this frame.
-        ctx.window.set_cursor(self.ui_scene.current_cursor().into());
+            // ── Dispatch events ───────────────────────────────────────────
+            let events = self.bindings.take_events();
+            for event in &events {
+                if let Some(handler) = self.event_handlers.get_mut(event) {
+                    handler();
+                }
+            }
 
-        // ── Dispatch events ───────────────────────────────────────────────
-        for event in self.bindings.take_events() {
-            if let Some(handler) = self.event_handlers.get_mut(&event) {
-                handler();
+            // Input and handlers change widget state the next build shows;
+            // the runtime follows input with one more frame to pick it up.
+            self.stale = had_input || animating || !events.is_empty();
+            if !events.is_empty() {
+                ctx.window.request_redraw();
+            }
+            self.schedule(ctx);
+
+            // Moving the pointer over inert content paints the same frame.
+            if !resized && self.ui_scene.draw_list.items() == self.presented.items() {
+                return AppControl::Continue;
             }
         }
 
         // ── Render ────────────────────────────────────────────────────────
-        let dl    = &mut self.ui_scene.draw_list;
-        let fs    = &self.ui_scene.font_system;
-        let imgs  = &self.ui_scene.image_store;
-        let r_r   = &mut self.rect_renderer;
-        let r_rr  = &mut self.rounded_rect_renderer;
-        let r_c   = &mut self.circle_renderer;
-        let r_t   = &mut self.text_renderer;
-        let r_img = &mut self.image_renderer;
-        let zoom  = self.zoom;
-
+        let dl       = &mut self.ui_scene.draw_list;
+        let fs       = &self.ui_scene.font_system;
+        let imgs     = &self.ui_scene.image_store;
+        let renderer = &mut self.renderer;
+        let zoom     = self.zoom;
+
+        // One pass in true paint order: overlays (z ≥ 100 000) land on top of
+        // normal content regardless of draw-command type.
         ctx.render_scaled(zoom, marduk_engine::paint::Color::from_straight(0.054, 0.051, 0.043, 1.0), |rctx, target| {
-            // Pass 1 — normal content (z < 100 000): shapes then text.
-            dl.set_z_range(i32::MIN, 99_999);
-            r_r.render(rctx, target, dl);
-            r_rr.render(rctx, target, dl);
-            r_c.render(rctx, target, dl);
-            r_img.render(rctx, target, dl, imgs);
-            r_t.render(rctx, target, dl, fs);
-
-            // Pass 2 — overlay content (z ≥ 100 000): shapes then text.
-            // Ensures overlay widgets (combobox dropdown, tooltip, modal) always
-            // appear above all normal content regardless of draw-command type.
-            dl.set_z_range(100_000, i32::MAX);
-            r_r.render(rctx, target, dl);
-            r_rr.render(rctx, target, dl);
-            r_c.render(rctx, target, dl);
-            r_img.render(rctx, target, dl, imgs);
-            r_t.render(rctx, target, dl, fs);
-
-            dl.reset_z_range();
+            renderer.render(rctx, target, dl, fs, imgs);
         })
     }
 }
 
+
+// ── tests ─────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn proxy_updates_cross_threads_and_apply_in_order() {
+        let samples = Rc::new(RefCell::new(Vec::new()));
+        let sink = samples.clone();
+        let app = Application::new().on_message("sample", move |v: f32| sink.borrow_mut().push(v));
+        let proxy = app.proxy();
+
+        let worker = proxy.clone();
+        std::thread::spawn(move || {
+            worker.set_str("status", "LINKED");
+            worker.set_float("fuel", 0.5);
+            worker.set_float("fuel", 0.25);
+            worker.clear("status");
+            worker.send("sample", 1.5_f32);
+            worker.send("sample", "not a number");
+            worker.send("unhandled", ());
+        })
+        .join()
+        .unwrap();
+
+        let state = RefCell::new(HashMap::new());
+        let mut handlers = app.message_handlers;
+        apply_updates(proxy.take(), &state, &mut handlers);
+        assert_eq!(state.borrow().get("fuel"), Some(&WidgetStateValue::Float(0.25)));
+        assert!(!state.borrow().contains_key("status"));
+        assert_eq!(*samples.borrow(), vec![1.5]);
+        assert!(proxy.take().is_empty());
+    }
+
+    #[test]
+    fn run_file_loads_and_watches_imports() {
+        let dir = std::env::temp_dir().join(format!("marduk-app-imports-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("parts")).unwrap();
+        let main = dir.join("main.mkml");
+        let card = dir.join("parts/card.mkml");
+        let badge = dir.join("parts/badge.mkml");
+        std::fs::write(&main, "import \"parts/card.mkml\" as Card\nColumn { Card { } }").unwrap();
+        std::fs::write(&card, "import \"badge.mkml\" as Badge\nimport \"card.mkml\" as Again\nRow { Badge { } }").unwrap();
+        std::fs::write(&badge, "Text \"new\"").unwrap();
+
+        let mut state = UiAppState::new_dsl(Application::new(), None, Some(main.clone()));
+        assert!(state.load_errors.is_empty());
+        assert!(state.loader.component("Card").is_some());
+        assert!(state.loader.component("Badge").is_some());
+        assert!([&main, &card, &badge].iter().all(|p| state.watcher.watches(p)));
+
+        // An edit to the nested import is picked up on its own.
+        std::fs::write(&badge, "Text \"old\" { size: 9 }").unwrap();
+        let later = std::time::SystemTime::now() + std::time::Duration::from_secs(5);
+        std::fs::File::options().write(true).open(&badge).unwrap().set_modified(later).unwrap();
+        state.watcher.force_poll();
+        for (source, path) in state.watcher.poll() {
+            state.load_file(&source, &path);
+        }
+        let badge_doc = state.loader.component("Badge").unwrap();
+        assert_eq!(badge_doc.root.prop_f32("size"), Some(9.0));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn error_overlay_uses_the_first_registered_font() {
+        let font = || std::fs::read(concat!(env!("CARGO_MANIFEST_DIR"), "/../../assets/fonts/Tuffy.ttf")).unwrap();
+        let app = ["zeta", "body", "alpha", "mono"]
+            .into_iter()
+            .fold(Application::new(), |app, name| app.font(name, font()));
+        let state = UiAppState::new_dsl(app, None, None);
+        assert_eq!(state.error_font, state.bindings.fonts.get("zeta").copied());
+    }
+}
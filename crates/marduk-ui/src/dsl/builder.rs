@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::rc::Rc;
@@ -7,16 +8,24 @@ use marduk_engine::paint::{Color, Paint};
 use marduk_engine::scene::Border;
 use marduk_engine::text::FontId;
 
+use crate::animation::{AnimId, Easing, Spring, Transition};
 use crate::constraints::Edges;
 use crate::dsl::ast::{DslDocument, Node, Value};
+use crate::dsl::check::{self, Issue};
 use crate::dsl::error::ParseError;
 use crate::dsl::parser::parse_str;
+use crate::dsl::component;
+use crate::dsl::model::{self, Model};
+use crate::dsl::retained::{self, Dep, Reconciler};
+use crate::dsl::schema::{Schema, WidgetSchema};
+use crate::dsl::style::Styled;
+use crate::dsl::theme::Theme;
 
 // ── Color conversion ──────────────────────────────────────────────────────
 
 /// Extension on [`Node`] that converts `[u8; 4]` straight-alpha color bytes
 /// (from the parser) into the engine's premultiplied linear `Color`.
-trait NodeExt {
+pub(super) trait NodeExt {
     fn engine_color(&self, key: &str) -> Option<Color>;
 }
 
@@ -55,7 +64,7 @@ use crate::widgets::{
 ///
 /// Keyed by the widget's `state_key` prop (falls back to `on_change` event name).
 /// Lives in [`DslBindings::widget_state`] so it survives widget-tree rebuilds.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum WidgetStateValue {
     Bool(bool),
     Float(f32),
@@ -78,7 +87,10 @@ pub struct DslBindings {
     pub focused_widget: Rc<RefCell<Option<String>>>,
     /// Persisted cursor/anchor/scroll state for TextBox widgets.
     /// Keyed by `state_key`; value is `(cursor_byte, anchor_byte, scroll_offset)`.
+    #[allow(clippy::type_complexity)]
     pub text_edit_states: Rc<RefCell<HashMap<String, (usize, usize, f32)>>>,
+    /// Application data that `{path}` bindings resolve against.
+    pub model: Option<Box<dyn Model>>,
 }
 
 impl DslBindings {
@@ -90,6 +102,7 @@ impl DslBindings {
             widget_state:     Rc::new(RefCell::new(HashMap::new())),
             focused_widget:   Rc::new(RefCell::new(None)),
             text_edit_states: Rc::new(RefCell::new(HashMap::new())),
+            model:            None,
         }
     }
 
@@ -105,6 +118,7 @@ impl DslBindings {
             widget_state,
             focused_widget:   Rc::new(RefCell::new(None)),
             text_edit_states: Rc::new(RefCell::new(HashMap::new())),
+            model:            None,
         }
     }
 
@@ -113,6 +127,12 @@ impl DslBindings {
         self
     }
 
+    /// Set the model `{path}` bindings resolve against.
+    pub fn with_model(mut self, model: impl Model + 'static) -> Self {
+        self.model = Some(Box::new(model));
+        self
+    }
+
     /// Drain all pending events from the queue.
     pub fn take_events(&self) -> Vec<String> {
         self.event_queue.borrow_mut().drain(..).collect()
@@ -125,16 +145,99 @@ impl Default for DslBindings {
     }
 }
 
+// ── BuildCtx ──────────────────────────────────────────────────────────────
+
+/// Builds an application widget from its node; see [`DslLoader::register_widget`].
+type BuildFn = dyn Fn(&Node, &mut BuildCtx<'_>) -> Element;
+
+/// What a [`register_widget`](DslLoader::register_widget) builder can use
+/// besides the node's props: bindings, the node's built children, and
+/// lookups that keep a [`DslTree`](super::DslTree) rebuilding the widget
+/// whenever the value read changes.
+pub struct BuildCtx<'a> {
+    loader:   &'a DslLoader,
+    node:     &'a Node,
+    bindings: &'a DslBindings,
+    children: Vec<Element>,
+}
+
+impl BuildCtx<'_> {
+    /// The bindings the document is built with. Values read straight from
+    /// here are not tracked; prefer [`font`](Self::font), [`image`](Self::image)
+    /// and [`state`](Self::state).
+    pub fn bindings(&self) -> &DslBindings {
+        self.bindings
+    }
+
+    /// The node's child widgets, already built, in document order (`for` and
+    /// `if` blocks expanded). Takes them; later calls return nothing.
+    pub fn children(&mut self) -> Vec<Element> {
+        std::mem::take(&mut self.children)
+    }
+
+    /// The font named by the node's `font` prop, or the default font.
+    pub fn font(&self) -> Option<FontId> {
+        self.loader.resolve_font(self.node, self.bindings)
+    }
+
+    /// The image bound as `name`.
+    pub fn image(&self, name: &str) -> Option<ImageId> {
+        let id = self.bindings.images.get(name).copied();
+        self.loader.record(Dep::Image(name.to_string(), id));
+        id
+    }
+
+    /// `widget_state[key]`.
+    pub fn state(&self, key: &str) -> Option<WidgetStateValue> {
+        self.loader.state(self.bindings, key)
+    }
+
+    /// Color prop `key`, converted to the engine's premultiplied `Color`.
+    pub fn color(&self, key: &str) -> Option<Color> {
+        self.node.engine_color(key)
+    }
+
+    /// `widget` with the active theme's style for it — and for `class`, when
+    /// given — applied, so widgets built here look like the document's own.
+    /// The node is rebuilt when switching themes changes that style.
+    pub fn themed<W: Styled>(&self, widget: W, class: Option<&str>) -> W {
+        let style = self.loader.theme.style_of(W::WIDGET, class);
+        self.loader.record(Dep::Style(W::WIDGET, class.map(str::to_string), style.props.clone()));
+        widget.apply_style(&style)
+    }
+
+    /// A callback pushing `event` to the event queue, for `on_*` props.
+    pub fn emitter(&self, event: &str) -> impl Fn() + 'static {
+        let queue = Rc::clone(&self.bindings.event_queue);
+        let name  = event.to_string();
+        move || queue.borrow_mut().push(name.clone())
+    }
+}
+
 // ── DslLoader ─────────────────────────────────────────────────────────────
 
 /// Parses and caches `.mkml` documents, resolving component imports.
 pub struct DslLoader {
     registry: HashMap<String, DslDocument>,
+    /// Application widgets, by name.
+    widgets:  HashMap<String, Box<BuildFn>>,
+    /// Widget vocabulary documents are [`check`](Self::check)ed against.
+    schema:   Schema,
+    /// Tokens and styles applied to every node before it is built.
+    theme:    Theme,
+    /// Active while a [`DslTree`](super::DslTree) is reconciling through this loader.
+    pub(super) session: RefCell<Option<Reconciler>>,
 }
 
 impl DslLoader {
     pub fn new() -> Self {
-        Self { registry: HashMap::new() }
+        Self {
+            registry: HashMap::new(),
+            widgets:  HashMap::new(),
+            schema:   Schema::new(),
+            theme:    Theme::new(),
+            session:  RefCell::new(None),
+        }
     }
 
     /// Parse a `.mkml` source string into a [`DslDocument`].
@@ -159,17 +262,138 @@ impl DslLoader {
         Ok(())
     }
 
+    /// Build `name` nodes with `build`, so a [`Widget`](crate::widget::Widget)
+    /// of the application's own can be used like a built-in:
+    ///
+    /// ```rust,ignore
+    /// loader.register_widget("Gauge", |node, ctx| {
+    ///     Gauge::new(node.prop_f32("value").unwrap_or(0.0))
+    ///         .needle(ctx.color("needle").unwrap_or(Color::from_srgb(1.0, 1.0, 1.0, 1.0)))
+    ///         .into()
+    /// });
+    /// ```
+    ///
+    /// The name takes precedence over a built-in widget or component of the
+    /// same name. Unless the name has a schema — a built-in's, or one given to
+    /// [`register_schema`](Self::register_schema) — its props are not
+    /// [`check`](Self::check)ed.
+    pub fn register_widget(
+        &mut self,
+        name: impl Into<String>,
+        build: impl Fn(&Node, &mut BuildCtx<'_>) -> Element + 'static,
+    ) {
+        self.widgets.insert(name.into(), Box::new(build));
+    }
+
+    /// Describe a widget the application provides, so documents using it
+    /// [`check`](Self::check) cleanly and its properties are validated.
+    pub fn register_schema(&mut self, widget: WidgetSchema) {
+        self.schema.register(widget);
+    }
+
+    /// The widget vocabulary: built-ins plus [`register_schema`](Self::register_schema) entries.
+    pub fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    /// Style every document built from now on with `theme`.
+    ///
+    /// A [`DslTree`](super::DslTree) rebuilds exactly the widgets whose
+    /// themed properties changed on its next build, so switching themes at
+    /// runtime restyles the whole tree while widgets keep their state.
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme = theme;
+    }
+
+    /// The active theme; empty unless [`set_theme`](Self::set_theme) was called.
+    pub fn theme(&self) -> &Theme {
+        &self.theme
+    }
+
+    /// Problems in `doc` the builder would reject or silently ignore:
+    /// unknown widgets and properties, mistyped values, content or children
+    /// on widgets that take none, and `$tokens` the theme does not define or
+    /// whose value does not fit. Registered components, and
+    /// registered widgets without a schema, count as known widgets.
+    ///
+    /// Building does not check; call this once after loading a document.
+    pub fn check(&self, doc: &DslDocument) -> Vec<Issue> {
+        let unchecked = self.widgets.keys().filter(|name| self.schema.widget(name).is_none());
+        let components: Vec<&str> = self.registry.keys().chain(unchecked).map(String::as_str).collect();
+        let mut issues = check::check(doc, &self.schema, &components);
+        issues.extend(self.theme.check(doc, &self.schema));
+        issues
+    }
+
     /// Build an [`Element`] from a previously parsed document.
     ///
     /// All component aliases referenced in the document must already be
     /// registered via [`register`] / [`parse_and_register`].
+    ///
+    /// Every call constructs a fresh tree. To keep widgets alive between
+    /// frames, build through a [`DslTree`](super::DslTree) instead.
     pub fn build(&self, doc: &DslDocument, bindings: &DslBindings) -> Element {
         self.build_node(&doc.root, bindings)
     }
 
+    /// The registered component document for `alias`, if any.
+    pub(crate) fn component(&self, alias: &str) -> Option<&DslDocument> {
+        self.registry.get(alias)
+    }
+
+    /// Aliases of all registered components.
+    pub(crate) fn component_aliases(&self) -> impl Iterator<Item = &str> {
+        self.registry.keys().map(String::as_str)
+    }
+
+    /// The component root `node` (a component use site) expands to, with
+    /// its params and slot filled in from `node`.
+    pub(super) fn instantiate<'a>(&'a self, node: &Node) -> Option<Cow<'a, Node>> {
+        self.registry.get(&node.widget).map(|doc| component::instantiate(doc, node))
+    }
+
     // ── internal ──────────────────────────────────────────────────────────
 
+    /// Build a child node — through the active [`DslTree`](super::DslTree)
+    /// session when there is one, so unchanged children are reused.
     fn build_node(&self, node: &Node, bindings: &DslBindings) -> Element {
+        if self.session.borrow().is_some() {
+            return retained::reconcile_child(self, node, bindings);
+        }
+        self.construct(&self.resolve(node, bindings), bindings)
+    }
+
+    /// `node` with its `{path}` bindings (and those of the child nodes its
+    /// builder reads directly) replaced by the model's current values,
+    /// `for` / `if` blocks among those children expanded, and the theme's
+    /// styles and tokens applied to it.
+    pub(super) fn resolve<'a>(&self, node: &'a Node, bindings: &DslBindings) -> Cow<'a, Node> {
+        let mut node = model::resolve(node, bindings.model.as_deref(), retained::CHILD_SEARCH_DEPTH);
+        if self.theme.applies_to(&node) {
+            self.theme.apply(node.to_mut());
+        }
+        node
+    }
+
+    /// Record a value the node being built depends on (no-op outside a session).
+    fn record(&self, dep: Dep) {
+        if let Some(session) = self.session.borrow_mut().as_mut() {
+            session.record(dep);
+        }
+    }
+
+    /// Read `widget_state[key]`, recording the dependency.
+    fn state(&self, bindings: &DslBindings, key: &str) -> Option<WidgetStateValue> {
+        let value = bindings.widget_state.borrow().get(key).cloned();
+        self.record(Dep::State(key.to_string(), value.clone()));
+        value
+    }
+
+    /// Construct the widget for `node` itself (children go through [`build_node`]).
+    pub(super) fn construct(&self, node: &Node, bindings: &DslBindings) -> Element {
+        if let Some(build) = self.widgets.get(&node.widget) {
+            return self.build_registered(build, node, bindings);
+        }
         match node.widget.as_str() {
             "Text"        => self.build_text(node, bindings),
             "Container"   => self.build_container(node, bindings),
@@ -192,20 +416,23 @@ impl DslLoader {
             "Modal"       => self.build_modal(node, bindings),
             "Combobox"    => self.build_combobox(node, bindings),
             alias => {
-                if let Some(component) = self.registry.get(alias) {
-                    self.build_node(&component.root, bindings)
+                if let Some(root) = self.instantiate(node) {
+                    if let Some(session) = self.session.borrow_mut().as_mut() {
+                        session.expect_component(&root);
+                    }
+                    self.build_node(&root, bindings)
                 } else {
                     #[cfg(debug_assertions)]
-                    eprintln!("marduk-ui: unknown widget '{alias}'");
+                    eprintln!("marduk-ui: unknown widget '{alias}' at {}", node.name_span.start);
                     // Render a visible red placeholder so layout errors are obvious.
                     let mut c = Container::new()
                         .background(marduk_engine::paint::Paint::Solid(
                             Color::from_srgb_u8(180, 40, 40, 255),
                         ))
                         .padding_all(4.0);
-                    if let Some(font) = bindings.fonts.values().next().copied() {
+                    if let Some(font) = self.resolve_font_named(None, bindings) {
                         let label: Element = Text::new(
-                            format!("[unknown: {alias}]"),
+                            format!("[unknown: {alias} at {}]", node.name_span.start),
                             font,
                             12.0,
                             Color::from_srgb(1.0, 1.0, 1.0, 1.0),
@@ -218,13 +445,21 @@ impl DslLoader {
         }
     }
 
+    // ── Registered widgets ────────────────────────────────────────────────
+
+    fn build_registered(&self, build: &BuildFn, node: &Node, bindings: &DslBindings) -> Element {
+        let children = node.children.iter().map(|c| self.build_node(c, bindings)).collect();
+        let mut ctx = BuildCtx { loader: self, node, bindings, children };
+        build(node, &mut ctx)
+    }
+
     // ── Text ──────────────────────────────────────────────────────────────
 
     fn build_text(&self, node: &Node, bindings: &DslBindings) -> Element {
         let Some(font) = self.resolve_font(node, bindings) else {
             return Container::new().into();
         };
-        let text  = node.content.clone().unwrap_or_default();
+        let text  = node.content_str().unwrap_or_default().to_string();
         let size  = node.prop_f32("size").unwrap_or(14.0);
         let color = node.engine_color("color")
             .unwrap_or_else(|| Color::from_straight(1.0, 1.0, 1.0, 1.0));
@@ -238,7 +473,7 @@ impl DslLoader {
         if let Some(v) = node.prop_f32("padding") {
             c = c.padding_all(v);
         }
-        if let Some(edges) = self.parse_edges(node) {
+        if let Some(edges) = parse_edges(node) {
             c = c.padding(edges);
         }
         if let Some(col) = node.engine_color("bg") {
@@ -263,13 +498,16 @@ impl DslLoader {
             col = col.spacing(v);
         } else if let Some(v) = node.prop_f32("spacing") {
             #[cfg(debug_assertions)]
-            eprintln!("marduk-ui: Column uses deprecated property 'spacing', use 'gap' instead");
+            eprintln!(
+                "marduk-ui: Column uses deprecated property 'spacing' at {}, use 'gap' instead",
+                prop_pos(node, "spacing"),
+            );
             col = col.spacing(v);
         }
         if let Some(v) = node.prop_f32("padding") {
             col = col.padding_all(v);
         }
-        if let Some(edges) = self.parse_edges(node) {
+        if let Some(edges) = parse_edges(node) {
             col = col.padding(edges);
         }
         col = col.cross_align(self.parse_align(node));
@@ -289,13 +527,16 @@ impl DslLoader {
             row = row.spacing(v);
         } else if let Some(v) = node.prop_f32("spacing") {
             #[cfg(debug_assertions)]
-            eprintln!("marduk-ui: Row uses deprecated property 'spacing', use 'gap' instead");
+            eprintln!(
+                "marduk-ui: Row uses deprecated property 'spacing' at {}, use 'gap' instead",
+                prop_pos(node, "spacing"),
+            );
             row = row.spacing(v);
         }
         if let Some(v) = node.prop_f32("padding") {
             row = row.padding_all(v);
         }
-        if let Some(edges) = self.parse_edges(node) {
+        if let Some(edges) = parse_edges(node) {
             row = row.padding(edges);
         }
         row = row.cross_align(self.parse_align(node));
@@ -320,7 +561,7 @@ impl DslLoader {
                 col.into()
             }
         } else if let Some(font) = self.resolve_font(node, bindings) {
-            let label = node.content.clone().unwrap_or_default();
+            let label = node.content_str().unwrap_or_default().to_string();
             let size  = node.prop_f32("font_size").unwrap_or(14.0);
             let color = node.engine_color("text_color")
                 .unwrap_or_else(|| Color::from_straight(1.0, 1.0, 1.0, 1.0));
@@ -329,31 +570,8 @@ impl DslLoader {
             Container::new().into()
         };
 
-        let mut btn = Button::new(inner);
-
-        if let Some(col) = node.engine_color("bg") {
-            btn = btn.background(col);
-        }
-        if let Some(col) = node.engine_color("hover_bg") {
-            btn = btn.hover_background(col);
-        }
-        if let Some(col) = node.engine_color("press_bg") {
-            btn = btn.press_background(col);
-        }
-        if let Some(r) = node.prop_f32("corner_radius") {
-            btn = btn.corner_radius(r);
-        }
-        if let Some(v) = node.prop_f32("padding") {
-            btn = btn.padding_all(v);
-        }
-        if let Some(edges) = self.parse_edges(node) {
-            btn = btn.padding(edges);
-        }
-        if let Some(bw) = node.prop_f32("border_width") {
-            let bc = node.engine_color("border_color")
-                .unwrap_or_else(|| Color::from_straight(1.0, 1.0, 1.0, 0.3));
-            btn = btn.border(Border::new(bw, bc));
-        }
+        let mut btn = Button::new(inner)
+            .apply_style(node).transition(self.parse_transition(node)).anim_id(self.anim_id(node.prop_str("id")));
         if let Some(event_name) = node.prop_str("on_click") {
             let queue = Rc::clone(&bindings.event_queue);
             let name  = event_name.to_string();
@@ -373,8 +591,8 @@ impl DslLoader {
 
         let default_checked = node.prop_f32("checked").map(|v| v != 0.0).unwrap_or(false);
         let checked = if let Some(key) = &state_key {
-            match bindings.widget_state.borrow().get(key.as_str()) {
-                Some(WidgetStateValue::Bool(b)) => *b,
+            match self.state(bindings, key) {
+                Some(WidgetStateValue::Bool(b)) => b,
                 _ => default_checked,
             }
         } else {
@@ -395,7 +613,7 @@ impl DslLoader {
         if let Some(v) = node.engine_color("border_color") { cb = cb.border_color(v); }
         if let Some(v) = node.prop_f32("corner_radius") { cb = cb.corner_radius(v); }
 
-        let label = node.content.clone()
+        let label = node.content_str().map(str::to_string)
             .or_else(|| node.prop_str("label").map(|s| s.to_string()))
             .unwrap_or_default();
         cb = cb.label(label);
@@ -424,8 +642,8 @@ impl DslLoader {
 
         let default_checked = node.prop_f32("checked").map(|v| v != 0.0).unwrap_or(false);
         let checked = if let Some(key) = &state_key {
-            match bindings.widget_state.borrow().get(key.as_str()) {
-                Some(WidgetStateValue::Bool(b)) => *b,
+            match self.state(bindings, key) {
+                Some(WidgetStateValue::Bool(b)) => b,
                 _ => default_checked,
             }
         } else {
@@ -439,6 +657,7 @@ impl DslLoader {
         if let Some(v) = node.engine_color("on_color")    { tg = tg.on_color(v); }
         if let Some(v) = node.engine_color("off_color")   { tg = tg.off_color(v); }
         if let Some(v) = node.engine_color("thumb_color") { tg = tg.thumb_color(v); }
+        tg = tg.transition(self.parse_transition(node)).anim_id(self.anim_id(state_key.as_deref()));
 
         if let Some(event_name) = node.prop_str("on_change") {
             let queue = Rc::clone(&bindings.event_queue);
@@ -467,8 +686,8 @@ impl DslLoader {
         let default_val = node.prop_f32("value").unwrap_or(min);
 
         let value = if let Some(key) = &state_key {
-            match bindings.widget_state.borrow().get(key.as_str()) {
-                Some(WidgetStateValue::Float(v)) => *v,
+            match self.state(bindings, key) {
+                Some(WidgetStateValue::Float(v)) => v,
                 _ => default_val,
             }
         } else {
@@ -521,8 +740,8 @@ impl DslLoader {
             .map(|s| s.to_string());
 
         let selected = if let Some(key) = &state_key {
-            match bindings.widget_state.borrow().get(key.as_str()) {
-                Some(WidgetStateValue::Str(s)) => Some(s.clone()),
+            match self.state(bindings, key) {
+                Some(WidgetStateValue::Str(s)) => Some(s),
                 _ => default_sel,
             }
         } else {
@@ -545,7 +764,7 @@ impl DslLoader {
         // Each RadioOption child node: RadioOption "Label" { value: some_value }
         for child in &node.children {
             if child.widget == "RadioOption" {
-                let label = child.content.clone().unwrap_or_default();
+                let label = child.content_str().unwrap_or_default().to_string();
                 let value = child.prop_str("value").unwrap_or(&label).to_string();
                 rg = rg.option(label, value);
             }
@@ -575,13 +794,13 @@ impl DslLoader {
             .map(|s| s.to_string());
 
         let default_text = node.prop_str("text")
-            .or_else(|| node.content.as_deref())
+            .or(node.content_str())
             .map(|s| s.to_string())
             .unwrap_or_default();
 
         let text = if let Some(key) = &state_key {
-            match bindings.widget_state.borrow().get(key.as_str()) {
-                Some(WidgetStateValue::Str(s)) => s.clone(),
+            match self.state(bindings, key) {
+                Some(WidgetStateValue::Str(s)) => s,
                 _ => default_text,
             }
         } else {
@@ -589,7 +808,12 @@ impl DslLoader {
         };
 
         let focused = state_key.as_deref()
-            .map(|k| bindings.focused_widget.borrow().as_deref() == Some(k))
+            .map(|k| {
+                let current = bindings.focused_widget.borrow().clone();
+                let focused = current.as_deref() == Some(k);
+                self.record(Dep::Focused(current));
+                focused
+            })
             .unwrap_or(false);
 
         // Restore cursor/anchor/scroll from previous frame (if any).
@@ -597,31 +821,22 @@ impl DslLoader {
         // with the text value stored under "{id}".
         let cursor_key = state_key.as_deref().map(|k| format!("{k}::cursor"));
         let (cursor, anchor, scroll) = cursor_key.as_deref()
-            .and_then(|k| bindings.text_edit_states.borrow().get(k).copied())
-            .unwrap_or_else(|| (text.len(), text.len(), 0.0));
+            .and_then(|k| {
+                let saved = bindings.text_edit_states.borrow().get(k).copied();
+                self.record(Dep::TextEdit(k.to_string(), saved));
+                saved
+            })
+            .unwrap_or((text.len(), text.len(), 0.0));
 
         let mut tb = TextBox::new()
             .text(text)
             .cursor(cursor)
             .anchor(anchor)
             .scroll_offset(scroll)
-            .focused(focused);
+            .focused(focused)
+            .apply_style(node);
 
         if let Some(font) = self.resolve_font(node, bindings) { tb = tb.font(font); }
-        if let Some(v) = node.prop_f32("font_size")               { tb = tb.font_size(v); }
-        if let Some(v) = node.engine_color("text_color").or_else(|| node.engine_color("color")) {
-            tb = tb.text_color(v);
-        }
-        if let Some(v) = node.engine_color("placeholder_color")     { tb = tb.placeholder_color(v); }
-        if let Some(v) = node.engine_color("bg")                    { tb = tb.bg(v); }
-        if let Some(v) = node.engine_color("focused_bg")            { tb = tb.focused_bg(v); }
-        if let Some(v) = node.engine_color("border_color")          { tb = tb.border_color(v); }
-        if let Some(v) = node.engine_color("focused_border_color").or_else(|| node.engine_color("accent")) {
-            tb = tb.focused_border_color(v);
-        }
-        if let Some(v) = node.prop_f32("corner_radius")                 { tb = tb.corner_radius(v); }
-        if let Some(v) = node.prop_f32("padding")                       { tb = tb.padding_all(v); }
-        if let Some(v) = node.engine_color("selection_color")           { tb = tb.selection_color(v); }
         if let Some(placeholder) = node.prop_str("placeholder")         {
             tb = tb.placeholder(placeholder.to_string());
         }
@@ -688,8 +903,8 @@ impl DslLoader {
 
         let default_offset = node.prop_f32("offset").unwrap_or(0.0);
         let offset = if let Some(key) = &state_key {
-            match bindings.widget_state.borrow().get(key.as_str()) {
-                Some(WidgetStateValue::Float(v)) => *v,
+            match self.state(bindings, key) {
+                Some(WidgetStateValue::Float(v)) => v,
                 _ => default_offset,
             }
         } else {
@@ -754,7 +969,9 @@ impl DslLoader {
         let Some(src) = node.prop_str("src") else {
             return Container::new().into();
         };
-        let Some(&id) = bindings.images.get(src) else {
+        let id = bindings.images.get(src).copied();
+        self.record(Dep::Image(src.to_string(), id));
+        let Some(id) = id else {
             #[cfg(debug_assertions)]
             eprintln!("marduk-ui: Image src '{src}' not found in bindings");
             return Container::new().into();
@@ -789,8 +1006,8 @@ impl DslLoader {
 
         let default_sel = node.prop_f32("selected").map(|v| v as usize).unwrap_or(0);
         let selected = if let Some(key) = &state_key {
-            match bindings.widget_state.borrow().get(key.as_str()) {
-                Some(WidgetStateValue::Float(v)) => *v as usize,
+            match self.state(bindings, key) {
+                Some(WidgetStateValue::Float(v)) => v as usize,
                 _ => default_sel,
             }
         } else {
@@ -807,11 +1024,12 @@ impl DslLoader {
         if let Some(v) = node.engine_color("indicator_color").or_else(|| node.engine_color("accent")) {
             tabs = tabs.indicator_color(v);
         }
+        tabs = tabs.transition(self.parse_transition(node)).anim_id(self.anim_id(state_key.as_deref()));
 
         // Each Tab child: Tab "Label" { ... content ... }
         for child in &node.children {
             if child.widget == "Tab" {
-                let label   = child.content.clone().unwrap_or_default();
+                let label   = child.content_str().unwrap_or_default().to_string();
                 let content = if let Some(inner) = child.children.first() {
                     self.build_node(inner, bindings)
                 } else {
@@ -847,13 +1065,12 @@ impl DslLoader {
         let drag_key = state_key.as_ref().map(|k| format!("{k}_dragging"));
 
         let (ratio, dragging) = if let Some(key) = &state_key {
-            let ws = bindings.widget_state.borrow();
-            let r = match ws.get(key.as_str()) {
-                Some(WidgetStateValue::Float(v)) => *v,
+            let r = match self.state(bindings, key) {
+                Some(WidgetStateValue::Float(v)) => v,
                 _ => default_ratio,
             };
-            let d = drag_key.as_ref().and_then(|dk| match ws.get(dk.as_str()) {
-                Some(WidgetStateValue::Bool(b)) => Some(*b),
+            let d = drag_key.as_ref().and_then(|dk| match self.state(bindings, dk) {
+                Some(WidgetStateValue::Bool(b)) => Some(b),
                 _ => None,
             }).unwrap_or(false);
             (r, d)
@@ -914,8 +1131,8 @@ impl DslLoader {
 
         let default_val = node.prop_f32("value").map(|v| v as f64).unwrap_or(0.0);
         let value = if let Some(key) = &state_key {
-            match bindings.widget_state.borrow().get(key.as_str()) {
-                Some(WidgetStateValue::Float(v)) => *v as f64,
+            match self.state(bindings, key) {
+                Some(WidgetStateValue::Float(v)) => v as f64,
                 _ => default_val,
             }
         } else {
@@ -966,11 +1183,11 @@ impl DslLoader {
         // Restore hover_since_ms from widget_state (stored under "{id}::hover").
         let hover_key = state_key.as_deref().map(|k| format!("{k}::hover"));
         let hover_since = hover_key.as_deref()
-            .and_then(|k| bindings.widget_state.borrow().get(k).and_then(|v| {
-                if let WidgetStateValue::Float(ms) = v { Some(*ms as u64) } else { None }
+            .and_then(|k| self.state(bindings, k).and_then(|v| {
+                if let WidgetStateValue::Float(ms) = v { Some(ms as u64) } else { None }
             }));
 
-        let text = node.content.clone()
+        let text = node.content_str().map(str::to_string)
             .or_else(|| node.prop_str("text").map(|s| s.to_string()))
             .unwrap_or_default();
 
@@ -1014,8 +1231,8 @@ impl DslLoader {
 
         let default_open = node.prop_f32("open").map(|v| v != 0.0).unwrap_or(false);
         let open = if let Some(key) = &state_key {
-            match bindings.widget_state.borrow().get(key.as_str()) {
-                Some(WidgetStateValue::Bool(b)) => *b,
+            match self.state(bindings, key) {
+                Some(WidgetStateValue::Bool(b)) => b,
                 _ => default_open,
             }
         } else {
@@ -1035,6 +1252,7 @@ impl DslLoader {
         if let Some(v) = node.engine_color("bg")               { m = m.bg(v); }
         if let Some(v) = node.engine_color("backdrop_color")   { m = m.backdrop_color(v); }
         if let Some(v) = node.prop_f32("corner_radius")        { m = m.corner_radius(v); }
+        m = m.transition(self.parse_transition(node)).anim_id(self.anim_id(state_key.as_deref()));
 
         if let Some(event_name) = node.prop_str("on_dismiss") {
             let queue = Rc::clone(&bindings.event_queue);
@@ -1062,8 +1280,8 @@ impl DslLoader {
             .or_else(|| node.prop_str("value"))
             .map(|s| s.to_string());
         let selected = if let Some(key) = &state_key {
-            match bindings.widget_state.borrow().get(key.as_str()) {
-                Some(WidgetStateValue::Str(s)) => Some(s.clone()),
+            match self.state(bindings, key) {
+                Some(WidgetStateValue::Str(s)) => Some(s),
                 _ => default_sel,
             }
         } else {
@@ -1073,36 +1291,24 @@ impl DslLoader {
         // Restore open state.
         let open_key = state_key.as_deref().map(|k| format!("{k}::open"));
         let open = open_key.as_deref()
-            .and_then(|k| bindings.widget_state.borrow().get(k).and_then(|v| {
-                if let WidgetStateValue::Bool(b) = v { Some(*b) } else { None }
+            .and_then(|k| self.state(bindings, k).and_then(|v| {
+                if let WidgetStateValue::Bool(b) = v { Some(b) } else { None }
             }))
             .unwrap_or(false);
 
-        let mut cb = Combobox::new().open(open);
+        let mut cb = Combobox::new().open(open).apply_style(node);
         if let Some(s) = selected { cb = cb.selected(s); }
 
         if let Some(v) = node.prop_str("placeholder")  { cb = cb.placeholder(v.to_string()); }
         if let Some(font) = self.resolve_font(node, bindings) { cb = cb.font(font); }
-        if let Some(v) = node.prop_f32("font_size")    { cb = cb.font_size(v); }
         if let Some(v) = node.prop_f32("width")        { cb = cb.width(v); }
         if let Some(v) = node.prop_f32("height")       { cb = cb.height(v); }
-        if let Some(v) = node.prop_f32("corner_radius")       { cb = cb.corner_radius(v); }
         if let Some(v) = node.prop_f32("max_dropdown_height") { cb = cb.max_dropdown_height(v); }
-        if let Some(v) = node.engine_color("text_color").or_else(|| node.engine_color("color")) {
-            cb = cb.text_color(v);
-        }
-        if let Some(v) = node.engine_color("bg")                    { cb = cb.bg(v); }
-        if let Some(v) = node.engine_color("border_color")          { cb = cb.border_color(v); }
-        if let Some(v) = node.engine_color("focused_border_color").or_else(|| node.engine_color("accent")) {
-            cb = cb.focused_border_color(v);
-        }
-        if let Some(v) = node.engine_color("item_bg")       { cb = cb.item_bg(v); }
-        if let Some(v) = node.engine_color("item_hover_bg") { cb = cb.item_hover_bg(v); }
 
         // Options from child nodes: Option "Label" { value: some_val }
         for child in &node.children {
             if child.widget == "Option" {
-                let label = child.content.clone().unwrap_or_default();
+                let label = child.content_str().unwrap_or_default().to_string();
                 let value = child.prop_str("value").unwrap_or(&label).to_string();
                 cb = cb.option(label, value);
             }
@@ -1160,8 +1366,18 @@ impl DslLoader {
     }
 
     fn resolve_font(&self, node: &Node, bindings: &DslBindings) -> Option<FontId> {
-        node.prop_str("font")
-            .and_then(|name| bindings.fonts.get(name).copied())
+        self.resolve_font_named(node.prop_str("font"), bindings)
+    }
+
+    fn resolve_font_named(&self, name: Option<&str>, bindings: &DslBindings) -> Option<FontId> {
+        let font = Self::lookup_font(name, bindings);
+        self.record(Dep::Font(name.map(str::to_string), font));
+        font
+    }
+
+    /// `name` if bound, otherwise any bound font.
+    pub(super) fn lookup_font(name: Option<&str>, bindings: &DslBindings) -> Option<FontId> {
+        name.and_then(|n| bindings.fonts.get(n).copied())
             .or_else(|| bindings.fonts.values().next().copied())
     }
 
@@ -1173,8 +1389,9 @@ impl DslLoader {
         } else if let Some(v) = node.prop_str("cross_align") {
             #[cfg(debug_assertions)]
             eprintln!(
-                "marduk-ui: '{}' uses deprecated property 'cross_align', use 'align' instead",
-                node.widget
+                "marduk-ui: '{}' uses deprecated property 'cross_align' at {}, use 'align' instead",
+                node.widget,
+                prop_pos(node, "cross_align"),
             );
             v
         } else {
@@ -1188,22 +1405,26 @@ impl DslLoader {
         }
     }
 
-    fn parse_edges(&self, node: &Node) -> Option<Edges> {
-        let top    = node.prop_f32("padding_top");
-        let right  = node.prop_f32("padding_right");
-        let bottom = node.prop_f32("padding_bottom");
-        let left   = node.prop_f32("padding_left");
+    /// `transition` (ms) and `easing`; instant when `transition` is unset or `0`.
+    fn parse_transition(&self, node: &Node) -> Transition {
+        let ms = node.prop_f32("transition").unwrap_or(0.0).max(0.0);
+        if ms == 0.0 {
+            return Transition::None;
+        }
+        let easing = match node.prop_str("easing") {
+            Some("spring")      => return Transition::Spring(Spring::settling_in(ms)),
+            Some("linear")      => Easing::Linear,
+            Some("ease_in")     => Easing::EaseIn,
+            Some("ease_in_out") => Easing::EaseInOut,
+            _                   => Easing::EaseOut,
+        };
+        Transition::Tween { duration_ms: ms as u32, easing }
+    }
 
-        if top.or(right).or(bottom).or(left).is_some() {
-            Some(Edges {
-                top:    top.unwrap_or(0.0),
-                right:  right.unwrap_or(0.0),
-                bottom: bottom.unwrap_or(0.0),
-                left:   left.unwrap_or(0.0),
-            })
-        } else {
-            None
-        }
+    /// Animation key for a widget rebuilt when its state changes: the state
+    /// key outlives the widget, so a transition carries over the rebuild.
+    fn anim_id(&self, state_key: Option<&str>) -> AnimId {
+        state_key.map(AnimId::named).unwrap_or_default()
     }
 
     fn apply_border(&self, c: Container, node: &Node) -> Container {
@@ -1225,6 +1446,26 @@ impl Default for DslLoader {
 
 // ── DSL property helpers for anchor/size types ────────────────────────────
 
+/// `padding_top` / `_right` / `_bottom` / `_left`, when any is set; the
+/// others are `0`.
+pub(super) fn parse_edges(node: &Node) -> Option<Edges> {
+    let top    = node.prop_f32("padding_top");
+    let right  = node.prop_f32("padding_right");
+    let bottom = node.prop_f32("padding_bottom");
+    let left   = node.prop_f32("padding_left");
+
+    if top.or(right).or(bottom).or(left).is_some() {
+        Some(Edges {
+            top:    top.unwrap_or(0.0),
+            right:  right.unwrap_or(0.0),
+            bottom: bottom.unwrap_or(0.0),
+            left:   left.unwrap_or(0.0),
+        })
+    } else {
+        None
+    }
+}
+
 /// Parse a `SizeHint` from a node property.
 ///
 /// Accepts:
@@ -1252,3 +1493,65 @@ fn parse_anchor_val(node: &Node, key: &str) -> Option<AnchorVal> {
         _ => None,
     }
 }
+
+/// Where `key` is written on `node`, for diagnostics.
+#[cfg(debug_assertions)]
+fn prop_pos(node: &Node, key: &str) -> crate::dsl::ast::Pos {
+    node.find_prop(key).map_or(node.span.start, |p| p.key_span.start)
+}
+
+// ── tests ─────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsl::check::Severity;
+    use crate::dsl::schema::{PropKind, PropSchema};
+
+    #[test]
+    fn registered_widgets_see_props_children_and_bindings() {
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let mut loader = DslLoader::new();
+        let log = Rc::clone(&seen);
+        loader.register_widget("Gauge", move |node, ctx| {
+            let children = ctx.children();
+            log.borrow_mut().push((node.prop_f32("value"), ctx.color("needle"), children.len()));
+            ctx.emitter("gauge_built")();
+            Container::new().into()
+        });
+
+        let bindings = DslBindings::new();
+        let doc = loader.parse("Row { Gauge { value: 0.5  needle: #ff000080  Text \"a\" { } Toggle { } } }").unwrap();
+        let _ = loader.build(&doc, &bindings);
+        assert_eq!(*seen.borrow(), [(Some(0.5), Some(Color::from_srgb_u8(255, 0, 0, 128)), 2)]);
+        assert_eq!(bindings.take_events(), ["gauge_built"]);
+    }
+
+    #[test]
+    fn registered_widgets_shadow_builtins() {
+        let mut loader = DslLoader::new();
+        let built = Rc::new(RefCell::new(false));
+        let flag = Rc::clone(&built);
+        loader.register_widget("Text", move |_, _| {
+            *flag.borrow_mut() = true;
+            Container::new().into()
+        });
+        let _ = loader.build(&loader.parse("Text \"hi\" { }").unwrap(), &DslBindings::new());
+        assert!(*built.borrow());
+    }
+
+    #[test]
+    fn registered_widgets_are_checked_only_with_a_schema() {
+        static GAUGE_PROPS: &[PropSchema] = &[PropSchema::new("value", PropKind::Number, "Needle position.")];
+        let mut loader = DslLoader::new();
+        loader.register_widget("Gauge", |_, _| Container::new().into());
+        let doc = loader.parse("Gauge { value: 1  needle: #ffffffff }").unwrap();
+        assert!(loader.check(&doc).is_empty());
+
+        loader.register_schema(WidgetSchema::new("Gauge", "A dial.").props(GAUGE_PROPS));
+        let issues = loader.check(&doc);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].severity, Severity::Warning);
+        assert!(issues[0].message.contains("needle"), "{}", issues[0].message);
+    }
+}
@@ -0,0 +1,271 @@
+//! Component instantiation: `param` substitution and `slot` filling.
+//!
+//! A component document declares its parameters up front and marks where
+//! use-site children go:
+//!
+//! ```mkml
+//! param title: "Untitled"
+//! param accent: #4c6ef5ff
+//!
+//! Container {
+//!     border_color: {accent}
+//!     Column { Text "{title}"  slot }
+//! }
+//! ```
+//!
+//! `Card { title: "Fleet"  Text "12 trucks" }` then builds the component root
+//! with `{title}` replaced by `"Fleet"`, `{accent}` by its default, and
+//! `slot` by the `Text`. References to names that are not parameters are
+//! left alone and resolve against the application model as usual.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use crate::dsl::ast::{Binding, Control, DslDocument, Node, TemplatePart, Value};
+
+/// The root of `doc` as used by `use_site`.
+///
+/// Borrows the component root when it declares no parameters and has no slot.
+pub(super) fn instantiate<'a>(doc: &'a DslDocument, use_site: &Node) -> Cow<'a, Node> {
+    if doc.params.is_empty() && !has_slot(&doc.root) {
+        return Cow::Borrowed(&doc.root);
+    }
+    let args: HashMap<&str, Value> = doc.params
+        .iter()
+        .filter_map(|p| {
+            let value = use_site.prop(&p.name).or(p.default.as_ref())?;
+            Some((p.name.as_str(), value.clone()))
+        })
+        .collect();
+    let mut root = doc.root.clone();
+    substitute(&mut root, &args, &use_site.children);
+    Cow::Owned(root)
+}
+
+fn has_slot(node: &Node) -> bool {
+    node.control == Some(Control::Slot) || node.children.iter().any(has_slot)
+}
+
+/// Replaces parameter references in `node` and fills slots in its children.
+fn substitute(node: &mut Node, args: &HashMap<&str, Value>, slot: &[Node]) {
+    if let Some(content) = node.content.take() {
+        node.content = substitute_value(content, args);
+    }
+    node.props.retain_mut(|p| match substitute_value(p.value.clone(), args) {
+        Some(v) => { p.value = v; true }
+        None => false,
+    });
+
+    let mut shadowed = None;
+    match &mut node.control {
+        Some(Control::For { item, list }) => {
+            rebase(list, args);
+            shadowed = args.contains_key(item.as_str()).then(|| item.clone());
+        }
+        Some(Control::If { cond, otherwise }) => {
+            rebase(cond, args);
+            *otherwise = substitute_children(std::mem::take(otherwise), args, slot);
+        }
+        _ => {}
+    }
+
+    let children = std::mem::take(&mut node.children);
+    node.children = match shadowed {
+        Some(item) => {
+            let mut inner = args.clone();
+            inner.remove(item.as_str());
+            substitute_children(children, &inner, slot)
+        }
+        None => substitute_children(children, args, slot),
+    };
+}
+
+/// Substitutes each child, splicing in slot contents and the branch of any
+/// `if` whose condition became a literal.
+fn substitute_children(children: Vec<Node>, args: &HashMap<&str, Value>, slot: &[Node]) -> Vec<Node> {
+    let mut out = Vec::with_capacity(children.len());
+    for mut child in children {
+        let decided = match &child.control {
+            Some(Control::If { cond, .. }) => literal_arg(cond, args).map(is_truthy),
+            _ => None,
+        };
+        match (child.control.take(), decided) {
+            (Some(Control::Slot), _) if !slot.is_empty() => out.extend(slot.iter().cloned()),
+            (Some(Control::Slot), _) => out.extend(substitute_children(child.children, args, slot)),
+            (Some(Control::If { otherwise, .. }), Some(truthy)) => {
+                let branch = if truthy { child.children } else { otherwise };
+                out.extend(substitute_children(branch, args, slot));
+            }
+            (control, _) => {
+                child.control = control;
+                substitute(&mut child, args, slot);
+                out.push(child);
+            }
+        }
+    }
+    out
+}
+
+/// `value` with parameter references replaced; `None` when a bare `{param}`
+/// was passed something that cannot stand in for it (the prop is dropped).
+fn substitute_value(value: Value, args: &HashMap<&str, Value>) -> Option<Value> {
+    match value {
+        Value::Binding(Binding::Path(path)) => {
+            let (name, rest) = split_path(&path);
+            match (args.get(name), rest) {
+                (None, _) => Some(Value::Binding(Binding::Path(path))),
+                (Some(arg), None) => Some(arg.clone()),
+                (Some(Value::Binding(Binding::Path(base))), Some(rest)) => {
+                    Some(Value::Binding(Binding::Path(format!("{base}.{rest}"))))
+                }
+                (Some(_), Some(_)) => None,
+            }
+        }
+        Value::Binding(Binding::Template(parts)) => {
+            let mut out = Vec::with_capacity(parts.len());
+            for part in parts {
+                match part {
+                    TemplatePart::Path(path) => out.extend(substitute_hole(path, args)),
+                    text => out.push(text),
+                }
+            }
+            if out.iter().all(|p| matches!(p, TemplatePart::Text(_))) {
+                let text = out.into_iter()
+                    .map(|p| match p {
+                        TemplatePart::Text(t) => t,
+                        TemplatePart::Path(_) => unreachable!(),
+                    })
+                    .collect();
+                return Some(Value::Str(text));
+            }
+            Some(Value::Binding(Binding::Template(out)))
+        }
+        literal => Some(literal),
+    }
+}
+
+/// The template parts a `{path}` hole becomes.
+fn substitute_hole(path: String, args: &HashMap<&str, Value>) -> Vec<TemplatePart> {
+    let (name, rest) = split_path(&path);
+    let text = |s: String| vec![TemplatePart::Text(s)];
+    match (args.get(name), rest) {
+        (Some(Value::Binding(Binding::Path(base))), Some(rest)) => {
+            vec![TemplatePart::Path(format!("{base}.{rest}"))]
+        }
+        (Some(arg), None) => match arg {
+            Value::Str(s) | Value::Ident(s) => text(s.clone()),
+            Value::Number(n) => text(n.to_string()),
+            Value::Color([r, g, b, a]) => text(format!("#{r:02x}{g:02x}{b:02x}{a:02x}")),
+            Value::Binding(Binding::Path(base)) => vec![TemplatePart::Path(base.clone())],
+            Value::Binding(Binding::Template(parts)) => parts.clone(),
+            // Resolved at the use site; only an unknown token is left.
+            Value::ThemeRef(_) => Vec::new(),
+        },
+        (Some(_), Some(_)) => Vec::new(),
+        (None, _) => vec![TemplatePart::Path(path)],
+    }
+}
+
+/// Rewrites a `for` list / `if` condition path whose parameter was bound to
+/// another path at the use site.
+fn rebase(path: &mut String, args: &HashMap<&str, Value>) {
+    let (name, rest) = split_path(path);
+    if let Some(Value::Binding(Binding::Path(base))) = args.get(name) {
+        *path = match rest {
+            Some(rest) => format!("{base}.{rest}"),
+            None => base.clone(),
+        };
+    }
+}
+
+/// The literal argument a `for` / `if` path names directly, if any.
+fn literal_arg<'a>(path: &str, args: &'a HashMap<&str, Value>) -> Option<&'a Value> {
+    match args.get(path)? {
+        Value::Binding(_) => None,
+        literal => Some(literal),
+    }
+}
+
+/// Truthiness of a literal parameter value in an `if`.
+fn is_truthy(value: &Value) -> bool {
+    match value {
+        Value::Number(n) => *n != 0.0,
+        Value::Ident(s) => s != "false",
+        Value::Str(s) => !s.is_empty(),
+        Value::Color(_) => true,
+        Value::Binding(_) | Value::ThemeRef(_) => false,
+    }
+}
+
+/// `("fleet", Some("trucks.0"))` for `"fleet.trucks.0"`.
+fn split_path(path: &str) -> (&str, Option<&str>) {
+    match path.split_once('.') {
+        Some((name, rest)) => (name, Some(rest)),
+        None => (path, None),
+    }
+}
+
+// ── tests ─────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsl::parse_str;
+
+    const CARD: &str = r#"
+        param title: "Untitled"
+        param accent: #ff0000ff
+        param closable
+        Column {
+            Text "{title}" { color: {accent} }
+            if closable { Button "x" { } }
+            slot { Text "empty" }
+        }
+    "#;
+
+    fn use_site(src: &str) -> Node {
+        parse_str(src).unwrap().root
+    }
+
+    fn widgets(node: &Node) -> Vec<&str> {
+        node.children.iter().map(|c| c.widget.as_str()).collect()
+    }
+
+    #[test]
+    fn defaults_and_fallback_slot() {
+        let card = parse_str(CARD).unwrap();
+        let root = instantiate(&card, &use_site("Card { }"));
+        assert_eq!(root.children[0].content_str(), Some("Untitled"));
+        assert_eq!(root.children[0].prop_color("color"), Some([255, 0, 0, 255]));
+        assert_eq!(widgets(&root), ["Text", "if", "Text"]);
+        assert_eq!(root.children[2].content_str(), Some("empty"));
+    }
+
+    #[test]
+    fn use_site_props_and_children() {
+        let card = parse_str(CARD).unwrap();
+        let root = instantiate(&card, &use_site(r#"Card { title: "Fleet"  closable: 1  Row { }  Row { } }"#));
+        assert_eq!(root.children[0].content_str(), Some("Fleet"));
+        assert_eq!(widgets(&root), ["Text", "Button", "Row", "Row"]);
+    }
+
+    #[test]
+    fn bound_arguments_become_bindings() {
+        let card = parse_str(CARD).unwrap();
+        let root = instantiate(&card, &use_site("Card { title: {truck.name}  closable: {truck.done} }"));
+        assert_eq!(
+            root.children[0].content,
+            Some(Value::Binding(Binding::Template(vec![TemplatePart::Path("truck.name".into())]))),
+        );
+        assert_eq!(
+            root.children[1].control,
+            Some(Control::If { cond: "truck.done".into(), otherwise: Vec::new() }),
+        );
+    }
+
+    #[test]
+    fn plain_components_are_borrowed() {
+        let doc = parse_str("Row { Text \"{title}\" }").unwrap();
+        assert!(matches!(instantiate(&doc, &use_site("Plain { title: \"x\" }")), Cow::Borrowed(_)));
+    }
+}
@@ -5,15 +5,20 @@
 //! This module re-exports those types at their original paths to keep the
 //! public API unchanged.
 //!
-//! The only piece that stays here is [`builder`], which converts a parsed
-//! [`DslDocument`] into a live widget tree using `marduk-ui` widget types.
+//! The pieces that stay here are [`builder`], which converts a parsed
+//! [`DslDocument`] into a live widget tree using `marduk-ui` widget types,
+//! [`DslTree`], which keeps that tree alive across frames, and [`model`],
+//! which supplies the values of `{path}` bindings.
 
 // ── Re-exports from marduk-mkml ───────────────────────────────────────────
 
 pub use marduk_mkml::ast;
+pub use marduk_mkml::check;
 pub use marduk_mkml::error;
 pub use marduk_mkml::lexer;
 pub use marduk_mkml::parser;
+pub use marduk_mkml::schema;
+pub use marduk_mkml::theme;
 
 pub use marduk_mkml::DslDocument;
 pub use marduk_mkml::ParseError;
@@ -22,4 +27,11 @@ pub use marduk_mkml::parse_str;
 // ── Widget builder (marduk-ui–specific) ───────────────────────────────────
 
 pub mod builder;
-pub use builder::{DslBindings, DslLoader, WidgetStateValue};
+mod component;
+pub mod model;
+mod retained;
+pub mod style;
+pub use builder::{BuildCtx, DslBindings, DslLoader, WidgetStateValue};
+pub use model::{Model, ModelValue};
+pub use retained::DslTree;
+pub use style::Styled;
@@ -0,0 +1,543 @@
+//! Application data for `.mkml` bindings.
+//!
+//! A document can reference application data with `{path}`:
+//!
+//! ```mkml
+//! Column {
+//!     Text "{fleet.count} trucks"
+//!     ProgressBar { value: {fleet.fuel_level} }
+//! }
+//! ```
+//!
+//! `for` / `if` blocks expand against list and boolean data:
+//!
+//! ```mkml
+//! Column {
+//!     for truck in fleet.trucks {
+//!         Row { id: {truck.id}  Text "{truck.name}"  ProgressBar { value: {truck.fuel} } }
+//!     }
+//!     if fleet.has_alerts { Text "Alerts!" } else { Text "All clear" }
+//! }
+//! ```
+//!
+//! Inside a loop body `truck` is scoped to the current element: the body is
+//! copied [`Model::len`] times with `truck.*` rewritten to
+//! `fleet.trucks.<i>.*`, which the model answers by indexing into the list.
+//! Give repeated rows an `id` so they keep their widgets when the list is
+//! reordered.
+//!
+//! The application exposes a [`Model`] (via `Application::model` or
+//! [`DslBindings::with_model`](super::DslBindings::with_model)) that answers
+//! those paths. Bindings are resolved every time the tree is built, so a
+//! [`DslTree`](super::DslTree) picks up model changes on the next frame and
+//! rebuilds only the nodes whose bound values differ.
+
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+
+use crate::dsl::ast::{Binding, Control, Node, TemplatePart, Value};
+
+// ── Model ─────────────────────────────────────────────────────────────────
+
+/// Typed application data that `.mkml` `{path}` bindings read from.
+///
+/// Implement it for your own state and map each path to a field:
+///
+/// ```rust,ignore
+/// struct Fleet { count: u32, fuel_level: f32 }
+///
+/// impl Model for Fleet {
+///     fn get(&self, path: &str) -> Option<ModelValue> {
+///         match path {
+///             "fleet.count"      => Some(self.count.into()),
+///             "fleet.fuel_level" => Some(self.fuel_level.into()),
+///             _ => None,
+///         }
+///     }
+/// }
+/// ```
+///
+/// `Rc<RefCell<M>>` is a model too, so the application can keep a handle and
+/// mutate the data from event handlers.
+///
+/// A `for` loop asks for the list's [`len`](Model::len) and then reads each
+/// element through indexed paths such as `fleet.trucks.3.name`, so `get`
+/// must answer those by walking its own data; nothing ever asks for the
+/// whole list. [`HashMap<String, ModelValue>`] and [`ModelValue`] models do
+/// this by reference.
+pub trait Model {
+    /// The value at the dotted `path`, or `None` if the path is unknown.
+    /// List elements are addressed by index: `fleet.trucks.3.name`.
+    fn get(&self, path: &str) -> Option<ModelValue>;
+
+    /// Number of elements in the list at `path`, or `None` if `path` is not
+    /// a list.
+    ///
+    /// The default builds the list through [`get`](Model::get); override it
+    /// when the length is known without doing so.
+    fn len(&self, path: &str) -> Option<usize> {
+        match self.get(path)? {
+            ModelValue::List(items) => Some(items.len()),
+            _ => None,
+        }
+    }
+}
+
+impl<M: Model + ?Sized> Model for Rc<RefCell<M>> {
+    fn get(&self, path: &str) -> Option<ModelValue> {
+        self.borrow().get(path)
+    }
+
+    fn len(&self, path: &str) -> Option<usize> {
+        self.borrow().len(path)
+    }
+}
+
+impl<M: Model + ?Sized> Model for Box<M> {
+    fn get(&self, path: &str) -> Option<ModelValue> {
+        (**self).get(path)
+    }
+
+    fn len(&self, path: &str) -> Option<usize> {
+        (**self).len(path)
+    }
+}
+
+/// Untyped model keyed by path. Paths below a stored list or record
+/// (`fleet.trucks.3.name` under `fleet.trucks`) walk into it.
+impl Model for HashMap<String, ModelValue> {
+    fn get(&self, path: &str) -> Option<ModelValue> {
+        find(self, path).cloned()
+    }
+
+    fn len(&self, path: &str) -> Option<usize> {
+        find(self, path)?.list_len()
+    }
+}
+
+/// The value at `path` in `map`, walking into the value stored under the
+/// longest prefix of `path`.
+fn find<'m>(map: &'m HashMap<String, ModelValue>, path: &str) -> Option<&'m ModelValue> {
+    if let Some(v) = map.get(path) {
+        return Some(v);
+    }
+    let mut end = path.len();
+    while let Some(dot) = path[..end].rfind('.') {
+        if let Some(v) = map.get(&path[..dot]) {
+            return v.at(&path[dot + 1..]);
+        }
+        end = dot;
+    }
+    None
+}
+
+/// A single record (or list) as the whole model: `fleet.trucks.3.name`
+/// is its `fleet` field's `trucks` field's fourth element's `name`.
+impl Model for ModelValue {
+    fn get(&self, path: &str) -> Option<ModelValue> {
+        self.at(path).cloned()
+    }
+
+    fn len(&self, path: &str) -> Option<usize> {
+        self.at(path)?.list_len()
+    }
+}
+
+// ── ModelValue ────────────────────────────────────────────────────────────
+
+/// A value a [`Model`] returns for a binding path.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ModelValue {
+    /// Bound into a property as `1` / `0`, like `checked: 1`.
+    Bool(bool),
+    Number(f64),
+    Str(String),
+    /// `[r, g, b, a]` straight-alpha sRGB bytes, like a `#rrggbbaa` literal.
+    Color([u8; 4]),
+    /// Source of a `for` loop; elements are addressed as `list.<index>`.
+    List(Vec<ModelValue>),
+    /// Named fields, addressed as `record.<field>`.
+    Record(HashMap<String, ModelValue>),
+}
+
+impl ModelValue {
+    /// A [`Record`](Self::Record) from `(field, value)` pairs.
+    pub fn record<K, V>(fields: impl IntoIterator<Item = (K, V)>) -> Self
+    where
+        K: Into<String>,
+        V: Into<ModelValue>,
+    {
+        ModelValue::Record(fields.into_iter().map(|(k, v)| (k.into(), v.into())).collect())
+    }
+
+    /// The value at the dotted `path` below this one (`"3.name"` in a list of records).
+    pub fn at(&self, path: &str) -> Option<&ModelValue> {
+        path.split('.').try_fold(self, |v, seg| match v {
+            ModelValue::List(items)    => items.get(seg.parse::<usize>().ok()?),
+            ModelValue::Record(fields) => fields.get(seg),
+            _ => None,
+        })
+    }
+
+    /// Number of elements, if this is a [`List`](Self::List).
+    pub fn list_len(&self) -> Option<usize> {
+        match self {
+            ModelValue::List(items) => Some(items.len()),
+            _ => None,
+        }
+    }
+
+    /// Whether an `if` on this value takes its first branch.
+    pub fn is_truthy(&self) -> bool {
+        match self {
+            ModelValue::Bool(b)   => *b,
+            ModelValue::Number(n) => *n != 0.0,
+            ModelValue::Str(s)    => !s.is_empty(),
+            ModelValue::Color(_)  => true,
+            ModelValue::List(v)   => !v.is_empty(),
+            ModelValue::Record(r) => !r.is_empty(),
+        }
+    }
+
+    /// The literal this value stands for in a property (`None` for lists and records).
+    fn into_value(self) -> Option<Value> {
+        Some(match self {
+            ModelValue::Bool(b)   => Value::Number(if b { 1.0 } else { 0.0 }),
+            ModelValue::Number(n) => Value::Number(n as f32),
+            ModelValue::Str(s)    => Value::Str(s),
+            ModelValue::Color(c)  => Value::Color(c),
+            ModelValue::List(_) | ModelValue::Record(_) => return None,
+        })
+    }
+}
+
+/// How the value reads inside a `"{path}"` template.
+impl fmt::Display for ModelValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ModelValue::Bool(b)   => write!(f, "{b}"),
+            ModelValue::Number(n) => write!(f, "{n}"),
+            ModelValue::Str(s)    => f.write_str(s),
+            ModelValue::Color([r, g, b, a]) => write!(f, "#{r:02x}{g:02x}{b:02x}{a:02x}"),
+            ModelValue::List(items) => {
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{item}")?;
+                }
+                Ok(())
+            }
+            ModelValue::Record(_) => f.write_str("{…}"),
+        }
+    }
+}
+
+impl From<bool> for ModelValue {
+    fn from(v: bool) -> Self { ModelValue::Bool(v) }
+}
+
+impl From<String> for ModelValue {
+    fn from(v: String) -> Self { ModelValue::Str(v) }
+}
+
+impl From<&str> for ModelValue {
+    fn from(v: &str) -> Self { ModelValue::Str(v.to_string()) }
+}
+
+macro_rules! number_from {
+    ($($t:ty),*) => {
+        $(impl From<$t> for ModelValue {
+            fn from(v: $t) -> Self { ModelValue::Number(v as f64) }
+        })*
+    };
+}
+
+number_from!(f32, f64, i32, i64, u32, u64, usize);
+
+impl<T: Into<ModelValue>> From<Vec<T>> for ModelValue {
+    fn from(v: Vec<T>) -> Self { ModelValue::List(v.into_iter().map(Into::into).collect()) }
+}
+
+// ── resolution ────────────────────────────────────────────────────────────
+
+/// `node` with every binding in it and its first `depth` levels of children
+/// replaced by the literal it currently resolves to, and `for` / `if` blocks
+/// in those child lists expanded.
+///
+/// `node` is borrowed when nothing in those levels is bound. Otherwise the
+/// copy is resolved all the way down, so the children built from it are
+/// already literal and borrow in turn: a bound subtree is copied once per
+/// build, not once for every level above its bindings.
+///
+/// A bare `{path}` the model does not know removes the property, so the
+/// widget falls back to its default. A template keeps unknown holes as
+/// `{path}` text so they stay visible.
+pub(super) fn resolve<'a>(node: &'a Node, model: Option<&dyn Model>, depth: usize) -> Cow<'a, Node> {
+    if !needs_resolve(node, depth) {
+        return Cow::Borrowed(node);
+    }
+    let mut node = node.clone();
+    resolve_in_place(&mut node, model);
+    Cow::Owned(node)
+}
+
+fn needs_resolve(node: &Node, depth: usize) -> bool {
+    let bound = |v: &Value| matches!(v, Value::Binding(_));
+    node.content.as_ref().is_some_and(bound)
+        || node.props.iter().any(|p| bound(&p.value))
+        || (depth > 0 && node.children.iter().any(|c| c.control.is_some() || needs_resolve(c, depth - 1)))
+}
+
+fn resolve_in_place(node: &mut Node, model: Option<&dyn Model>) {
+    let get = |path: &str| model.and_then(|m| m.get(path));
+    if let Some(Value::Binding(b)) = &node.content {
+        node.content = Some(Value::Str(interpolate(b, &get)));
+    }
+    node.props.retain_mut(|p| match &p.value {
+        Value::Binding(Binding::Path(path)) => {
+            match get(path).and_then(ModelValue::into_value) {
+                Some(v) => { p.value = v; true }
+                None => false,
+            }
+        }
+        Value::Binding(b) => {
+            p.value = Value::Str(interpolate(b, &get));
+            true
+        }
+        _ => true,
+    });
+    if node.children.iter().any(|c| c.control.is_some()) {
+        node.children = expand(std::mem::take(&mut node.children), model);
+    }
+    for child in &mut node.children {
+        resolve_in_place(child, model);
+    }
+}
+
+fn interpolate(binding: &Binding, get: &dyn Fn(&str) -> Option<ModelValue>) -> String {
+    let hole = |path: &str| match get(path) {
+        Some(v) => v.to_string(),
+        None => format!("{{{path}}}"),
+    };
+    match binding {
+        Binding::Path(path) => hole(path),
+        Binding::Template(parts) => parts
+            .iter()
+            .map(|part| match part {
+                TemplatePart::Text(t) => Cow::Borrowed(t.as_str()),
+                TemplatePart::Path(p) => Cow::Owned(hole(p)),
+            })
+            .collect(),
+    }
+}
+
+// ── control flow ──────────────────────────────────────────────────────────
+
+/// Replaces `for` / `if` nodes in a child list with the widgets they produce.
+/// A `slot` left outside a component (nothing to fill it) yields its fallback.
+fn expand(children: Vec<Node>, model: Option<&dyn Model>) -> Vec<Node> {
+    let mut out = Vec::with_capacity(children.len());
+    for child in children {
+        match child.control {
+            None => out.push(child),
+            Some(Control::For { item, list }) => {
+                let len = model.and_then(|m| m.len(&list)).unwrap_or(0);
+                for i in 0..len {
+                    let scope = format!("{list}.{i}");
+                    let body = child.children
+                        .iter()
+                        .map(|n| {
+                            let mut n = n.clone();
+                            rescope(&mut n, &item, &scope);
+                            n
+                        })
+                        .collect();
+                    out.extend(expand(body, model));
+                }
+            }
+            Some(Control::If { cond, otherwise }) => {
+                let branch = if model.and_then(|m| m.get(&cond)).is_some_and(|v| v.is_truthy()) {
+                    child.children
+                } else {
+                    otherwise
+                };
+                out.extend(expand(branch, model));
+            }
+            Some(Control::Slot) => out.extend(expand(child.children, model)),
+        }
+    }
+    out
+}
+
+/// Rewrites paths starting with the loop variable `name` to start with `scope`.
+fn rescope(node: &mut Node, name: &str, scope: &str) {
+    let fix = |path: &mut String| {
+        if path == name {
+            *path = scope.to_string();
+        } else if path.starts_with(name) && path[name.len()..].starts_with('.') {
+            *path = format!("{scope}{}", &path[name.len()..]);
+        }
+    };
+    let fix_value = |value: &mut Value| match value {
+        Value::Binding(Binding::Path(p)) => fix(p),
+        Value::Binding(Binding::Template(parts)) => {
+            for part in parts {
+                if let TemplatePart::Path(p) = part {
+                    fix(p);
+                }
+            }
+        }
+        _ => {}
+    };
+    if let Some(content) = &mut node.content {
+        fix_value(content);
+    }
+    for prop in &mut node.props {
+        fix_value(&mut prop.value);
+    }
+    match &mut node.control {
+        Some(Control::For { item, list }) => {
+            fix(list);
+            if item == name {
+                return; // shadowed inside this loop's body
+            }
+        }
+        Some(Control::If { cond, otherwise }) => {
+            fix(cond);
+            for n in otherwise {
+                rescope(n, name, scope);
+            }
+        }
+        Some(Control::Slot) | None => {}
+    }
+    for child in &mut node.children {
+        rescope(child, name, scope);
+    }
+}
+
+// ── tests ─────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsl::parse_str;
+
+    fn model() -> HashMap<String, ModelValue> {
+        HashMap::from([
+            ("fleet.count".to_string(), 12u32.into()),
+            ("fuel".to_string(), 0.25f32.into()),
+            ("armed".to_string(), true.into()),
+        ])
+    }
+
+    #[test]
+    fn resolves_props_and_content() {
+        let doc = parse_str(r#"Column { Text "{fleet.count} trucks, {ghost}" ProgressBar { value: {fuel} } }"#).unwrap();
+        let m = model();
+        let node = resolve(&doc.root, Some(&m), 1);
+        assert_eq!(node.children[0].content_str(), Some("12 trucks, {ghost}"));
+        assert_eq!(node.children[1].prop_f32("value"), Some(0.25));
+    }
+
+    #[test]
+    fn unknown_path_drops_the_prop() {
+        let doc = parse_str("Toggle { checked: {armed}  on_color: {missing} }").unwrap();
+        let m = model();
+        let node = resolve(&doc.root, Some(&m), 0);
+        assert_eq!(node.prop_f32("checked"), Some(1.0));
+        assert_eq!(node.prop("on_color"), None);
+    }
+
+    fn fleet() -> HashMap<String, ModelValue> {
+        let truck = |name: &str, loads: &[&str]| ModelValue::record([
+            ("name", ModelValue::from(name)),
+            ("loads", loads.iter().map(|&l| ModelValue::from(l)).collect::<Vec<_>>().into()),
+        ]);
+        HashMap::from([
+            ("fleet.trucks".to_string(), vec![truck("Ada", &["coal", "ore"]), truck("Bo", &[])].into()),
+            ("alerts".to_string(), Vec::<ModelValue>::new().into()),
+            ("idle".to_string(), 2u32.into()),
+        ])
+    }
+
+    fn texts(node: &Node) -> Vec<String> {
+        node.children.iter().map(|c| c.content_str().unwrap_or_default().to_string()).collect()
+    }
+
+    #[test]
+    fn maps_walk_into_lists_and_records() {
+        let m = fleet();
+        assert_eq!(Model::get(&m, "fleet.trucks.1.name"), Some("Bo".into()));
+        assert_eq!(Model::get(&m, "fleet.trucks.0.loads.1"), Some("ore".into()));
+        assert_eq!(Model::get(&m, "fleet.trucks.2.name"), None);
+        assert_eq!(Model::len(&m, "fleet.trucks.0.loads"), Some(2));
+        assert_eq!(Model::len(&m, "idle"), None);
+    }
+
+    /// Answers only indexed paths, as a model over large data should.
+    struct Rows(usize);
+
+    impl Model for Rows {
+        fn get(&self, path: &str) -> Option<ModelValue> {
+            let i: usize = path.strip_prefix("rows.")?.strip_suffix(".n")?.parse().ok()?;
+            (i < self.0).then(|| i.into())
+        }
+
+        fn len(&self, path: &str) -> Option<usize> {
+            (path == "rows").then_some(self.0)
+        }
+    }
+
+    #[test]
+    fn for_reads_rows_by_index() {
+        let doc = parse_str(r#"Column { for r in rows { Text "{r.n}" } }"#).unwrap();
+        assert_eq!(texts(&resolve(&doc.root, Some(&Rows(3)), 1)), ["0", "1", "2"]);
+    }
+
+    #[test]
+    fn for_repeats_the_body_per_element() {
+        let doc = parse_str(r#"Column {
+            Text "head"
+            for t in fleet.trucks {
+                Text "{t.name}"
+                for t in t.loads { Text "- {t}" }
+            }
+        }"#).unwrap();
+        let m = fleet();
+        let node = resolve(&doc.root, Some(&m), 1);
+        assert_eq!(texts(&node), ["head", "Ada", "- coal", "- ore", "Bo"]);
+    }
+
+    #[test]
+    fn if_picks_a_branch() {
+        let doc = parse_str(r#"Column {
+            if alerts { Text "alerts" } else if idle { Text "{idle} idle" } else { Text "busy" }
+            if missing { Text "never" }
+        }"#).unwrap();
+        let m = fleet();
+        assert_eq!(texts(&resolve(&doc.root, Some(&m), 1)), ["2 idle"]);
+        assert_eq!(texts(&resolve(&doc.root, None, 1)), ["busy"]);
+    }
+
+    #[test]
+    fn literal_nodes_are_borrowed() {
+        let doc = parse_str(r#"Column { Text "plain" Column { Text "{deep}" } }"#).unwrap();
+        assert!(matches!(resolve(&doc.root, None, 1), Cow::Borrowed(_)));
+        assert!(matches!(resolve(&doc.root, None, 2), Cow::Owned(_)));
+    }
+
+    #[test]
+    fn copies_resolve_below_the_window() {
+        let doc = parse_str(r#"Column { Text "{fleet.count}" Column { Column { Text "{fleet.count}" } } }"#).unwrap();
+        let m = model();
+        let root = resolve(&doc.root, Some(&m), 1);
+        let inner = &root.children[1];
+        assert_eq!(inner.children[0].children[0].content_str(), Some("12"));
+        // Reconciling the children of a resolved copy copies nothing more.
+        assert!(matches!(resolve(inner, Some(&m), 1), Cow::Borrowed(_)));
+    }
+}
@@ -0,0 +1,703 @@
+//! Retained DSL widget tree with keyed reconciliation.
+//!
+//! [`DslLoader::build`] constructs a fresh `Element` tree on every call, so
+//! any state a widget keeps in its own fields is lost each frame. [`DslTree`]
+//! keeps the widgets alive instead: every DSL node owns a slot holding its
+//! built `Element`, and parents hold lightweight proxies to their children's
+//! slots. On each [`DslTree::build`] the document is walked and each node is
+//! reconstructed only when something it was built from has changed:
+//!
+//! - its own widget name, content or props (and those of child nodes it reads
+//!   directly, e.g. `Tab` labels or `Stack` anchors),
+//! - a `widget_state` / `text_edit_states` / focus value it read,
+//! - a font or image it resolved,
+//! - the current model value of a `{path}` binding in any of the above.
+//!
+//! A rebuilt node gets fresh proxies to its children, so children that did
+//! not change keep their `Element` — and all internal state with it (scroll
+//! offsets, open dropdowns, hover timers). A changed child is swapped into
+//! its slot in place, without touching the parent.
+//!
+//! # Keys
+//!
+//! A node's key is its parent's key plus its position in the document, or
+//! `Widget#id` when the node has an `id:` prop (bound ids such as
+//! `id: {truck.id}` included). Siblings with ids therefore keep their widgets
+//! when they are reordered, e.g. rows of a `for` loop whose list is sorted.
+
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use marduk_engine::coords::{Rect, Vec2};
+use marduk_engine::image::ImageId;
+use marduk_engine::text::FontId;
+
+use crate::constraints::{Constraints, LayoutCtx};
+use crate::dsl::ast::{DslDocument, Node, Prop, Value};
+use crate::event::{EventResult, UiEvent};
+use crate::painter::Painter;
+use crate::widget::{Element, Widget};
+
+use super::builder::{DslBindings, DslLoader, WidgetStateValue};
+
+// ── Dep ───────────────────────────────────────────────────────────────────
+
+/// One external value a node read while it was built.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Dep {
+    State(String, Option<WidgetStateValue>),
+    TextEdit(String, Option<(usize, usize, f32)>),
+    Focused(Option<String>),
+    Font(Option<String>, Option<FontId>),
+    Image(String, Option<ImageId>),
+    /// The properties of the theme's style for a widget and class.
+    Style(&'static str, Option<String>, Vec<Prop>),
+}
+
+impl Dep {
+    fn still_holds(&self, loader: &DslLoader, bindings: &DslBindings) -> bool {
+        match self {
+            Dep::State(key, v) => bindings.widget_state.borrow().get(key) == v.as_ref(),
+            Dep::TextEdit(key, v) => bindings.text_edit_states.borrow().get(key) == v.as_ref(),
+            Dep::Focused(v) => *bindings.focused_widget.borrow() == *v,
+            Dep::Font(name, v) => DslLoader::lookup_font(name.as_deref(), bindings) == *v,
+            Dep::Image(name, v) => bindings.images.get(name) == v.as_ref(),
+            Dep::Style(widget, class, v) => loader.theme().style_of(widget, class.as_deref()).props == *v,
+        }
+    }
+}
+
+// ── ChildRef ──────────────────────────────────────────────────────────────
+
+/// Where a child node sits relative to the node that built it.
+#[derive(Debug, Clone, PartialEq)]
+enum ChildRef {
+    /// Indices into `children`, e.g. `[2]` or `[0, 1]` for a `Tab`'s content.
+    Path(Vec<usize>),
+    /// Root of the component document a component alias expands to.
+    Component,
+    /// Not found in the parent's subtree; the parent cannot be reused.
+    Opaque(usize),
+}
+
+impl ChildRef {
+    fn resolve<'a>(&self, node: &'a Node, loader: &'a DslLoader) -> Option<Cow<'a, Node>> {
+        match self {
+            ChildRef::Path(path) => path.iter().try_fold(node, |n, &i| n.children.get(i)).map(Cow::Borrowed),
+            ChildRef::Component => loader.instantiate(node),
+            ChildRef::Opaque(_) => None,
+        }
+    }
+
+    /// `resolve(..).is_some()` without instantiating components.
+    fn exists(&self, node: &Node, loader: &DslLoader) -> bool {
+        match self {
+            ChildRef::Path(path) => path.iter().try_fold(node, |n, &i| n.children.get(i)).is_some(),
+            ChildRef::Component => loader.component(&node.widget).is_some(),
+            ChildRef::Opaque(_) => false,
+        }
+    }
+
+    fn segment(&self) -> String {
+        match self {
+            ChildRef::Path(path) => path.iter().map(|i| i.to_string()).collect::<Vec<_>>().join("."),
+            ChildRef::Component => "component".to_string(),
+            ChildRef::Opaque(n) => format!("?{n}"),
+        }
+    }
+}
+
+/// How deep below a node its builder may reach for child nodes (`Tabs` →
+/// `Tab` → content is the deepest today).
+pub(super) const CHILD_SEARCH_DEPTH: usize = 2;
+
+fn index_descendants(
+    node: &Node,
+    path: &mut Vec<usize>,
+    out: &mut HashMap<*const Node, Vec<usize>>,
+) {
+    if path.len() == CHILD_SEARCH_DEPTH {
+        return;
+    }
+    for (i, child) in node.children.iter().enumerate() {
+        path.push(i);
+        out.insert(child as *const Node, path.clone());
+        index_descendants(child, path, out);
+        path.pop();
+    }
+}
+
+// ── shape comparison ──────────────────────────────────────────────────────
+
+/// Copy of `node` with the subtrees owned by child entries cut off below
+/// their own props. This is everything the node's builder could have read.
+fn shape_of(node: &Node, delegated: &[Vec<usize>], path: &mut Vec<usize>) -> Node {
+    let cut = !path.is_empty() && delegated.contains(path);
+    let children = if cut {
+        Vec::new()
+    } else {
+        node.children
+            .iter()
+            .enumerate()
+            .map(|(i, c)| {
+                path.push(i);
+                let s = shape_of(c, delegated, path);
+                path.pop();
+                s
+            })
+            .collect()
+    };
+    Node {
+        widget:   node.widget.clone(),
+        content:  node.content.clone(),
+        props:    node.props.clone(),
+        children,
+        control:  node.control.clone(),
+        span:         node.span,
+        name_span:    node.name_span,
+        content_span: node.content_span,
+    }
+}
+
+/// `shape == shape_of(node, delegated)`, without allocating.
+fn same_shape(shape: &Node, node: &Node, delegated: &[Vec<usize>], path: &mut Vec<usize>) -> bool {
+    if shape.widget != node.widget
+        || shape.content != node.content
+        || shape.props != node.props
+        || shape.control != node.control
+    {
+        return false;
+    }
+    if !path.is_empty() && delegated.contains(path) {
+        return true;
+    }
+    shape.children.len() == node.children.len()
+        && shape.children.iter().zip(&node.children).enumerate().all(|(i, (s, n))| {
+            path.push(i);
+            let eq = same_shape(s, n, delegated, path);
+            path.pop();
+            eq
+        })
+}
+
+// ── Entry / Reconciler ────────────────────────────────────────────────────
+
+struct Entry {
+    slot: Rc<RefCell<Element>>,
+    shape: Node,
+    children: Vec<ChildRef>,
+    deps: Vec<Dep>,
+    /// Build generation this entry was last visited in.
+    generation: u64,
+}
+
+impl Entry {
+    fn delegated(&self) -> Vec<Vec<usize>> {
+        self.children
+            .iter()
+            .filter_map(|c| match c {
+                ChildRef::Path(p) => Some(p.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// Bookkeeping for a node currently being constructed.
+struct Frame {
+    key: String,
+    paths: HashMap<*const Node, Vec<usize>>,
+    component_root: Option<*const Node>,
+    children: Vec<ChildRef>,
+    deps: Vec<Dep>,
+}
+
+/// Per-build state, installed on the [`DslLoader`] for the duration of
+/// [`DslTree::build`] so `build_node` can reconcile instead of constructing.
+pub(super) struct Reconciler {
+    entries: HashMap<String, Entry>,
+    stack: Vec<Frame>,
+    generation: u64,
+    constructed: usize,
+}
+
+impl Reconciler {
+    pub(super) fn record(&mut self, dep: Dep) {
+        if let Some(frame) = self.stack.last_mut() {
+            frame.deps.push(dep);
+        }
+    }
+
+    /// Classifies `node` as a child of the frame being constructed.
+    fn locate(&mut self, node: &Node) -> ChildRef {
+        let Some(frame) = self.stack.last_mut() else {
+            return ChildRef::Path(Vec::new());
+        };
+        let ptr = node as *const Node;
+        let child = if let Some(path) = frame.paths.get(&ptr) {
+            ChildRef::Path(path.clone())
+        } else if frame.component_root == Some(ptr) {
+            ChildRef::Component
+        } else {
+            ChildRef::Opaque(frame.children.len())
+        };
+        frame.children.push(child.clone());
+        child
+    }
+
+    /// Marks `root` as the instantiated component the frame being
+    /// constructed is about to build.
+    pub(super) fn expect_component(&mut self, root: &Node) {
+        if let Some(frame) = self.stack.last_mut() {
+            frame.component_root = Some(root as *const Node);
+        }
+    }
+
+    /// Key for `node` reached through `child` from the current parent.
+    fn key_for(&self, parent: &str, child: &ChildRef, node: &Node) -> String {
+        let segment = match node.prop("id") {
+            Some(Value::Str(id) | Value::Ident(id)) => format!("{}#{id}", node.widget),
+            Some(Value::Number(id)) => format!("{}#{id}", node.widget),
+            _ => format!("{}@{}", node.widget, child.segment()),
+        };
+        let base = format!("{parent}/{segment}");
+        // Duplicate ids among siblings must not share a slot.
+        let mut key = base.clone();
+        let mut n = 1;
+        while self.entries.get(&key).is_some_and(|e| e.generation == self.generation) {
+            n += 1;
+            key = format!("{base}~{n}");
+        }
+        key
+    }
+}
+
+// ── DslLoader hooks ───────────────────────────────────────────────────────
+
+/// Reconciles `node`, a child of the node currently being constructed.
+pub(super) fn reconcile_child(loader: &DslLoader, node: &Node, bindings: &DslBindings) -> Element {
+    let (parent, child) = {
+        let mut guard = loader.session.borrow_mut();
+        let session = guard.as_mut().expect("reconcile outside DslTree::build");
+        let parent = session.stack.last().map(|f| f.key.clone()).unwrap_or_default();
+        (parent, session.locate(node))
+    };
+    reconcile(loader, &parent, &child, node, bindings)
+}
+
+fn reconcile(
+    loader: &DslLoader,
+    parent: &str,
+    child: &ChildRef,
+    node: &Node,
+    bindings: &DslBindings,
+) -> Element {
+    // Bound values are compared as part of the shape: a node is rebuilt
+    // exactly when something it displays changed in the model.
+    let resolved = loader.resolve(node, bindings);
+    let node: &Node = &resolved;
+
+    // ── reuse ─────────────────────────────────────────────────────────────
+    let (key, reuse) = {
+        let mut guard = loader.session.borrow_mut();
+        let session = guard.as_mut().expect("reconcile outside DslTree::build");
+        let key = session.key_for(parent, child, node);
+        let generation = session.generation;
+        let reuse = session.entries.get_mut(&key).and_then(|entry| {
+            let delegated = entry.delegated();
+            let reusable = same_shape(&entry.shape, node, &delegated, &mut Vec::new())
+                && entry.deps.iter().all(|d| d.still_holds(loader, bindings))
+                && entry.children.iter().all(|c| c.exists(node, loader));
+            if !reusable {
+                return None;
+            }
+            entry.generation = generation;
+            Some((Rc::clone(&entry.slot), entry.children.clone()))
+        });
+        (key, reuse)
+    };
+
+    if let Some((slot, children)) = reuse {
+        // The node itself is unchanged; its children may not be.
+        for c in &children {
+            if let Some(child_node) = c.resolve(node, loader) {
+                drop(reconcile(loader, &key, c, &child_node, bindings));
+            }
+        }
+        return Retained(slot).into();
+    }
+
+    // ── construct ─────────────────────────────────────────────────────────
+    {
+        let mut paths = HashMap::new();
+        index_descendants(node, &mut Vec::new(), &mut paths);
+        let mut guard = loader.session.borrow_mut();
+        let session = guard.as_mut().expect("reconcile outside DslTree::build");
+        session.stack.push(Frame {
+            key: key.clone(),
+            paths,
+            component_root: None,
+            children: Vec::new(),
+            deps: Vec::new(),
+        });
+    }
+
+    let element = loader.construct(node, bindings);
+
+    let mut guard = loader.session.borrow_mut();
+    let session = guard.as_mut().expect("reconcile outside DslTree::build");
+    let frame = session.stack.pop().expect("reconcile frame");
+    session.constructed += 1;
+
+    let delegated: Vec<Vec<usize>> = frame.children.iter()
+        .filter_map(|c| match c {
+            ChildRef::Path(p) => Some(p.clone()),
+            _ => None,
+        })
+        .collect();
+    let shape = shape_of(node, &delegated, &mut Vec::new());
+    let generation = session.generation;
+
+    let slot = match session.entries.get(&key) {
+        Some(old) => {
+            *old.slot.borrow_mut() = element;
+            Rc::clone(&old.slot)
+        }
+        None => Rc::new(RefCell::new(element)),
+    };
+    session.entries.insert(key, Entry {
+        slot: Rc::clone(&slot),
+        shape,
+        children: frame.children,
+        deps: frame.deps,
+        generation,
+    });
+    Retained(slot).into()
+}
+
+// ── Retained proxy ────────────────────────────────────────────────────────
+
+/// Stand-in for a node's `Element` inside its parent; the element itself
+/// lives in the [`DslTree`] so it can be replaced or kept independently.
+struct Retained(Rc<RefCell<Element>>);
+
+impl Widget for Retained {
+    fn measure(&self, constraints: Constraints, ctx: &LayoutCtx) -> Vec2 {
+        self.0.borrow().measure(constraints, ctx)
+    }
+
+    fn paint(&self, painter: &mut Painter, rect: Rect) {
+        self.0.borrow().paint(painter, rect)
+    }
+
+    fn on_event(&mut self, event: &UiEvent, rect: Rect, ctx: &LayoutCtx<'_>) -> EventResult {
+        self.0.borrow_mut().on_event(event, rect, ctx)
+    }
+}
+
+// ── DslTree ───────────────────────────────────────────────────────────────
+
+/// A DSL widget tree that persists across frames.
+///
+/// Use in place of calling [`DslLoader::build`] every frame:
+///
+/// ```rust,ignore
+/// let mut tree = DslTree::new();
+/// // each frame:
+/// let root = tree.build(&loader, &doc, &bindings);
+/// let draw_list = ui_scene.frame(root, viewport, &input);
+/// ```
+///
+/// The returned root is a proxy; dropping it (as `UiScene::frame` does)
+/// leaves the retained widgets in place for the next build.
+#[derive(Default)]
+pub struct DslTree {
+    entries: HashMap<String, Entry>,
+    generation: u64,
+    constructed: usize,
+}
+
+impl DslTree {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reconcile `doc` against the previous build and return the root.
+    ///
+    /// Nodes that are no longer in the document are dropped.
+    pub fn build(&mut self, loader: &DslLoader, doc: &DslDocument, bindings: &DslBindings) -> Element {
+        self.generation += 1;
+        *loader.session.borrow_mut() = Some(Reconciler {
+            entries: std::mem::take(&mut self.entries),
+            stack: Vec::new(),
+            generation: self.generation,
+            constructed: 0,
+        });
+
+        let root = reconcile(loader, "", &ChildRef::Path(Vec::new()), &doc.root, bindings);
+
+        let session = loader.session.borrow_mut().take().expect("DslTree session");
+        self.entries = session.entries;
+        self.entries.retain(|_, e| e.generation == self.generation);
+        self.constructed = session.constructed;
+        root
+    }
+
+    /// Drop every retained widget; the next build constructs from scratch.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Number of nodes currently retained.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Number of nodes the most recent [`build`](Self::build) had to
+    /// construct (`0` when nothing changed).
+    pub fn constructed(&self) -> usize {
+        self.constructed
+    }
+}
+
+// ── tests ─────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scene::UiScene;
+    use crate::testing::{Harness, InputScript};
+
+    fn doc(src: &str) -> DslDocument {
+        DslLoader::new().parse(src).unwrap()
+    }
+
+    #[test]
+    fn unchanged_document_constructs_nothing() {
+        let (loader, bindings) = (DslLoader::new(), DslBindings::new());
+        let d = doc("Column { Toggle { id: a } Row { Toggle { id: b } } }");
+        let mut tree = DslTree::new();
+
+        let _ = tree.build(&loader, &d, &bindings);
+        assert_eq!(tree.constructed(), 4);
+        assert_eq!(tree.len(), 4);
+
+        let _ = tree.build(&loader, &d, &bindings);
+        assert_eq!(tree.constructed(), 0);
+        assert_eq!(tree.len(), 4);
+    }
+
+    #[test]
+    fn state_change_rebuilds_only_the_reader() {
+        let (loader, bindings) = (DslLoader::new(), DslBindings::new());
+        let d = doc("Column { Toggle { id: a } Toggle { id: b } }");
+        let mut tree = DslTree::new();
+        let _ = tree.build(&loader, &d, &bindings);
+
+        bindings.widget_state.borrow_mut().insert("b".into(), WidgetStateValue::Bool(true));
+        let _ = tree.build(&loader, &d, &bindings);
+        assert_eq!(tree.constructed(), 1);
+    }
+
+    #[test]
+    fn prop_change_rebuilds_node_but_not_children() {
+        let (loader, bindings) = (DslLoader::new(), DslBindings::new());
+        let mut tree = DslTree::new();
+        let _ = tree.build(&loader, &doc("Column { gap: 4 Toggle { } Toggle { } }"), &bindings);
+        let _ = tree.build(&loader, &doc("Column { gap: 8 Toggle { } Toggle { } }"), &bindings);
+        assert_eq!(tree.constructed(), 1);
+    }
+
+    #[test]
+    fn keyed_siblings_survive_reordering() {
+        let (loader, bindings) = (DslLoader::new(), DslBindings::new());
+        let mut tree = DslTree::new();
+        let _ = tree.build(&loader, &doc("Row { Toggle { id: a } Toggle { id: b } }"), &bindings);
+        let _ = tree.build(&loader, &doc("Row { Toggle { id: b } Toggle { id: a } }"), &bindings);
+        assert_eq!(tree.constructed(), 1);
+
+        // Unkeyed siblings are matched by position instead.
+        let _ = tree.build(&loader, &doc("Row { Toggle { id: b } Slider { } }"), &bindings);
+        assert_eq!(tree.constructed(), 2);
+        assert_eq!(tree.len(), 3);
+    }
+
+    #[test]
+    fn removed_nodes_are_dropped() {
+        let (loader, bindings) = (DslLoader::new(), DslBindings::new());
+        let mut tree = DslTree::new();
+        let _ = tree.build(&loader, &doc("Column { Toggle { } Toggle { } Toggle { } }"), &bindings);
+        let _ = tree.build(&loader, &doc("Column { Toggle { } }"), &bindings);
+        assert_eq!(tree.len(), 2);
+    }
+
+    #[test]
+    fn component_change_is_picked_up() {
+        let bindings = DslBindings::new();
+        let mut loader = DslLoader::new();
+        loader.parse_and_register("Pair", "Row { Toggle { } Toggle { } }").unwrap();
+        let d = doc("Column { Pair { } }");
+        let mut tree = DslTree::new();
+        let _ = tree.build(&loader, &d, &bindings);
+        assert_eq!(tree.constructed(), 5);
+
+        loader.parse_and_register("Pair", "Row { gap: 2 Toggle { } Toggle { } }").unwrap();
+        let _ = tree.build(&loader, &d, &bindings);
+        assert_eq!(tree.constructed(), 1);
+    }
+
+    #[test]
+    fn component_arguments_reconcile_like_props() {
+        let bindings = DslBindings::new();
+        let mut loader = DslLoader::new();
+        loader.parse_and_register("Card", "param gap: 0\nColumn { gap: {gap}  slot }").unwrap();
+        let mut tree = DslTree::new();
+        let _ = tree.build(&loader, &doc("Card { gap: 4  Toggle { } }"), &bindings);
+        assert_eq!(tree.constructed(), 3);
+
+        let _ = tree.build(&loader, &doc("Card { gap: 4  Toggle { } }"), &bindings);
+        assert_eq!(tree.constructed(), 0);
+
+        // Card and its Column; the slotted Toggle keeps its widget.
+        let _ = tree.build(&loader, &doc("Card { gap: 8  Toggle { } }"), &bindings);
+        assert_eq!(tree.constructed(), 2);
+    }
+
+    #[test]
+    fn model_change_rebuilds_only_bound_nodes() {
+        use crate::dsl::ModelValue;
+        let model = Rc::new(RefCell::new(HashMap::from([
+            ("fuel".to_string(), ModelValue::from(0.5f32)),
+        ])));
+        let loader = DslLoader::new();
+        let bindings = DslBindings::new().with_model(Rc::clone(&model));
+        let d = doc("Column { ProgressBar { value: {fuel} } Toggle { } }");
+        let mut tree = DslTree::new();
+        let _ = tree.build(&loader, &d, &bindings);
+
+        let _ = tree.build(&loader, &d, &bindings);
+        assert_eq!(tree.constructed(), 0);
+
+        // The bar and the Column (which sees its children's props); not the Toggle.
+        model.borrow_mut().insert("fuel".into(), ModelValue::from(0.75f32));
+        let _ = tree.build(&loader, &d, &bindings);
+        assert_eq!(tree.constructed(), 2);
+    }
+
+    #[test]
+    fn keyed_loop_rows_survive_reordering() {
+        use crate::dsl::ModelValue;
+        let trucks = |ids: &[u32]| ModelValue::from(
+            ids.iter().map(|&id| ModelValue::record([("id", id)])).collect::<Vec<_>>(),
+        );
+        let model = Rc::new(RefCell::new(HashMap::from([("trucks".to_string(), trucks(&[1, 2, 3]))])));
+        let loader = DslLoader::new();
+        let bindings = DslBindings::new().with_model(Rc::clone(&model));
+        let d = doc("Column { for t in trucks { Toggle { id: {t.id} } } }");
+        let mut tree = DslTree::new();
+        let _ = tree.build(&loader, &d, &bindings);
+        assert_eq!(tree.len(), 4);
+
+        model.borrow_mut().insert("trucks".into(), trucks(&[3, 1, 2]));
+        let _ = tree.build(&loader, &d, &bindings);
+        assert_eq!(tree.constructed(), 1);
+
+        model.borrow_mut().insert("trucks".into(), trucks(&[3]));
+        let _ = tree.build(&loader, &d, &bindings);
+        assert_eq!(tree.len(), 2);
+    }
+
+    #[test]
+    fn registered_widgets_rebuild_on_what_they_read() {
+        let bindings = DslBindings::new();
+        let mut loader = DslLoader::new();
+        loader.register_widget("Lamp", |_, ctx| {
+            let lit = ctx.state("lamp") == Some(WidgetStateValue::Bool(true));
+            let mut row = crate::widgets::flex::Row::new().spacing(if lit { 1.0 } else { 0.0 });
+            for child in ctx.children() {
+                row = row.child(child);
+            }
+            row.into()
+        });
+        let d = doc("Column { Lamp { Toggle { } } Toggle { } }");
+        let mut tree = DslTree::new();
+        let _ = tree.build(&loader, &d, &bindings);
+        assert_eq!(tree.constructed(), 4);
+
+        let _ = tree.build(&loader, &d, &bindings);
+        assert_eq!(tree.constructed(), 0);
+
+        // The lamp read the state; its child Toggle keeps its widget.
+        bindings.widget_state.borrow_mut().insert("lamp".into(), WidgetStateValue::Bool(true));
+        let _ = tree.build(&loader, &d, &bindings);
+        assert_eq!(tree.constructed(), 1);
+    }
+
+    #[test]
+    fn theme_switch_rebuilds_only_styled_nodes() {
+        use crate::dsl::theme::Theme;
+        let bindings = DslBindings::new();
+        let mut loader = DslLoader::new();
+        loader.set_theme(Theme::parse("Theme { accent: #ff0000ff  Button { bg: $accent } }").unwrap());
+        let d = doc("Column { Button \"a\" { } Container { bg: $accent  Text \"b\" { } } Text \"c\" { } }");
+        let mut tree = DslTree::new();
+        let _ = tree.build(&loader, &d, &bindings);
+        assert_eq!(tree.constructed(), 5);
+
+        let _ = tree.build(&loader, &d, &bindings);
+        assert_eq!(tree.constructed(), 0);
+
+        // The button and the container change colour; the texts are untouched.
+        loader.set_theme(Theme::parse("Theme { accent: #0000ffff  Button { bg: $accent } }").unwrap());
+        let _ = tree.build(&loader, &d, &bindings);
+        assert_eq!(tree.constructed(), 2);
+    }
+
+    #[test]
+    fn theme_switch_rebuilds_app_widgets_that_read_a_style() {
+        use crate::dsl::theme::Theme;
+        use crate::widgets::{button::Button, container::Container};
+        let bindings = DslBindings::new();
+        let mut loader = DslLoader::new();
+        loader.register_widget("Gauge", |_, ctx| ctx.themed(Button::new(Container::new()), None).into());
+        loader.set_theme(Theme::parse("Theme { Button { bg: #ff0000ff } }").unwrap());
+        let d = doc("Column { Gauge { } Text \"c\" { } }");
+        let mut tree = DslTree::new();
+        let _ = tree.build(&loader, &d, &bindings);
+        assert_eq!(tree.constructed(), 3);
+
+        loader.set_theme(Theme::parse("Theme { Button { bg: #ff0000ff }  Text { size: 9 } }").unwrap());
+        let _ = tree.build(&loader, &d, &bindings);
+        assert_eq!(tree.constructed(), 1);
+
+        loader.set_theme(Theme::parse("Theme { Button { bg: #0000ffff }  Text { size: 9 } }").unwrap());
+        let _ = tree.build(&loader, &d, &bindings);
+        assert_eq!(tree.constructed(), 1);
+    }
+
+    #[test]
+    fn widget_internal_state_persists_across_frames() {
+        // No `id` / `on_change`: the toggle's state lives only in the widget.
+        let mut h = Harness::mkml(
+            UiScene::new(),
+            DslLoader::new(),
+            "Toggle { }",
+            DslBindings::new(),
+            Vec2::new(80.0, 40.0),
+        ).unwrap();
+
+        let before = h.render();
+        h.run(InputScript::new().click(Vec2::new(10.0, 10.0)).idle());
+        let after = h.render();
+        assert_ne!(before, after);
+
+        h.run(InputScript::new().idle().idle());
+        assert_eq!(h.render(), after);
+    }
+}
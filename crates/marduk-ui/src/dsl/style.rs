@@ -0,0 +1,124 @@
+//! Theme styles for widgets, whether a document or Rust code builds them.
+//!
+//! [`DslLoader`](super::DslLoader) fills in what a node leaves unset from the
+//! theme, then hands the node to [`Styled::apply_style`]. Widgets an
+//! application builds itself go through the same step with
+//! [`BuildCtx::themed`](super::BuildCtx::themed) or [`Styled::themed`], so a
+//! `Button::new(…)` inside an application's own widget looks like a
+//! `Button { }` in a document.
+//!
+//! Only looks are styled here — colors, radii, padding, borders, font size.
+//! Fonts are bound by name, so they stay with the builder.
+
+use marduk_engine::paint::Color;
+use marduk_engine::scene::Border;
+
+use crate::dsl::ast::Node;
+use crate::dsl::builder::{parse_edges, NodeExt};
+use crate::dsl::theme::Theme;
+use crate::widgets::{button::Button, combobox::Combobox, textbox::TextBox};
+
+/// A widget a theme can style.
+pub trait Styled: Sized {
+    /// The widget name theme styles are written for, e.g. `"Button"`.
+    const WIDGET: &'static str;
+
+    /// Set the looks `style`'s properties specify, keeping the rest.
+    fn apply_style(self, style: &Node) -> Self;
+
+    /// `self` with `theme`'s style for the widget — and for `class`, when
+    /// given — applied.
+    fn themed(self, theme: &Theme, class: Option<&str>) -> Self {
+        self.apply_style(&theme.style_of(Self::WIDGET, class))
+    }
+}
+
+impl Styled for Button {
+    const WIDGET: &'static str = "Button";
+
+    fn apply_style(mut self, style: &Node) -> Self {
+        if let Some(c) = style.engine_color("bg")       { self = self.background(c); }
+        if let Some(c) = style.engine_color("hover_bg") { self = self.hover_background(c); }
+        if let Some(c) = style.engine_color("press_bg") { self = self.press_background(c); }
+        if let Some(r) = style.prop_f32("corner_radius") { self = self.corner_radius(r); }
+        if let Some(v) = style.prop_f32("padding")       { self = self.padding_all(v); }
+        if let Some(edges) = parse_edges(style)          { self = self.padding(edges); }
+        if let Some(bw) = style.prop_f32("border_width") {
+            let bc = style.engine_color("border_color")
+                .unwrap_or_else(|| Color::from_straight(1.0, 1.0, 1.0, 0.3));
+            self = self.border(Border::new(bw, bc));
+        }
+        self
+    }
+}
+
+impl Styled for TextBox {
+    const WIDGET: &'static str = "TextBox";
+
+    fn apply_style(mut self, style: &Node) -> Self {
+        if let Some(v) = style.prop_f32("font_size") { self = self.font_size(v); }
+        if let Some(v) = style.engine_color("text_color").or_else(|| style.engine_color("color")) {
+            self = self.text_color(v);
+        }
+        if let Some(v) = style.engine_color("placeholder_color") { self = self.placeholder_color(v); }
+        if let Some(v) = style.engine_color("bg")                { self = self.bg(v); }
+        if let Some(v) = style.engine_color("focused_bg")        { self = self.focused_bg(v); }
+        if let Some(v) = style.engine_color("border_color")      { self = self.border_color(v); }
+        if let Some(v) = style.engine_color("focused_border_color").or_else(|| style.engine_color("accent")) {
+            self = self.focused_border_color(v);
+        }
+        if let Some(v) = style.prop_f32("corner_radius")         { self = self.corner_radius(v); }
+        if let Some(v) = style.prop_f32("padding")               { self = self.padding_all(v); }
+        if let Some(v) = style.engine_color("selection_color")   { self = self.selection_color(v); }
+        self
+    }
+}
+
+impl Styled for Combobox {
+    const WIDGET: &'static str = "Combobox";
+
+    fn apply_style(mut self, style: &Node) -> Self {
+        if let Some(v) = style.prop_f32("font_size")     { self = self.font_size(v); }
+        if let Some(v) = style.prop_f32("corner_radius") { self = self.corner_radius(v); }
+        if let Some(v) = style.engine_color("text_color").or_else(|| style.engine_color("color")) {
+            self = self.text_color(v);
+        }
+        if let Some(v) = style.engine_color("bg")           { self = self.bg(v); }
+        if let Some(v) = style.engine_color("border_color") { self = self.border_color(v); }
+        if let Some(v) = style.engine_color("focused_border_color").or_else(|| style.engine_color("accent")) {
+            self = self.focused_border_color(v);
+        }
+        if let Some(v) = style.engine_color("item_bg")       { self = self.item_bg(v); }
+        if let Some(v) = style.engine_color("item_hover_bg") { self = self.item_hover_bg(v); }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use marduk_engine::coords::Vec2;
+
+    use super::*;
+    use crate::dsl::{DslBindings, DslLoader};
+    use crate::scene::UiScene;
+    use crate::testing::Harness;
+    use crate::widgets::container::Container;
+
+    const THEME: &str = "Theme { Button { bg: #d04030ff  hover_bg: #e05040ff  padding: 10  corner_radius: 3 } }";
+
+    #[test]
+    fn rust_built_widgets_match_document_ones() {
+        let viewport = Vec2::new(40.0, 30.0);
+        let theme = Theme::parse(THEME).unwrap();
+        let mut loader = DslLoader::new();
+        loader.set_theme(theme.clone());
+        let mut from_doc = Harness::mkml(UiScene::new(), loader, "Button { }", DslBindings::new(), viewport).unwrap();
+        let themed = Button::new(Container::new()).themed(&theme, None);
+        let mut from_rust = Harness::element(UiScene::new(), themed, viewport);
+        let mut plain = Harness::element(UiScene::new(), Button::new(Container::new()), viewport);
+
+        let styled = from_doc.render();
+        assert_eq!(from_rust.render(), styled);
+        assert_ne!(plain.render(), styled);
+    }
+}
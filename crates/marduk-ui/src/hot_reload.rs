@@ -0,0 +1,173 @@
+//! Development-time reloading of `.mkml` files from disk.
+//!
+//! Sources registered with [`Application::run_file`],
+//! [`Application::component_file`] and [`Application::theme_file`] are
+//! watched by polling their modification times, as are the files they
+//! `import` — no platform file-notification APIs are involved. A changed
+//! file is re-parsed and swapped in; a file that fails to load is reported
+//! in an overlay at the bottom of the window until it is fixed.
+//!
+//! [`Application::run_file`]: crate::Application::run_file
+//! [`Application::component_file`]: crate::Application::component_file
+//! [`Application::theme_file`]: crate::Application::theme_file
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+use marduk_engine::paint::{Color, Paint};
+use marduk_engine::text::FontId;
+
+use crate::widget::Element;
+use crate::widgets::{
+    container::Container,
+    flex::Column,
+    stack::{AnchorVal, Stack, StackItem},
+    text::Text,
+};
+
+/// How often modification times are checked.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+// ── Watcher ───────────────────────────────────────────────────────────────
+
+/// What a watched file provides.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Source {
+    /// The document passed to `run_file`.
+    Main,
+    /// A component registered under this alias.
+    Component(String),
+    /// A theme registered under this name.
+    Theme(String),
+}
+
+struct Watched {
+    source:   Source,
+    path:     PathBuf,
+    modified: Option<SystemTime>,
+}
+
+/// Polls a set of files for modification-time changes.
+pub(crate) struct Watcher {
+    files:     Vec<Watched>,
+    last_poll: Option<Instant>,
+}
+
+impl Watcher {
+    pub(crate) fn new() -> Self {
+        Self { files: Vec::new(), last_poll: None }
+    }
+
+    /// Start watching `path`; its current state counts as seen.
+    pub(crate) fn watch(&mut self, source: Source, path: impl Into<PathBuf>) {
+        let path = path.into();
+        let modified = modified(&path);
+        self.files.push(Watched { source, path, modified });
+    }
+
+    /// Whether `path` is already watched.
+    pub(crate) fn watches(&self, path: &Path) -> bool {
+        self.files.iter().any(|f| f.path == path)
+    }
+
+    /// Make the next [`poll`](Self::poll) check the files immediately.
+    #[cfg(test)]
+    pub(crate) fn force_poll(&mut self) {
+        self.last_poll = None;
+    }
+
+    /// Files whose modification time changed since the last poll (including
+    /// files that disappeared or reappeared). Checks at most every
+    /// [`POLL_INTERVAL`]; returns nothing in between.
+    pub(crate) fn poll(&mut self) -> Vec<(Source, PathBuf)> {
+        if self.files.is_empty() || self.last_poll.is_some_and(|t| t.elapsed() < POLL_INTERVAL) {
+            return Vec::new();
+        }
+        self.last_poll = Some(Instant::now());
+
+        let mut changed = Vec::new();
+        for file in &mut self.files {
+            let now = modified(&file.path);
+            if now != file.modified {
+                file.modified = now;
+                changed.push((file.source.clone(), file.path.clone()));
+            }
+        }
+        changed
+    }
+
+    /// When [`poll`](Self::poll) next checks the files; `None` when nothing
+    /// is watched. An idle window must be woken then to notice edits.
+    pub(crate) fn next_poll(&self) -> Option<Instant> {
+        if self.files.is_empty() {
+            return None;
+        }
+        Some(self.last_poll.map_or_else(Instant::now, |t| t + POLL_INTERVAL))
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+// ── Error overlay ─────────────────────────────────────────────────────────
+
+/// `root` with a red panel listing `errors` pinned to the bottom edge.
+///
+/// Without a font the panel is a plain red strip; the errors are still
+/// logged when they occur.
+pub(crate) fn with_error_overlay(root: Element, errors: &[String], font: Option<FontId>) -> Element {
+    if errors.is_empty() {
+        return root;
+    }
+    let white = Color::from_straight(1.0, 1.0, 1.0, 1.0);
+    let mut lines = Column::new().spacing(4.0);
+    if let Some(font) = font {
+        for line in errors.iter().flat_map(|e| e.lines()) {
+            lines = lines.child(Text::new(line, font, 13.0, white));
+        }
+    }
+    let panel = Container::new()
+        .background(Paint::Solid(Color::from_srgb_u8(150, 30, 30, 235)))
+        .padding_all(if font.is_some() { 10.0 } else { 3.0 })
+        .child(lines);
+
+    let px = AnchorVal::Px(0.0);
+    Stack::new()
+        .item(StackItem::new(root).left(px).top(px).right(px).bottom(px))
+        .item(StackItem::new(panel).left(px).right(px).bottom(px))
+        .into()
+}
+
+// ── tests ─────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn poll_reports_changed_files() {
+        let dir = std::env::temp_dir().join(format!("marduk-hot-reload-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("main.mkml");
+        std::fs::write(&path, "Column { }").unwrap();
+
+        let mut watcher = Watcher::new();
+        assert_eq!(watcher.next_poll(), None);
+        watcher.watch(Source::Main, &path);
+        assert!(watcher.poll().is_empty());
+        assert!(watcher.next_poll().is_some_and(|t| t > Instant::now()));
+
+        // Bump the mtime explicitly; coarse filesystem clocks may not tick
+        // between two writes in the same test.
+        let later = SystemTime::now() + Duration::from_secs(5);
+        std::fs::File::options().write(true).open(&path).unwrap().set_modified(later).unwrap();
+        watcher.last_poll = None;
+        assert_eq!(watcher.poll(), vec![(Source::Main, path.clone())]);
+
+        std::fs::remove_file(&path).unwrap();
+        watcher.last_poll = None;
+        assert_eq!(watcher.poll(), vec![(Source::Main, path.clone())]);
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -40,24 +40,28 @@
 //! }
 //! ```
 
+pub mod animation;
 pub mod app;
 pub mod constraints;
 pub mod cursor;
 pub mod focus;
+pub(crate) mod hot_reload;
 pub(crate) mod image_loader;
 
 // Top-level re-exports for the common entry point — `use marduk_ui::Application`
-pub use app::Application;
+pub use app::{Application, Proxy};
 pub use marduk_engine::window::WindowMode;
 pub mod dsl;
 pub mod event;
 pub mod painter;
 pub mod scene;
+pub mod testing;
 pub mod widget;
 pub mod widgets;
 
 /// Everything you need to build and extend UI — import this in your component files.
 pub mod prelude {
+    pub use crate::animation::{AnimId, Easing, Spring, Transition};
     pub use crate::constraints::{Constraints, Edges, LayoutCtx};
     pub use crate::focus::{FocusId, FocusManager};
     pub use crate::cursor::CursorIcon;
@@ -96,8 +100,10 @@ pub mod prelude {
     pub use marduk_engine::text::FontId;
 
     // DSL
-    pub use crate::dsl::{DslBindings, DslDocument, DslLoader, ParseError};
+    pub use crate::dsl::{BuildCtx, DslBindings, DslDocument, DslLoader, DslTree, Model, ModelValue, ParseError};
+    pub use crate::dsl::schema::{PropKind, PropSchema, WidgetSchema};
+    pub use crate::dsl::theme::Theme;
 
     // Application (entry point for end-user apps)
-    pub use crate::app::{Application, FontMap, WidgetState};
+    pub use crate::app::{ActiveTheme, Application, FontMap, Proxy, WidgetState};
 }
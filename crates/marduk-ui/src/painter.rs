@@ -7,6 +7,7 @@ use marduk_engine::paint::{Color, Paint};
 use marduk_engine::scene::{Border, DrawList, ZIndex};
 use marduk_engine::text::{FontId, FontSystem};
 
+use crate::animation::{AnimId, Animatable, Animations, Transition};
 use crate::constraints::LayoutCtx;
 use crate::cursor::CursorIcon;
 use crate::focus::{FocusId, FocusManager};
@@ -45,6 +46,10 @@ pub struct Painter<'a> {
     /// Widgets call [`set_cursor`] during paint when hovered.  The last call
     /// wins; the scene reads this after the paint pass and forwards it to the OS.
     cursor: Option<Rc<Cell<CursorIcon>>>,
+    /// Transition store shared with `UiScene`; see [`animate`].
+    animations: Option<Rc<RefCell<Animations>>>,
+    /// Earliest time a widget asked to be painted again; see [`request_frame_at`].
+    frame_at: Option<Rc<Cell<Option<u64>>>>,
     /// Monotonic application time in milliseconds. Matches [`UiInput::time_ms`].
     pub time_ms: u64,
 }
@@ -70,6 +75,8 @@ impl<'a> Painter<'a> {
             focus: None,
             overlays: None,
             cursor: None,
+            animations: None,
+            frame_at: None,
             time_ms,
         }
     }
@@ -89,6 +96,16 @@ impl<'a> Painter<'a> {
         self
     }
 
+    pub(crate) fn with_animations(mut self, animations: Rc<RefCell<Animations>>) -> Self {
+        self.animations = Some(animations);
+        self
+    }
+
+    pub(crate) fn with_frame_at(mut self, frame_at: Rc<Cell<Option<u64>>>) -> Self {
+        self.frame_at = Some(frame_at);
+        self
+    }
+
     // ── focus ─────────────────────────────────────────────────────────────
 
     /// Returns `true` if `id` is the currently focused widget.
@@ -201,6 +218,40 @@ impl<'a> Painter<'a> {
         }
     }
 
+    // ── animation ─────────────────────────────────────────────────────────
+
+    /// The current value of `channel` of widget `id`, moving toward `target`
+    /// with `transition`.
+    ///
+    /// Call it every frame with the value the widget's state calls for; a
+    /// changed target starts the transition. Returns `target` when painting
+    /// without a scene.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let bg = if painter.is_hovered(rect) { self.hover_bg } else { self.bg };
+    /// let bg = painter.animate(self.anim_id, "bg", bg, self.transition);
+    /// ```
+    pub fn animate<T: Animatable>(&mut self, id: AnimId, channel: &'static str, target: T, transition: Transition) -> T {
+        match &self.animations {
+            Some(animations) => animations.borrow_mut().animate(id, channel, target, transition, self.time_ms),
+            None => target,
+        }
+    }
+
+    /// Ask for another frame at app time `time_ms` (as in [`time_ms`](Self::time_ms)),
+    /// even if no input arrives before then.
+    ///
+    /// The application sleeps between input events; a widget whose look
+    /// changes with time alone — a tooltip delay, a blinking caret — calls
+    /// this during paint with the time of its next change. The earliest
+    /// request of the frame wins.
+    pub fn request_frame_at(&mut self, time_ms: u64) {
+        if let Some(cell) = &self.frame_at {
+            cell.set(Some(cell.get().map_or(time_ms, |t| t.min(time_ms))));
+        }
+    }
+
     // ── layout context ────────────────────────────────────────────────────
 
     /// Returns a [`LayoutCtx`] borrowing this painter's font and image stores.
@@ -7,6 +7,7 @@ use marduk_engine::input::{Key, Modifiers};
 use marduk_engine::scene::DrawList;
 use marduk_engine::text::{FontId, FontSystem};
 
+use crate::animation::Animations;
 use crate::constraints::{Constraints, LayoutCtx};
 use crate::cursor::CursorIcon;
 use crate::event::UiEvent;
@@ -54,8 +55,8 @@ pub struct UiInput {
 /// indirectly through the `TextRenderer`) and the `DrawList` that is populated
 /// each frame by [`frame`].
 ///
-/// The GPU renderers (`RectRenderer`, `TextRenderer`, …) still live in the
-/// application and receive `&mut DrawList` returned by `frame`.
+/// The GPU renderer (`BatchRenderer`) still lives in the application and
+/// receives `&mut DrawList` returned by `frame`.
 ///
 /// # Example
 ///
@@ -69,15 +70,14 @@ pub struct UiInput {
 ///     viewport,
 ///     &UiInput { mouse_pos, ..Default::default() },
 /// );
-/// rect_renderer.render(rctx, target, draw_list);
-/// text_renderer.render(rctx, target, draw_list, &ui.font_system);
+/// renderer.render(rctx, target, draw_list, &ui.font_system, &ui.image_store);
 /// ```
 pub struct UiScene {
     /// Fonts are public so the application can pass `&ui.font_system` to the
-    /// engine's `TextRenderer::render`.
+    /// engine's `BatchRenderer::render`.
     pub font_system: FontSystem,
     /// Images are public so the application can pass `&ui.image_store` to the
-    /// engine's `ImageRenderer::render`.
+    /// engine's `BatchRenderer::render`.
     pub image_store: ImageStore,
     /// Draw list populated by the most recent [`frame`] call.
     ///
@@ -108,6 +108,12 @@ pub struct UiScene {
     /// Reset to `CursorIcon::Default` at the start of each frame.
     /// Read via [`current_cursor`] after the frame to apply to the OS window.
     cursor: Rc<Cell<CursorIcon>>,
+    /// Widget transitions, read through [`Painter::animate`] during paint.
+    animations: Rc<RefCell<Animations>>,
+    /// Earliest frame time requested through [`Painter::request_frame_at`].
+    ///
+    /// Reset at the start of each frame.
+    frame_at: Rc<Cell<Option<u64>>>,
 }
 
 impl UiScene {
@@ -120,6 +126,8 @@ impl UiScene {
             focus: RefCell::new(FocusManager::new()),
             overlay_rects: Rc::new(RefCell::new(Vec::new())),
             cursor: Rc::new(Cell::new(CursorIcon::Default)),
+            animations: Rc::new(RefCell::new(Animations::new())),
+            frame_at: Rc::new(Cell::new(None)),
         }
     }
 
@@ -131,6 +139,23 @@ impl UiScene {
         self.cursor.get()
     }
 
+    /// Returns `true` while a transition painted in the last frame has not
+    /// reached its target.
+    ///
+    /// Keep requesting frames while this holds, or animations freeze until
+    /// the next input event.
+    pub fn is_animating(&self) -> bool {
+        self.animations.borrow().is_running()
+    }
+
+    /// The app time (as in [`UiInput::time_ms`]) at which a widget painted in
+    /// the last frame changes with no input, if any.
+    ///
+    /// Schedule a frame for then; nothing else would wake an idle window.
+    pub fn next_frame_at(&self) -> Option<u64> {
+        self.frame_at.get()
+    }
+
     /// Load a TrueType / OpenType font from raw bytes.
     pub fn load_font(&mut self, data: &[u8]) -> Result<FontId, marduk_engine::text::FontLoadError> {
         self.font_system.load_font(data)
@@ -156,6 +181,13 @@ impl UiScene {
         self.image_store.insert_scaled(pixels, width, height, logical_width, logical_height)
     }
 
+    /// Decode and store an encoded image (PNG, JPEG, …, or SVG rasterized
+    /// at `scale × natural size`), sized by its logical dimensions.
+    pub fn load_image_bytes(&mut self, bytes: &[u8], scale: f32) -> Result<ImageId, String> {
+        let img = crate::image_loader::decode_image(bytes, scale)?;
+        Ok(self.load_image_scaled(img.pixels, img.width, img.height, img.logical_width, img.logical_height))
+    }
+
     /// Like [`frame`] but borrows the root widget instead of consuming it.
     ///
     /// Use this when the root widget holds state that must persist across frames
@@ -171,6 +203,7 @@ impl UiScene {
         self.draw_list.clear();
         self.overlay_rects.borrow_mut().clear();
         self.cursor.set(CursorIcon::Default);
+        self.frame_at.set(None);
 
         // ── Tab / Escape: advance or clear focus before the paint pass ────
         // This ensures the paint pass sees the correct focus state.
@@ -205,8 +238,12 @@ impl UiScene {
                 input.time_ms,
             ).with_focus(&self.focus)
              .with_overlays(Rc::clone(&self.overlay_rects))
-             .with_cursor(Rc::clone(&self.cursor));
+             .with_cursor(Rc::clone(&self.cursor))
+             .with_animations(Rc::clone(&self.animations))
+             .with_frame_at(Rc::clone(&self.frame_at));
+            self.animations.borrow_mut().begin_frame();
             root.paint(&mut painter, rect);
+            self.animations.borrow_mut().end_frame();
         }
 
         // ── events ────────────────────────────────────────────────────────
@@ -309,6 +346,7 @@ impl UiScene {
         self.draw_list.clear();
         self.overlay_rects.borrow_mut().clear();
         self.cursor.set(CursorIcon::Default);
+        self.frame_at.set(None);
 
         // ── Tab / Escape: advance or clear focus before the paint pass ────
         for key in &input.keys_pressed {
@@ -342,8 +380,12 @@ impl UiScene {
                 input.time_ms,
             ).with_focus(&self.focus)
              .with_overlays(Rc::clone(&self.overlay_rects))
-             .with_cursor(Rc::clone(&self.cursor));
+             .with_cursor(Rc::clone(&self.cursor))
+             .with_animations(Rc::clone(&self.animations))
+             .with_frame_at(Rc::clone(&self.frame_at));
+            self.animations.borrow_mut().begin_frame();
             root.paint(&mut painter, rect);
+            self.animations.borrow_mut().end_frame();
         }
 
         // ── events ────────────────────────────────────────────────────────
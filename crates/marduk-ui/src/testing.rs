@@ -0,0 +1,751 @@
+//! Headless golden-image snapshot testing for widget trees.
+//!
+//! [`Harness`] drives a [`UiScene`] without a window: it feeds a scripted
+//! sequence of [`UiInput`] frames through [`UiScene::frame`], rasterizes the
+//! final `DrawList` with the engine's CPU [`SoftwareRenderer`], and compares
+//! the result against a PNG on disk.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use marduk_ui::prelude::*;
+//! use marduk_ui::testing::{Harness, InputScript};
+//!
+//! let mut scene = UiScene::new();
+//! let font = scene.load_font(include_bytes!("../assets/Inter.ttf")).unwrap();
+//! let bindings = DslBindings::new().with_font("body", font);
+//!
+//! let mut h = Harness::mkml(scene, DslLoader::new(), SRC, bindings, Vec2::new(320.0, 200.0))
+//!     .unwrap()
+//!     .tolerance(2);
+//! h.run(InputScript::new().click(Vec2::new(40.0, 12.0)).idle());
+//! h.assert_snapshot("tests/snapshots/combobox_open.png");
+//! ```
+//!
+//! # Golden files
+//!
+//! A missing golden fails the check. Set `MARDUK_UPDATE_SNAPSHOTS=1` to write
+//! goldens — new or existing — from the current output instead of
+//! comparing. On a mismatch, `<name>.actual.png` and `<name>.diff.png` are
+//! written next to the golden; they are removed again once the check passes.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use marduk_engine::coords::Vec2;
+use marduk_engine::input::{Key, Modifiers};
+use marduk_engine::paint::Color;
+use marduk_engine::render::software::{Pixmap, SoftwareRenderer};
+
+use crate::dsl::{DslBindings, DslDocument, DslLoader, DslTree, ParseError};
+use crate::scene::{UiInput, UiScene};
+use crate::widget::Element;
+
+/// Environment variable that makes goldens be written instead of compared.
+pub const UPDATE_ENV: &str = "MARDUK_UPDATE_SNAPSHOTS";
+
+/// Simulated time between scripted frames, in milliseconds.
+const FRAME_MS: u64 = 16;
+
+// ── SnapshotError ─────────────────────────────────────────────────────────
+
+/// Why a snapshot comparison failed.
+#[derive(Debug)]
+pub enum SnapshotError {
+    /// Reading, writing, or encoding a PNG failed.
+    Io { path: PathBuf, message: String },
+    /// There is no golden image and no update was requested.
+    Missing { path: PathBuf },
+    /// The golden image has different dimensions from the rendered frame.
+    SizeMismatch {
+        path: PathBuf,
+        expected: (u32, u32),
+        actual: (u32, u32),
+    },
+    /// Some pixels differ by more than the configured tolerance.
+    Mismatch {
+        path: PathBuf,
+        /// Number of pixels with at least one channel outside the tolerance.
+        differing: usize,
+        total: usize,
+        /// Largest per-channel difference seen anywhere in the image.
+        max_delta: u8,
+        diff_path: PathBuf,
+    },
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io { path, message } => {
+                write!(f, "snapshot {}: {message}", path.display())
+            }
+            Self::Missing { path } => write!(
+                f,
+                "snapshot {}: no golden image; set {UPDATE_ENV}=1 to write it",
+                path.display(),
+            ),
+            Self::SizeMismatch { path, expected, actual } => write!(
+                f,
+                "snapshot {}: golden is {}×{} but the frame rendered {}×{}",
+                path.display(), expected.0, expected.1, actual.0, actual.1,
+            ),
+            Self::Mismatch { path, differing, total, max_delta, diff_path } => write!(
+                f,
+                "snapshot {}: {differing} of {total} pixels differ (max channel delta {max_delta}); \
+                 diff written to {}",
+                path.display(), diff_path.display(),
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
+/// Outcome of a successful [`Harness::check_snapshot`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapshotStatus {
+    /// The frame matched the golden within tolerance.
+    Matched,
+    /// An update was requested, so the golden was written.
+    Written,
+}
+
+// ── Harness ───────────────────────────────────────────────────────────────
+
+#[allow(clippy::large_enum_variant)]
+enum Root {
+    /// Caller-built tree, kept alive across frames via [`UiScene::frame_ref`].
+    Element(Element),
+    /// Parsed `.mkml`, reconciled every frame exactly like `Application` does.
+    Dsl {
+        loader: DslLoader,
+        doc: DslDocument,
+        bindings: DslBindings,
+        tree: DslTree,
+    },
+}
+
+/// Runs a widget tree headlessly and snapshots the result.
+///
+/// Construct with [`element`](Self::element) or [`mkml`](Self::mkml), passing
+/// a `UiScene` that already has any fonts and images the tree refers to.
+///
+/// Painting happens before event routing inside a frame, so the effect of an
+/// input frame only becomes visible in the *next* frame. Scripts built with
+/// [`InputScript`] should end with [`idle`](InputScript::idle) when the last
+/// interaction matters.
+pub struct Harness {
+    scene: UiScene,
+    root: Root,
+    viewport: Vec2,
+    background: Color,
+    tolerance: u8,
+    renderer: SoftwareRenderer,
+    frames_run: usize,
+    /// Every script's clock starts at zero; frames run at `epoch_ms +
+    /// time_ms` so time keeps moving forward across scripts.
+    epoch_ms: u64,
+    last_ms: u64,
+}
+
+impl Harness {
+    /// Snapshot a widget tree built in Rust.
+    ///
+    /// The element is borrowed by every frame, so widget-internal state
+    /// (open dropdowns, scroll offsets, splitter ratios) persists.
+    pub fn element(scene: UiScene, root: impl Into<Element>, viewport: Vec2) -> Self {
+        Self::with_root(scene, Root::Element(root.into()), viewport)
+    }
+
+    /// Snapshot a `.mkml` document.
+    ///
+    /// Components referenced by `src` must already be registered on `loader`.
+    /// The tree is reconciled against `bindings` every frame through a
+    /// [`DslTree`], just as it is under `Application`.
+    pub fn mkml(
+        scene: UiScene,
+        loader: DslLoader,
+        src: &str,
+        bindings: DslBindings,
+        viewport: Vec2,
+    ) -> Result<Self, ParseError> {
+        let doc = loader.parse(src)?;
+        Ok(Self::with_root(scene, Root::Dsl { loader, doc, bindings, tree: DslTree::new() }, viewport))
+    }
+
+    fn with_root(scene: UiScene, root: Root, viewport: Vec2) -> Self {
+        Self {
+            scene,
+            root,
+            viewport,
+            background: Color::from_straight(0.054, 0.051, 0.043, 1.0),
+            tolerance: 1,
+            renderer: SoftwareRenderer::new(),
+            frames_run: 0,
+            epoch_ms: 0,
+            last_ms: 0,
+        }
+    }
+
+    /// Physical pixels per logical pixel (default `1.0`).
+    pub fn scale(mut self, scale: f32) -> Self {
+        self.scene.pixel_ratio = scale;
+        self
+    }
+
+    /// Colour the frame is cleared to before drawing (defaults to the
+    /// `Application` window background).
+    pub fn background(mut self, color: Color) -> Self {
+        self.background = color;
+        self
+    }
+
+    /// Largest per-channel difference (0–255) still treated as equal.
+    pub fn tolerance(mut self, tolerance: u8) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    pub fn scene(&self) -> &UiScene {
+        &self.scene
+    }
+
+    pub fn scene_mut(&mut self) -> &mut UiScene {
+        &mut self.scene
+    }
+
+    /// Bindings of a `.mkml` harness (`None` for [`element`](Self::element)).
+    pub fn bindings(&self) -> Option<&DslBindings> {
+        match &self.root {
+            Root::Dsl { bindings, .. } => Some(bindings),
+            Root::Element(_) => None,
+        }
+    }
+
+    /// Drain the DSL event queue (`on_click`, `on_change`, …) filled so far.
+    pub fn take_events(&self) -> Vec<String> {
+        self.bindings().map(DslBindings::take_events).unwrap_or_default()
+    }
+
+    /// Run one frame with `input`.
+    ///
+    /// `input.time_ms` is taken relative to the start of its script, so
+    /// transitions keep running when several scripts are run in turn.
+    pub fn frame(&mut self, input: &UiInput) {
+        if self.epoch_ms + input.time_ms < self.last_ms {
+            self.epoch_ms = self.last_ms;
+        }
+        self.last_ms = self.epoch_ms + input.time_ms;
+        let input = &UiInput { time_ms: self.last_ms, ..input.clone() };
+        match &mut self.root {
+            Root::Element(root) => {
+                let _ = self.scene.frame_ref(root, self.viewport, input);
+            }
+            Root::Dsl { loader, doc, bindings, tree } => {
+                let root = tree.build(loader, doc, bindings);
+                let _ = self.scene.frame(root, self.viewport, input);
+            }
+        }
+        self.frames_run += 1;
+    }
+
+    /// Run every frame of a script (or any sequence of inputs) in order.
+    pub fn run(&mut self, frames: impl IntoIterator<Item = UiInput>) {
+        for input in frames {
+            self.frame(&input);
+        }
+    }
+
+    /// Rasterize the most recent frame.
+    ///
+    /// Runs a single idle frame first if none has been run yet.
+    pub fn render(&mut self) -> Pixmap {
+        if self.frames_run == 0 {
+            self.frame(&UiInput::default());
+        }
+        let scale = self.scene.pixel_ratio.max(f32::EPSILON);
+        let width = (self.viewport.x * scale).round().max(1.0) as u32;
+        let height = (self.viewport.y * scale).round().max(1.0) as u32;
+
+        let mut pixmap = Pixmap::new(width, height);
+        pixmap.fill(self.background);
+        let UiScene { font_system, image_store, draw_list, .. } = &mut self.scene;
+        self.renderer.render(&mut pixmap, scale, draw_list, font_system, image_store);
+        pixmap
+    }
+
+    /// Render the most recent frame and compare it with the PNG at `path`.
+    pub fn check_snapshot(&mut self, path: impl AsRef<Path>) -> Result<SnapshotStatus, SnapshotError> {
+        let pixmap = self.render();
+        compare_or_write(&pixmap, path.as_ref(), self.tolerance, update_requested())
+    }
+
+    /// Like [`check_snapshot`](Self::check_snapshot) but panics on failure.
+    #[track_caller]
+    pub fn assert_snapshot(&mut self, path: impl AsRef<Path>) {
+        if let Err(e) = self.check_snapshot(path) {
+            panic!("{e}");
+        }
+    }
+}
+
+// ── golden comparison ─────────────────────────────────────────────────────
+
+fn update_requested() -> bool {
+    std::env::var(UPDATE_ENV).is_ok_and(|v| !v.is_empty() && v != "0")
+}
+
+/// `foo/bar.png` → `foo/bar.<suffix>.png`.
+fn sibling(path: &Path, suffix: &str) -> PathBuf {
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("snapshot");
+    path.with_file_name(format!("{stem}.{suffix}.png"))
+}
+
+fn io_err(path: &Path, e: impl fmt::Display) -> SnapshotError {
+    SnapshotError::Io { path: path.to_path_buf(), message: e.to_string() }
+}
+
+fn write_png(path: &Path, width: u32, height: u32, rgba: &[u8]) -> Result<(), SnapshotError> {
+    if let Some(dir) = path.parent()
+        && !dir.as_os_str().is_empty()
+    {
+        std::fs::create_dir_all(dir).map_err(|e| io_err(path, e))?;
+    }
+    image::save_buffer(path, rgba, width, height, image::ExtendedColorType::Rgba8)
+        .map_err(|e| io_err(path, e))
+}
+
+fn compare_or_write(
+    pixmap: &Pixmap,
+    path: &Path,
+    tolerance: u8,
+    update: bool,
+) -> Result<SnapshotStatus, SnapshotError> {
+    let (width, height) = (pixmap.width(), pixmap.height());
+    let actual = pixmap.to_rgba8();
+    let actual_path = sibling(path, "actual");
+    let diff_path = sibling(path, "diff");
+
+    if update {
+        write_png(path, width, height, &actual)?;
+        return Ok(SnapshotStatus::Written);
+    }
+    if !path.exists() {
+        return Err(SnapshotError::Missing { path: path.to_path_buf() });
+    }
+
+    let golden = image::open(path).map_err(|e| io_err(path, e))?.into_rgba8();
+    if golden.dimensions() != (width, height) {
+        write_png(&actual_path, width, height, &actual)?;
+        return Err(SnapshotError::SizeMismatch {
+            path: path.to_path_buf(),
+            expected: golden.dimensions(),
+            actual: (width, height),
+        });
+    }
+
+    let (diff, differing, max_delta) = diff_rgba8(golden.as_raw(), &actual, tolerance);
+    if differing == 0 {
+        // Stale artifacts from an earlier failure would only confuse.
+        let _ = std::fs::remove_file(&actual_path);
+        let _ = std::fs::remove_file(&diff_path);
+        return Ok(SnapshotStatus::Matched);
+    }
+
+    write_png(&actual_path, width, height, &actual)?;
+    write_png(&diff_path, width, height, &diff)?;
+    Err(SnapshotError::Mismatch {
+        path: path.to_path_buf(),
+        differing,
+        total: (width * height) as usize,
+        max_delta,
+        diff_path,
+    })
+}
+
+/// Compares two equally sized RGBA8 buffers.
+///
+/// Returns `(diff_image, differing_pixels, max_channel_delta)`. The diff image
+/// shows differing pixels in solid red over a dimmed greyscale of `expected`.
+fn diff_rgba8(expected: &[u8], actual: &[u8], tolerance: u8) -> (Vec<u8>, usize, u8) {
+    let mut diff = Vec::with_capacity(expected.len());
+    let mut differing = 0;
+    let mut max_delta = 0u8;
+
+    for (e, a) in expected.chunks_exact(4).zip(actual.chunks_exact(4)) {
+        let delta = e.iter().zip(a).map(|(x, y)| x.abs_diff(*y)).max().unwrap_or(0);
+        max_delta = max_delta.max(delta);
+        if delta > tolerance {
+            differing += 1;
+            diff.extend_from_slice(&[255, 0, 0, 255]);
+        } else {
+            let luma = (e[0] as u32 * 77 + e[1] as u32 * 150 + e[2] as u32 * 29) >> 8;
+            let dim = (luma * e[3] as u32 / 255 / 3) as u8;
+            diff.extend_from_slice(&[dim, dim, dim, 255]);
+        }
+    }
+    (diff, differing, max_delta)
+}
+
+// ── InputScript ───────────────────────────────────────────────────────────
+
+/// Builder for a sequence of [`UiInput`] frames.
+///
+/// Mirrors how `Application` derives input from window events: a press
+/// starts a drag at the pointer, and the release frame reports both
+/// `mouse_clicked` and `drag_end`. Each frame advances `time_ms` by 16 ms.
+///
+/// ```rust,ignore
+/// let script = InputScript::new()
+///     .drag(Vec2::new(200.0, 100.0), Vec2::new(260.0, 100.0), 4)
+///     .idle();
+/// harness.run(script);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct InputScript {
+    frames: Vec<UiInput>,
+    mouse_pos: Vec2,
+    time_ms: u64,
+}
+
+impl InputScript {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn push(mut self, input: UiInput) -> Self {
+        self.time_ms += FRAME_MS;
+        self.frames.push(UiInput { mouse_pos: self.mouse_pos, time_ms: self.time_ms, ..input });
+        self
+    }
+
+    /// A frame with no new input.
+    pub fn idle(self) -> Self {
+        self.push(UiInput::default())
+    }
+
+    /// Advance the clock by `ms`, then run one idle frame (e.g. for tooltips).
+    pub fn wait(mut self, ms: u64) -> Self {
+        self.time_ms += ms;
+        self.idle()
+    }
+
+    /// Move the pointer to `pos` (one hover frame).
+    pub fn move_to(mut self, pos: Vec2) -> Self {
+        self.mouse_pos = pos;
+        self.idle()
+    }
+
+    /// Press and release the primary button at `pos` (two frames).
+    pub fn click(mut self, pos: Vec2) -> Self {
+        self.mouse_pos = pos;
+        self.push(UiInput { mouse_pressed: true, drag_origin: Some(pos), ..Default::default() })
+            .push(UiInput { mouse_clicked: true, drag_end: Some(pos), ..Default::default() })
+    }
+
+    /// Press at `from`, move to `to` over `steps` frames, then release.
+    pub fn drag(mut self, from: Vec2, to: Vec2, steps: usize) -> Self {
+        self.mouse_pos = from;
+        self = self.push(UiInput { mouse_pressed: true, drag_origin: Some(from), ..Default::default() });
+        let steps = steps.max(1);
+        for i in 1..=steps {
+            let t = i as f32 / steps as f32;
+            self.mouse_pos = Vec2::new(from.x + (to.x - from.x) * t, from.y + (to.y - from.y) * t);
+            self = self.push(UiInput { mouse_pressed: true, drag_origin: Some(from), ..Default::default() });
+        }
+        self.push(UiInput { mouse_clicked: true, drag_end: Some(from), ..Default::default() })
+    }
+
+    /// Press a named key.
+    pub fn key(self, key: Key) -> Self {
+        self.key_with(key, Modifiers::default())
+    }
+
+    /// Press a named key while `modifiers` are held.
+    pub fn key_with(self, key: Key, modifiers: Modifiers) -> Self {
+        self.push(UiInput { keys_pressed: vec![key], modifiers, ..Default::default() })
+    }
+
+    /// Type `text` as committed text input (one frame).
+    pub fn text(self, text: impl Into<String>) -> Self {
+        self.push(UiInput { text_input: vec![text.into()], ..Default::default() })
+    }
+
+    /// Scroll the wheel by `delta` with the pointer at `pos`.
+    pub fn scroll(mut self, pos: Vec2, delta: f32) -> Self {
+        self.mouse_pos = pos;
+        self.push(UiInput { scroll_delta: delta, ..Default::default() })
+    }
+
+    /// Append an arbitrary frame. `mouse_pos` and `time_ms` are overwritten
+    /// with the script's pointer and clock.
+    pub fn frame(self, input: UiInput) -> Self {
+        self.push(input)
+    }
+
+    pub fn frames(&self) -> &[UiInput] {
+        &self.frames
+    }
+}
+
+impl IntoIterator for InputScript {
+    type Item = UiInput;
+    type IntoIter = std::vec::IntoIter<UiInput>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.frames.into_iter()
+    }
+}
+
+// ── tests ─────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use marduk_engine::paint::Paint;
+
+    use crate::dsl::WidgetStateValue;
+    use crate::widgets::container::Container;
+    use crate::widgets::tooltip::Tooltip;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("marduk-testing-{}-{name}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    /// Checked-in golden under `tests/snapshots/`.
+    fn golden(name: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/snapshots").join(name)
+    }
+
+    /// A `.mkml` harness with the bundled test font registered as `body`.
+    fn widget_harness(src: &str, viewport: Vec2) -> Harness {
+        let mut scene = UiScene::new();
+        let font = scene
+            .load_font(include_bytes!("../../../assets/fonts/Tuffy.ttf"))
+            .unwrap();
+        let bindings = DslBindings::new().with_font("body", font);
+        Harness::mkml(scene, DslLoader::new(), src, bindings, viewport).unwrap().tolerance(2)
+    }
+
+    fn state(h: &Harness, key: &str) -> Option<WidgetStateValue> {
+        h.bindings()?.widget_state.borrow().get(key).cloned()
+    }
+
+    fn red_box() -> Container {
+        Container::new()
+            .min_size(40.0, 30.0)
+            .background(Paint::Solid(Color::from_straight(1.0, 0.0, 0.0, 1.0)))
+    }
+
+    #[test]
+    fn missing_golden_fails_until_written() {
+        let dir = temp_dir("golden");
+        let path = dir.join("box.png");
+
+        let frame = Harness::element(UiScene::new(), red_box(), Vec2::new(64.0, 48.0)).render();
+        let err = compare_or_write(&frame, &path, 0, false).unwrap_err();
+        assert!(matches!(err, SnapshotError::Missing { .. }));
+        assert!(err.to_string().contains(UPDATE_ENV));
+        assert!(!path.exists());
+
+        assert_eq!(compare_or_write(&frame, &path, 0, true).unwrap(), SnapshotStatus::Written);
+        assert_eq!(compare_or_write(&frame, &path, 0, false).unwrap(), SnapshotStatus::Matched);
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn mismatch_writes_diff_image() {
+        let dir = temp_dir("mismatch");
+        let path = dir.join("box.png");
+        let red = Harness::element(UiScene::new(), red_box(), Vec2::new(64.0, 48.0)).render();
+        compare_or_write(&red, &path, 0, true).unwrap();
+
+        let blue = Container::new()
+            .min_size(40.0, 30.0)
+            .background(Paint::Solid(Color::from_straight(0.0, 0.0, 1.0, 1.0)));
+        let err = Harness::element(UiScene::new(), blue, Vec2::new(64.0, 48.0))
+            .check_snapshot(&path)
+            .unwrap_err();
+        match err {
+            SnapshotError::Mismatch { differing, diff_path, .. } => {
+                // The root is laid out over the whole viewport.
+                assert_eq!(differing, 64 * 48);
+                assert!(diff_path.exists());
+                assert!(sibling(&path, "actual").exists());
+            }
+            other => panic!("expected a pixel mismatch, got {other}"),
+        }
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn scale_changes_output_size() {
+        let mut h = Harness::element(UiScene::new(), red_box(), Vec2::new(64.0, 48.0)).scale(2.0);
+        let pixmap = h.render();
+        assert_eq!((pixmap.width(), pixmap.height()), (128, 96));
+    }
+
+    #[test]
+    fn diff_respects_tolerance() {
+        let a = [10, 20, 30, 255, 0, 0, 0, 255];
+        let b = [12, 20, 30, 255, 0, 0, 9, 255];
+        let (_, differing, max_delta) = diff_rgba8(&a, &b, 2);
+        assert_eq!((differing, max_delta), (1, 9));
+        assert_eq!(diff_rgba8(&a, &b, 9).1, 0);
+    }
+
+    #[test]
+    fn scripted_click_toggles_dsl_widget() {
+        let src = "Toggle { id: dark on_change: dark_changed }";
+        let mut h = Harness::mkml(
+            UiScene::new(),
+            DslLoader::new(),
+            src,
+            DslBindings::new(),
+            Vec2::new(80.0, 40.0),
+        ).unwrap();
+
+        let before = h.render();
+        h.run(InputScript::new().click(Vec2::new(10.0, 10.0)).idle());
+        let after = h.render();
+
+        assert_eq!(h.take_events(), vec!["dark_changed".to_string()]);
+        assert_ne!(before, after);
+    }
+
+    #[test]
+    fn transitions_run_on_the_script_clock() {
+        let toggle = |src: &str| {
+            Harness::mkml(UiScene::new(), DslLoader::new(), src, DslBindings::new(), Vec2::new(60.0, 30.0)).unwrap()
+        };
+        let mut instant = toggle("Toggle { id: t  on_change: c }");
+        instant.run(InputScript::new().click(Vec2::new(10.0, 10.0)).idle());
+        let on = instant.render();
+
+        let mut h = toggle("Toggle { id: t  on_change: c  transition: 160  easing: linear }");
+        let off = h.render();
+        // The toggle is rebuilt checked on the idle frame and slides from there.
+        h.run(InputScript::new().click(Vec2::new(10.0, 10.0)).idle().wait(64));
+        assert!(h.scene().is_animating());
+        let sliding = h.render();
+        assert_ne!(sliding, off);
+        assert_ne!(sliding, on);
+
+        // A second script continues the clock rather than restarting it.
+        h.run(InputScript::new().wait(200));
+        assert!(!h.scene().is_animating());
+        assert_eq!(h.render(), on);
+    }
+
+    #[test]
+    fn tooltips_ask_for_a_frame_when_their_delay_ends() {
+        let tip = Tooltip::new(red_box()).text("fuel low").delay_ms(400);
+        let mut h = Harness::element(UiScene::new(), tip, Vec2::new(64.0, 48.0));
+        h.run(InputScript::new().move_to(Vec2::new(10.0, 10.0)));
+        assert_eq!(h.scene().next_frame_at(), None);
+
+        // Hovered from t = 16; nothing else would wake the window at 416.
+        h.run(InputScript::new().idle());
+        assert_eq!(h.scene().next_frame_at(), Some(416));
+
+        h.run(InputScript::new().wait(400));
+        assert_eq!(h.scene().next_frame_at(), None);
+    }
+
+    const COMBOBOX: &str = r#"
+        Column {
+            padding: 8
+            gap: 8
+            Combobox {
+                id: fuel
+                font: body
+                placeholder: "Fuel type"
+                on_change: fuel_changed
+                Option "Diesel" { value: diesel }
+                Option "Petrol" { value: petrol }
+                Option "Electric" { value: electric }
+            }
+            Container { bg: #3a5a8aff  padding: 30 }
+        }
+    "#;
+
+    #[test]
+    fn combobox_dropdown_paints_over_later_siblings() {
+        let mut h = widget_harness(COMBOBOX, Vec2::new(200.0, 160.0));
+        h.run(InputScript::new().click(Vec2::new(40.0, 24.0)).idle());
+        assert_eq!(state(&h, "fuel::open"), Some(WidgetStateValue::Bool(true)));
+        h.assert_snapshot(golden("combobox_open.png"));
+    }
+
+    #[test]
+    fn combobox_option_click_selects_and_closes() {
+        let mut h = widget_harness(COMBOBOX, Vec2::new(200.0, 160.0));
+        // Open, then pick the second option (rows are 32 px below the field).
+        h.run(InputScript::new().click(Vec2::new(40.0, 24.0)).idle().click(Vec2::new(40.0, 88.0)).idle());
+        assert_eq!(h.take_events(), vec!["fuel_changed".to_string()]);
+        assert_eq!(state(&h, "fuel"), Some(WidgetStateValue::Str("petrol".into())));
+        assert_eq!(state(&h, "fuel::open"), None);
+        h.assert_snapshot(golden("combobox_selected.png"));
+    }
+
+    const SPLITTER: &str = "
+        Splitter {
+            id: split
+            on_change: split_changed
+            Container { bg: #2a4a2aff }
+            Container { bg: #4a2a2aff }
+        }
+    ";
+
+    #[test]
+    fn splitter_drag_moves_the_handle() {
+        let mut h = widget_harness(SPLITTER, Vec2::new(200.0, 80.0));
+        // Handle sits at x = 98..102 for ratio 0.5; drag it 40 px right.
+        h.run(InputScript::new().drag(Vec2::new(100.0, 40.0), Vec2::new(140.0, 40.0), 4).idle());
+        assert!(h.take_events().iter().all(|e| e == "split_changed"));
+        let Some(WidgetStateValue::Float(ratio)) = state(&h, "split") else {
+            panic!("splitter ratio not persisted");
+        };
+        assert!((ratio - 0.7).abs() < 0.02, "ratio {ratio}");
+        h.assert_snapshot(golden("splitter_dragged.png"));
+    }
+
+    #[test]
+    fn tabs_click_switches_page() {
+        let src = r#"
+            Tabs {
+                id: page
+                font: body
+                on_change: page_changed
+                Tab "Fleet" { Container { bg: #2a4a2aff } }
+                Tab "Routes" { Container { bg: #4a2a2aff } }
+            }
+        "#;
+        let mut h = widget_harness(src, Vec2::new(200.0, 100.0));
+        let first = h.render();
+        h.run(InputScript::new().click(Vec2::new(150.0, 18.0)).idle());
+        assert_eq!(h.take_events(), vec!["page_changed".to_string()]);
+        assert_eq!(state(&h, "page"), Some(WidgetStateValue::Float(1.0)));
+        assert_ne!(h.render(), first);
+        h.assert_snapshot(golden("tabs_second.png"));
+    }
+
+    #[test]
+    fn drag_reports_origin_until_release() {
+        let frames = InputScript::new()
+            .drag(Vec2::new(0.0, 0.0), Vec2::new(30.0, 0.0), 3)
+            .frames()
+            .to_vec();
+        assert_eq!(frames.len(), 5);
+        assert!(frames[..4].iter().all(|f| f.mouse_pressed && f.drag_origin.is_some()));
+        let last = &frames[4];
+        assert!(last.mouse_clicked && last.drag_end.is_some() && last.drag_origin.is_none());
+        assert_eq!(last.mouse_pos.x, 30.0);
+        assert!(frames.windows(2).all(|w| w[1].time_ms > w[0].time_ms));
+    }
+}
@@ -2,6 +2,7 @@ use marduk_engine::coords::{Rect, Vec2};
 use marduk_engine::paint::{Color, Paint};
 use marduk_engine::scene::Border;
 
+use crate::animation::{AnimId, Transition};
 use crate::constraints::{inset_rect, Constraints, Edges, LayoutCtx};
 use crate::cursor::CursorIcon;
 use crate::event::{EventResult, UiEvent};
@@ -12,6 +13,8 @@ use crate::widget::{Element, Widget};
 ///
 /// Visual state (hover, press) is read from `Painter` during `paint` so no
 /// retained state is needed — the button tree can be rebuilt each frame.
+/// With a [`transition`](Self::transition), give a rebuilt button a stable
+/// [`anim_id`](Self::anim_id) so its fades carry over.
 ///
 /// # Example
 /// ```rust,ignore
@@ -34,6 +37,8 @@ pub struct Button {
     padding: Edges,
     min_width: f32,
     min_height: f32,
+    anim_id: AnimId,
+    transition: Transition,
 }
 
 impl Button {
@@ -49,6 +54,8 @@ impl Button {
             padding: Edges::default(),
             min_width: 0.0,
             min_height: 0.0,
+            anim_id: AnimId::new(),
+            transition: Transition::None,
         }
     }
 
@@ -100,6 +107,18 @@ impl Button {
         self.min_height = height;
         self
     }
+
+    /// How the background moves between its normal, hover and press colors.
+    pub fn transition(mut self, t: Transition) -> Self {
+        self.transition = t;
+        self
+    }
+
+    /// Key the background transition by `id` instead of this instance.
+    pub fn anim_id(mut self, id: AnimId) -> Self {
+        self.anim_id = id;
+        self
+    }
 }
 
 impl Widget for Button {
@@ -120,6 +139,7 @@ impl Widget for Button {
         } else {
             self.background
         };
+        let bg = painter.animate(self.anim_id, "bg", bg, self.transition);
 
         painter.fill_rounded_rect(rect, self.corner_radius, Paint::Solid(bg), self.border.clone());
         if painter.is_hovered(rect) {
@@ -129,13 +149,13 @@ impl Widget for Button {
     }
 
     fn on_event(&mut self, event: &UiEvent, rect: Rect, _ctx: &LayoutCtx<'_>) -> EventResult {
-        if let UiEvent::Click { pos } = event {
-            if rect.contains(*pos) {
-                if let Some(f) = &mut self.on_click {
-                    f();
-                }
-                return EventResult::Consumed;
+        if let UiEvent::Click { pos } = event
+            && rect.contains(*pos)
+        {
+            if let Some(f) = &mut self.on_click {
+                f();
             }
+            return EventResult::Consumed;
         }
         EventResult::Ignored
     }
@@ -152,27 +152,27 @@ impl Widget for Checkbox {
         }
 
         // Label
-        if !self.label.is_empty() {
-            if let Some(font) = self.font {
-                let text_x = rect.origin.x + self.box_size + self.gap;
-                let text_h  = self.font_size;
-                let text_y  = rect.origin.y + (rect.size.y - text_h) * 0.5;
-                painter.text(
-                    &self.label, font, self.font_size, self.label_color,
-                    Vec2::new(text_x, text_y),
-                    Some(rect.size.x - self.box_size - self.gap),
-                );
-            }
+        if !self.label.is_empty()
+            && let Some(font) = self.font
+        {
+            let text_x = rect.origin.x + self.box_size + self.gap;
+            let text_h  = self.font_size;
+            let text_y  = rect.origin.y + (rect.size.y - text_h) * 0.5;
+            painter.text(
+                &self.label, font, self.font_size, self.label_color,
+                Vec2::new(text_x, text_y),
+                Some(rect.size.x - self.box_size - self.gap),
+            );
         }
     }
 
     fn on_event(&mut self, event: &UiEvent, rect: Rect, _ctx: &LayoutCtx<'_>) -> EventResult {
-        if let UiEvent::Click { pos } = event {
-            if rect.contains(*pos) {
-                self.checked = !self.checked;
-                if let Some(f) = &mut self.on_change { f(self.checked); }
-                return EventResult::Consumed;
-            }
+        if let UiEvent::Click { pos } = event
+            && rect.contains(*pos)
+        {
+            self.checked = !self.checked;
+            if let Some(f) = &mut self.on_change { f(self.checked); }
+            return EventResult::Consumed;
         }
         EventResult::Ignored
     }
@@ -247,10 +247,6 @@ impl Widget for Combobox {
                     } else {
                         item_bg
                     };
-                    // Use rounded_rect (radius=0) so this lands in RoundedRectRenderer
-                    // alongside the dropdown background.  Both are then sorted by z within
-                    // the same renderer, ensuring the hover highlight renders on top of the
-                    // dropdown background rather than being covered by it.
                     p.fill_rounded_rect(item, 0.0, Paint::Solid(row_bg), None);
 
                     if let Some(f) = font {
@@ -303,69 +299,65 @@ impl Widget for Combobox {
                 }
             }
 
-            UiEvent::OverlayDismiss => {
-                if self.open {
-                    self.open = false;
-                    if let Some(f) = &mut self.on_open_change {
-                        f(false);
-                    }
-                    return EventResult::Consumed;
+            UiEvent::OverlayDismiss if self.open => {
+                self.open = false;
+                if let Some(f) = &mut self.on_open_change {
+                    f(false);
                 }
+                return EventResult::Consumed;
             }
 
-            UiEvent::KeyPress { key, .. } => {
-                if self.open {
-                    match key {
-                        Key::Escape => {
-                            self.open = false;
-                            if let Some(f) = &mut self.on_open_change {
-                                f(false);
-                            }
-                            return EventResult::Consumed;
+            UiEvent::KeyPress { key, .. } if self.open => {
+                match key {
+                    Key::Escape => {
+                        self.open = false;
+                        if let Some(f) = &mut self.on_open_change {
+                            f(false);
                         }
-                        Key::Enter => {
-                            self.open = false;
-                            if let Some(f) = &mut self.on_open_change {
-                                f(false);
-                            }
-                            return EventResult::Consumed;
+                        return EventResult::Consumed;
+                    }
+                    Key::Enter => {
+                        self.open = false;
+                        if let Some(f) = &mut self.on_open_change {
+                            f(false);
                         }
-                        Key::ArrowUp => {
-                            let len = self.options.len();
-                            if len > 0 {
-                                let new_idx = if let Some(cur) = &self.selected {
-                                    let i = self.options.iter().position(|(_, v)| v == cur).unwrap_or(0);
-                                    i.saturating_sub(1)
-                                } else {
-                                    0
-                                };
-                                let value = self.options[new_idx].1.clone();
-                                self.selected = Some(value.clone());
-                                if let Some(f) = &mut self.on_change {
-                                    f(value);
-                                }
+                        return EventResult::Consumed;
+                    }
+                    Key::ArrowUp => {
+                        let len = self.options.len();
+                        if len > 0 {
+                            let new_idx = if let Some(cur) = &self.selected {
+                                let i = self.options.iter().position(|(_, v)| v == cur).unwrap_or(0);
+                                i.saturating_sub(1)
+                            } else {
+                                0
+                            };
+                            let value = self.options[new_idx].1.clone();
+                            self.selected = Some(value.clone());
+                            if let Some(f) = &mut self.on_change {
+                                f(value);
                             }
-                            return EventResult::Consumed;
                         }
-                        Key::ArrowDown => {
-                            let len = self.options.len();
-                            if len > 0 {
-                                let new_idx = if let Some(cur) = &self.selected {
-                                    let i = self.options.iter().position(|(_, v)| v == cur).unwrap_or(0);
-                                    (i + 1).min(len - 1)
-                                } else {
-                                    0
-                                };
-                                let value = self.options[new_idx].1.clone();
-                                self.selected = Some(value.clone());
-                                if let Some(f) = &mut self.on_change {
-                                    f(value);
-                                }
+                        return EventResult::Consumed;
+                    }
+                    Key::ArrowDown => {
+                        let len = self.options.len();
+                        if len > 0 {
+                            let new_idx = if let Some(cur) = &self.selected {
+                                let i = self.options.iter().position(|(_, v)| v == cur).unwrap_or(0);
+                                (i + 1).min(len - 1)
+                            } else {
+                                0
+                            };
+                            let value = self.options[new_idx].1.clone();
+                            self.selected = Some(value.clone());
+                            if let Some(f) = &mut self.on_change {
+                                f(value);
                             }
-                            return EventResult::Consumed;
                         }
-                        _ => {}
+                        return EventResult::Consumed;
                     }
+                    _ => {}
                 }
             }
 
@@ -125,10 +125,10 @@ impl Column {
     /// and stores the result.
     fn get_child_sizes(&self, child_c: Constraints, ctx: &LayoutCtx) -> Vec<Vec2> {
         let mut cache = self.cached_sizes.borrow_mut();
-        if let Some((cached_c, ref sizes)) = *cache {
-            if cached_c == child_c {
-                return sizes.clone();
-            }
+        if let Some((cached_c, ref sizes)) = *cache
+            && cached_c == child_c
+        {
+            return sizes.clone();
         }
         let sizes: Vec<Vec2> = self.children.iter().map(|c| c.measure(child_c, ctx)).collect();
         *cache = Some((child_c, sizes.clone()));
@@ -350,10 +350,10 @@ impl Row {
     /// typical UI depths); on a miss children are re-measured and spacers expanded.
     fn get_child_sizes(&self, child_c: Constraints, available_w: f32, ctx: &LayoutCtx) -> Vec<Vec2> {
         let mut cache = self.cached_sizes.borrow_mut();
-        if let Some((cached_c, ref sizes)) = *cache {
-            if cached_c == child_c {
-                return sizes.clone();
-            }
+        if let Some((cached_c, ref sizes)) = *cache
+            && cached_c == child_c
+        {
+            return sizes.clone();
         }
         let mut sizes: Vec<Vec2> = self.children.iter().map(|c| c.measure(child_c, ctx)).collect();
         Self::distribute_spacers(&mut sizes, available_w, self.children.len(), self.spacing);
@@ -3,11 +3,15 @@ use marduk_engine::paint::{Color, Paint};
 use marduk_engine::scene::Border;
 use marduk_engine::text::FontId;
 
+use crate::animation::{AnimId, Transition};
 use crate::constraints::{Constraints, LayoutCtx};
 use crate::event::{EventResult, UiEvent};
 use crate::painter::Painter;
 use crate::widget::{Element, Widget};
 
+/// How far below its resting place the dialog starts sliding in (logical px).
+const SLIDE: f32 = 16.0;
+
 /// A centered overlay panel that blocks input behind it.
 ///
 /// Renders a dim backdrop when `open` is `true` and a centered dialog box on
@@ -41,6 +45,8 @@ pub struct Modal {
     bg: Color,
     border_color: Color,
     corner_radius: f32,
+    anim_id: AnimId,
+    transition: Transition,
 }
 
 impl Modal {
@@ -59,6 +65,8 @@ impl Modal {
             bg:             Color::from_srgb(0.16, 0.16, 0.20, 1.0),
             border_color:   Color::from_srgb(0.3, 0.3, 0.36, 1.0),
             corner_radius: 8.0,
+            anim_id: AnimId::new(),
+            transition: Transition::None,
         }
     }
 
@@ -112,6 +120,20 @@ impl Modal {
         self
     }
 
+    /// How the backdrop fades and the dialog slides in on opening, and
+    /// back out on closing.
+    pub fn transition(mut self, t: Transition) -> Self {
+        self.transition = t;
+        self
+    }
+
+    /// Key the open/close transition by `id`, so a modal rebuilt when it
+    /// opens still fades in.
+    pub fn anim_id(mut self, id: AnimId) -> Self {
+        self.anim_id = id;
+        self
+    }
+
     // ── helpers ───────────────────────────────────────────────────────────
 
     fn dialog_rect(&self, viewport: Rect, content_h: f32) -> Rect {
@@ -145,23 +167,36 @@ impl Widget for Modal {
     }
 
     fn paint(&self, painter: &mut Painter, rect: Rect) {
-        if !self.open { return; }
+        // 1 when fully open; fades toward 0 on closing before it disappears.
+        let shown = painter.animate(self.anim_id, "open", if self.open { 1.0 } else { 0.0 }, self.transition);
+        if shown <= 0.0 { return; }
+        let faded = |c: Color| if shown >= 1.0 { c } else { fade(c, shown) };
 
         // Compute dialog size based on child's natural height.
         let ctx = painter.layout_ctx();
         let content_c = Constraints::loose(Vec2::new(self.max_width - 48.0, f32::INFINITY));
         let content_sz = self.child.measure(content_c, &ctx);
-        let dialog = self.dialog_rect(rect, content_sz.y);
+        let mut dialog = self.dialog_rect(rect, content_sz.y);
+        dialog.origin.y += (1.0 - shown) * SLIDE;
         let content = self.content_rect(dialog);
 
-        let backdrop_color = self.backdrop_color;
-        let bg             = self.bg;
-        let border_color   = self.border_color;
+        let backdrop_color = faded(self.backdrop_color);
+        let bg             = faded(self.bg);
+        let border_color   = faded(self.border_color);
         let corner         = self.corner_radius;
         let title          = self.title.clone();
         let font           = self.font;
         let font_size      = self.font_size;
-        let title_color    = self.title_color;
+        let title_color    = faded(self.title_color);
+
+        // Closing: the dialog fades out empty and no longer blocks input.
+        if !self.open {
+            painter.overlay_scope(|p| {
+                p.fill_rect(rect, backdrop_color);
+                p.fill_rounded_rect(dialog, corner, Paint::Solid(bg), Some(Border::new(1.0, border_color)));
+            });
+            return;
+        }
 
         painter.register_overlay(dialog);
         painter.overlay_scope(|p| {
@@ -260,3 +295,9 @@ impl Widget for Modal {
         EventResult::Ignored
     }
 }
+
+/// `c` at `alpha` of its opacity.
+fn fade(c: Color, alpha: f32) -> Color {
+    let alpha = alpha.clamp(0.0, 1.0);
+    Color::from_premul(c.r * alpha, c.g * alpha, c.b * alpha, c.a * alpha)
+}
@@ -139,11 +139,11 @@ impl NumberInput {
     }
 
     fn commit_edit(&mut self) {
-        if let Some(text) = self.edit_text.take() {
-            if let Ok(v) = text.trim().parse::<f64>() {
-                self.clamp_and_commit(v);
-            }
-            // If parse fails, revert to current value (edit_text already None).
+        // If parse fails, revert to current value (edit_text already None).
+        if let Some(text) = self.edit_text.take()
+            && let Ok(v) = text.trim().parse::<f64>()
+        {
+            self.clamp_and_commit(v);
         }
     }
 }
@@ -257,59 +257,53 @@ impl Widget for NumberInput {
                 }
             }
 
-            UiEvent::TextInput { text } => {
-                if focused {
-                    if self.edit_text.is_none() {
-                        self.edit_text = Some(self.format_value());
-                    }
-                    let current = self.edit_text.as_mut().unwrap();
-                    for ch in text.chars() {
-                        if ch.is_ascii_digit() || ch == '-' || ch == '.' {
-                            current.push(ch);
-                        }
+            UiEvent::TextInput { text } if focused => {
+                if self.edit_text.is_none() {
+                    self.edit_text = Some(self.format_value());
+                }
+                let current = self.edit_text.as_mut().unwrap();
+                for ch in text.chars() {
+                    if ch.is_ascii_digit() || ch == '-' || ch == '.' {
+                        current.push(ch);
                     }
-                    return EventResult::Consumed;
                 }
+                return EventResult::Consumed;
             }
 
-            UiEvent::KeyPress { key, .. } => {
-                if focused {
-                    match key {
-                        Key::Backspace => {
-                            if self.edit_text.is_none() {
-                                self.edit_text = Some(self.format_value());
-                            }
-                            let current = self.edit_text.as_mut().unwrap();
-                            current.pop();
-                            return EventResult::Consumed;
-                        }
-                        Key::Enter => {
-                            self.commit_edit();
-                            return EventResult::Consumed;
+            UiEvent::KeyPress { key, .. } if focused => {
+                match key {
+                    Key::Backspace => {
+                        if self.edit_text.is_none() {
+                            self.edit_text = Some(self.format_value());
                         }
-                        Key::Escape => {
-                            self.edit_text = None;
-                            return EventResult::Consumed;
-                        }
-                        Key::ArrowUp => {
-                            self.step_value(1.0);
-                            return EventResult::Consumed;
-                        }
-                        Key::ArrowDown => {
-                            self.step_value(-1.0);
-                            return EventResult::Consumed;
-                        }
-                        _ => {}
+                        let current = self.edit_text.as_mut().unwrap();
+                        current.pop();
+                        return EventResult::Consumed;
+                    }
+                    Key::Enter => {
+                        self.commit_edit();
+                        return EventResult::Consumed;
+                    }
+                    Key::Escape => {
+                        self.edit_text = None;
+                        return EventResult::Consumed;
                     }
+                    Key::ArrowUp => {
+                        self.step_value(1.0);
+                        return EventResult::Consumed;
+                    }
+                    Key::ArrowDown => {
+                        self.step_value(-1.0);
+                        return EventResult::Consumed;
+                    }
+                    _ => {}
                 }
             }
 
-            UiEvent::ScrollWheel { delta } => {
-                if focused {
-                    // Positive delta = scroll down = decrease value.
-                    self.step_value(-(*delta as f64).signum());
-                    return EventResult::Consumed;
-                }
+            UiEvent::ScrollWheel { delta } if focused => {
+                // Positive delta = scroll down = decrease value.
+                self.step_value(-(*delta as f64).signum());
+                return EventResult::Consumed;
             }
 
             UiEvent::FocusLost => {
@@ -192,10 +192,10 @@ impl ScrollView {
         let max = (content_h - viewport_h).max(0.0);
         let prev = self.scroll_offset;
         self.scroll_offset = (self.scroll_offset + delta).clamp(0.0, max);
-        if self.scroll_offset != prev {
-            if let Some(f) = &mut self.on_scroll {
-                f(self.scroll_offset);
-            }
+        if self.scroll_offset != prev
+            && let Some(f) = &mut self.on_scroll
+        {
+            f(self.scroll_offset);
         }
     }
 }
@@ -234,25 +234,21 @@ impl Widget for Splitter {
         // `on_drag_change` callback which persists it through widget_state.
         let grab = self.grab_rect(handle_rect);
         match event {
-            UiEvent::Drag { pos, start } => {
-                if self.dragging || grab.contains(*start) {
-                    if !self.dragging {
-                        self.dragging = true;
-                        if let Some(f) = &mut self.on_drag_change { f(true); }
-                    }
-                    self.ratio = self.ratio_from_drag(rect, *pos).clamp(0.01, 0.99);
-                    if let Some(f) = &mut self.on_change { f(self.ratio); }
-                    return EventResult::Consumed;
+            UiEvent::Drag { pos, start } if self.dragging || grab.contains(*start) => {
+                if !self.dragging {
+                    self.dragging = true;
+                    if let Some(f) = &mut self.on_drag_change { f(true); }
                 }
+                self.ratio = self.ratio_from_drag(rect, *pos).clamp(0.01, 0.99);
+                if let Some(f) = &mut self.on_change { f(self.ratio); }
+                return EventResult::Consumed;
             }
-            UiEvent::DragEnd { pos, start } => {
-                if self.dragging || grab.contains(*start) {
-                    self.dragging = false;
-                    if let Some(f) = &mut self.on_drag_change { f(false); }
-                    self.ratio = self.ratio_from_drag(rect, *pos).clamp(0.01, 0.99);
-                    if let Some(f) = &mut self.on_change { f(self.ratio); }
-                    return EventResult::Consumed;
-                }
+            UiEvent::DragEnd { pos, start } if self.dragging || grab.contains(*start) => {
+                self.dragging = false;
+                if let Some(f) = &mut self.on_drag_change { f(false); }
+                self.ratio = self.ratio_from_drag(rect, *pos).clamp(0.01, 0.99);
+                if let Some(f) = &mut self.on_change { f(self.ratio); }
+                return EventResult::Consumed;
             }
             _ => {}
         }
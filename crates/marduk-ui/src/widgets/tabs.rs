@@ -2,6 +2,7 @@ use marduk_engine::coords::{Rect, Vec2};
 use marduk_engine::paint::Color;
 use marduk_engine::text::FontId;
 
+use crate::animation::{AnimId, Transition};
 use crate::constraints::{Constraints, LayoutCtx};
 use crate::event::{EventResult, UiEvent};
 use crate::painter::Painter;
@@ -29,6 +30,8 @@ pub struct Tabs {
     active_text_color: Color,
     inactive_text_color: Color,
     indicator_color: Color,
+    anim_id: AnimId,
+    transition: Transition,
 }
 
 impl Tabs {
@@ -45,6 +48,8 @@ impl Tabs {
             active_text_color:    Color::from_srgb(1.0, 1.0, 1.0, 1.0),
             inactive_text_color:  Color::from_srgb(0.65, 0.65, 0.65, 1.0),
             indicator_color:      Color::from_srgb(0.39, 0.55, 1.0, 1.0),
+            anim_id:              AnimId::new(),
+            transition:           Transition::None,
         }
     }
 
@@ -93,6 +98,19 @@ impl Tabs {
         self
     }
 
+    /// How the indicator slides to a newly selected tab.
+    pub fn transition(mut self, t: Transition) -> Self {
+        self.transition = t;
+        self
+    }
+
+    /// Key the indicator's slide by `id`, so tabs rebuilt with a new
+    /// selection still slide from the old one.
+    pub fn anim_id(mut self, id: AnimId) -> Self {
+        self.anim_id = id;
+        self
+    }
+
     // ── helpers ───────────────────────────────────────────────────────────
 
     fn tab_rect(&self, rect: Rect, i: usize) -> Rect {
@@ -157,16 +175,19 @@ impl Widget for Tabs {
                 let tc = if active { self.active_text_color } else { self.inactive_text_color };
                 painter.text(label.clone(), font, self.font_size, tc, Vec2::new(tx, ty), None);
             }
+        }
 
-            if active {
-                let indicator = Rect::new(
-                    tab_rect.origin.x,
-                    tab_rect.origin.y + self.tab_height - 2.0,
-                    tab_rect.size.x,
-                    2.0,
-                );
-                painter.fill_rect(indicator, self.indicator_color);
-            }
+        // The indicator slides between tabs; `at` is a fractional tab index.
+        if self.selected < self.tabs.len() {
+            let at = painter.animate(self.anim_id, "indicator", self.selected as f32, self.transition);
+            let tab_w = rect.size.x / self.tabs.len() as f32;
+            let indicator = Rect::new(
+                rect.origin.x + at * tab_w,
+                rect.origin.y + self.tab_height - 2.0,
+                tab_w,
+                2.0,
+            );
+            painter.fill_rect(indicator, self.indicator_color);
         }
 
         let content_rect = self.content_rect(rect);
@@ -178,36 +199,36 @@ impl Widget for Tabs {
     fn on_event(&mut self, event: &UiEvent, rect: Rect, ctx: &LayoutCtx<'_>) -> EventResult {
         let bar = Rect::new(rect.origin.x, rect.origin.y, rect.size.x, self.tab_height);
 
-        if let UiEvent::Click { pos } = event {
-            if bar.contains(*pos) {
-                let n = self.tabs.len().max(1);
-                let tab_w = rect.size.x / n as f32;
-                let i = ((pos.x - rect.origin.x) / tab_w) as usize;
-                let i = i.min(self.tabs.len().saturating_sub(1));
-                if i != self.selected {
-                    self.selected = i;
-                    if let Some(f) = &mut self.on_change {
-                        f(i);
-                    }
+        if let UiEvent::Click { pos } = event
+            && bar.contains(*pos)
+        {
+            let n = self.tabs.len().max(1);
+            let tab_w = rect.size.x / n as f32;
+            let i = ((pos.x - rect.origin.x) / tab_w) as usize;
+            let i = i.min(self.tabs.len().saturating_sub(1));
+            if i != self.selected {
+                self.selected = i;
+                if let Some(f) = &mut self.on_change {
+                    f(i);
                 }
-                return EventResult::Consumed;
             }
+            return EventResult::Consumed;
         }
 
         // Route keyboard events to the active tab content
-        if let UiEvent::KeyPress { key: marduk_engine::input::Key::ArrowLeft, .. } = event {
-            if self.selected > 0 {
-                self.selected -= 1;
-                if let Some(f) = &mut self.on_change { f(self.selected); }
-                return EventResult::Consumed;
-            }
+        if let UiEvent::KeyPress { key: marduk_engine::input::Key::ArrowLeft, .. } = event
+            && self.selected > 0
+        {
+            self.selected -= 1;
+            if let Some(f) = &mut self.on_change { f(self.selected); }
+            return EventResult::Consumed;
         }
-        if let UiEvent::KeyPress { key: marduk_engine::input::Key::ArrowRight, .. } = event {
-            if self.selected + 1 < self.tabs.len() {
-                self.selected += 1;
-                if let Some(f) = &mut self.on_change { f(self.selected); }
-                return EventResult::Consumed;
-            }
+        if let UiEvent::KeyPress { key: marduk_engine::input::Key::ArrowRight, .. } = event
+            && self.selected + 1 < self.tabs.len()
+        {
+            self.selected += 1;
+            if let Some(f) = &mut self.on_change { f(self.selected); }
+            return EventResult::Consumed;
         }
 
         let content_rect = self.content_rect(rect);
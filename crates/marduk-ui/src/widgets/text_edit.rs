@@ -146,11 +146,11 @@ impl TextEditState {
 
     /// Paste from clipboard at cursor; returns `true` if text changed.
     pub fn paste(&mut self) -> bool {
-        if let Ok(mut cb) = arboard::Clipboard::new() {
-            if let Ok(text) = cb.get_text() {
-                self.insert_str(&text);
-                return true;
-            }
+        if let Ok(mut cb) = arboard::Clipboard::new()
+            && let Ok(text) = cb.get_text()
+        {
+            self.insert_str(&text);
+            return true;
         }
         false
     }
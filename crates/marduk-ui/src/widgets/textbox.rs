@@ -1,3 +1,5 @@
+use std::cell::Cell;
+
 use marduk_engine::coords::{Rect, Vec2};
 use marduk_engine::input::Key;
 use marduk_engine::paint::{Color, Paint};
@@ -12,6 +14,9 @@ use crate::painter::Painter;
 use crate::widget::Widget;
 use crate::widgets::text_edit::TextEditState;
 
+/// How long the caret stays on, and then off, while a focused box is idle.
+const CARET_BLINK_MS: u64 = 530;
+
 /// A single-line text input field with cursor, selection, and clipboard support.
 ///
 /// Click to place cursor, drag to select, Shift+Arrows to extend selection,
@@ -54,7 +59,11 @@ pub struct TextBox {
     on_focus:         Option<Box<dyn FnMut()>>,
     /// Fired after any cursor / selection / scroll change.
     /// Args: (cursor_byte, anchor_byte, scroll_offset)
+    #[allow(clippy::type_complexity)]
     on_cursor_change: Option<Box<dyn FnMut(usize, usize, f32)>>,
+    /// App time the caret's blink cycle started: the first frame painted
+    /// focused since the cursor last moved. Set during `paint`.
+    blink_since:      Cell<Option<u64>>,
 }
 
 impl TextBox {
@@ -80,6 +89,7 @@ impl TextBox {
             on_submit:            None,
             on_focus:             None,
             on_cursor_change:     None,
+            blink_since:          Cell::new(None),
         }
     }
 
@@ -149,6 +159,8 @@ impl TextBox {
     // ── helpers ───────────────────────────────────────────────────────────
 
     fn notify_cursor_change(&mut self) {
+        // Show the caret right away wherever it went.
+        self.blink_since.set(None);
         if let Some(f) = &mut self.on_cursor_change {
             f(self.edit.cursor, self.edit.anchor, self.edit.scroll_offset);
         }
@@ -160,6 +172,17 @@ impl TextBox {
         }
     }
 
+    /// Whether the caret shows this frame. It stays on for
+    /// [`CARET_BLINK_MS`] after the cursor moves, then toggles; asks for a
+    /// frame at the next toggle.
+    fn caret_visible(&self, painter: &mut Painter) -> bool {
+        let since = self.blink_since.get().unwrap_or(painter.time_ms);
+        self.blink_since.set(Some(since));
+        let phase = painter.time_ms.saturating_sub(since) / CARET_BLINK_MS;
+        painter.request_frame_at(since + (phase + 1) * CARET_BLINK_MS);
+        phase.is_multiple_of(2)
+    }
+
     /// Compute the inner content rect from a widget rect.
     fn inner_rect(&self, rect: Rect) -> Rect {
         Rect::new(
@@ -189,6 +212,9 @@ impl Widget for TextBox {
 
         // Focused state: either explicitly set (DSL / click) or via FocusManager (Tab cycling).
         let is_focused = self.focused || painter.is_focused(self.focus_id);
+        if !is_focused {
+            self.blink_since.set(None);
+        }
 
         let bg = if is_focused { self.focused_bg } else { self.bg };
         let border_color = if is_focused { self.focused_border_color } else { self.border_color };
@@ -242,8 +268,8 @@ impl Widget for TextBox {
                 Some(inner.size.x + scroll),
             );
 
-            // Cursor bar — only when nothing is selected
-            if is_focused && !self.edit.has_selection() {
+            // Cursor bar — only when nothing is selected, and blinking
+            if is_focused && !self.edit.has_selection() && self.caret_visible(painter) {
                 let scale = painter.scale;
                 let fs    = painter.font_system;
                 let cx = fs.measure_text_scaled(
@@ -477,3 +503,51 @@ impl TextBox {
         self.notify_cursor_change();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scene::{UiInput, UiScene};
+    use crate::testing::Harness;
+
+    fn harness(focused: bool) -> Harness {
+        let mut scene = UiScene::new();
+        let font = scene.load_font(include_bytes!("../../../../assets/fonts/Tuffy.ttf")).unwrap();
+        let tb = TextBox::new().font(font).text("ab").focused(focused);
+        Harness::element(scene, tb, Vec2::new(120.0, 32.0))
+    }
+
+    fn render_at(h: &mut Harness, input: UiInput) -> marduk_engine::render::software::Pixmap {
+        h.frame(&input);
+        h.render()
+    }
+
+    fn at(time_ms: u64) -> UiInput {
+        UiInput { time_ms, ..UiInput::default() }
+    }
+
+    #[test]
+    fn caret_blinks_while_focused() {
+        let mut h = harness(true);
+        let on = render_at(&mut h, at(0));
+        assert_eq!(h.scene().next_frame_at(), Some(CARET_BLINK_MS));
+
+        let off = render_at(&mut h, at(CARET_BLINK_MS));
+        assert_ne!(off, on);
+        assert_eq!(h.scene().next_frame_at(), Some(2 * CARET_BLINK_MS));
+        assert_eq!(render_at(&mut h, at(2 * CARET_BLINK_MS + 10)), on);
+
+        // Moving the cursor shows the caret again and restarts the cycle.
+        let typed = UiInput { text_input: vec!["c".into()], ..at(3 * CARET_BLINK_MS + 10) };
+        h.frame(&typed);
+        let _ = render_at(&mut h, at(3 * CARET_BLINK_MS + 20));
+        assert_eq!(h.scene().next_frame_at(), Some(4 * CARET_BLINK_MS + 20));
+    }
+
+    #[test]
+    fn unfocused_box_asks_for_no_frames() {
+        let mut h = harness(false);
+        let _ = render_at(&mut h, at(0));
+        assert_eq!(h.scene().next_frame_at(), None);
+    }
+}
@@ -1,6 +1,7 @@
 use marduk_engine::coords::{Rect, Vec2};
 use marduk_engine::paint::{Color, Paint};
 
+use crate::animation::{AnimId, Transition};
 use crate::constraints::{Constraints, LayoutCtx};
 use crate::event::{EventResult, UiEvent};
 use crate::painter::Painter;
@@ -23,6 +24,8 @@ pub struct Toggle {
     off_color: Color,
     thumb_color: Color,
     on_change: Option<Box<dyn FnMut(bool)>>,
+    anim_id: AnimId,
+    transition: Transition,
 }
 
 impl Toggle {
@@ -35,6 +38,8 @@ impl Toggle {
             off_color: Color::from_straight(0.2, 0.25, 0.35, 1.0),
             thumb_color: Color::from_straight(1.0, 1.0, 1.0, 1.0),
             on_change: None,
+            anim_id: AnimId::new(),
+            transition: Transition::None,
         }
     }
 
@@ -48,6 +53,11 @@ impl Toggle {
         self.on_change = Some(Box::new(f));
         self
     }
+    /// How the thumb slides and the track recolors when toggled.
+    pub fn transition(mut self, t: Transition) -> Self { self.transition = t; self }
+    /// Key the transition by `id`, so a toggle rebuilt with its new state
+    /// still slides from the old one.
+    pub fn anim_id(mut self, id: AnimId) -> Self { self.anim_id = id; self }
 }
 
 impl Default for Toggle { fn default() -> Self { Self::new() } }
@@ -58,7 +68,9 @@ impl Widget for Toggle {
     }
 
     fn paint(&self, painter: &mut Painter, rect: Rect) {
-        let track_color = if self.checked { self.on_color } else { self.off_color };
+        // 0 = off, 1 = on; in between while sliding.
+        let on = painter.animate(self.anim_id, "on", if self.checked { 1.0 } else { 0.0 }, self.transition);
+        let track_color = mix(self.off_color, self.on_color, on);
         let radius = rect.size.y * 0.5;
 
         // Track (pill shape)
@@ -67,11 +79,8 @@ impl Widget for Toggle {
         // Thumb
         let margin = rect.size.y * 0.13;
         let thumb_r = (rect.size.y * 0.5) - margin;
-        let thumb_cx = if self.checked {
-            rect.origin.x + rect.size.x - margin - thumb_r
-        } else {
-            rect.origin.x + margin + thumb_r
-        };
+        let travel = rect.size.x - 2.0 * (margin + thumb_r);
+        let thumb_cx = rect.origin.x + margin + thumb_r + travel * on;
         let thumb_cy = rect.origin.y + rect.size.y * 0.5;
         painter.fill_circle(
             Vec2::new(thumb_cx, thumb_cy),
@@ -82,13 +91,25 @@ impl Widget for Toggle {
     }
 
     fn on_event(&mut self, event: &UiEvent, rect: Rect, _ctx: &LayoutCtx<'_>) -> EventResult {
-        if let UiEvent::Click { pos } = event {
-            if rect.contains(*pos) {
-                self.checked = !self.checked;
-                if let Some(f) = &mut self.on_change { f(self.checked); }
-                return EventResult::Consumed;
-            }
+        if let UiEvent::Click { pos } = event
+            && rect.contains(*pos)
+        {
+            self.checked = !self.checked;
+            if let Some(f) = &mut self.on_change { f(self.checked); }
+            return EventResult::Consumed;
         }
         EventResult::Ignored
     }
 }
+
+/// `a` at `t = 0`, `b` at `t = 1`. Springs may overshoot, so `t` is clamped.
+fn mix(a: Color, b: Color, t: f32) -> Color {
+    if t <= 0.0 {
+        return a;
+    }
+    if t >= 1.0 {
+        return b;
+    }
+    let lerp = |x: f32, y: f32| x + (y - x) * t;
+    Color::from_premul(lerp(a.r, b.r), lerp(a.g, b.g), lerp(a.b, b.b), lerp(a.a, b.a))
+}
@@ -113,7 +113,14 @@ impl Widget for Tooltip {
     fn paint(&self, painter: &mut Painter, rect: Rect) {
         self.child.paint(painter, rect);
 
-        if self.text.is_empty() || !self.is_ready(painter.time_ms) {
+        if self.text.is_empty() {
+            return;
+        }
+        if !self.is_ready(painter.time_ms) {
+            // Wake up to show the tip once the delay has passed.
+            if let Some(start) = self.hover_since_ms {
+                painter.request_frame_at(start + self.delay_ms);
+            }
             return;
         }
         let Some(font) = self.font else { return; };
@@ -7,9 +7,18 @@ import {
     ServerOptions,
 } from 'vscode-languageclient/node';
 
+import { PreviewPanel } from './preview';
+
 let client: LanguageClient | undefined;
 
 export function activate(context: vscode.ExtensionContext): void {
+    context.subscriptions.push(
+        vscode.commands.registerCommand('mkml.showPreview', () => {
+            const document = vscode.window.activeTextEditor?.document;
+            if (document?.languageId === 'mkml') PreviewPanel.show(document);
+        })
+    );
+
     const serverOptions = resolveServerOptions();
     if (!serverOptions) {
         vscode.window.showWarningMessage(
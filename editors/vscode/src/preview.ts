@@ -0,0 +1,139 @@
+import { execFile } from 'child_process';
+import * as fs from 'fs';
+import * as path from 'path';
+import * as vscode from 'vscode';
+
+/**
+ * Side-by-side preview of an `.mkml` document.
+ *
+ * Runs `marduk-preview` on the document whenever it (or any `.mkml` file,
+ * since it may be an import) is saved, and shows the PNG in a webview.
+ */
+export class PreviewPanel {
+    private static current: PreviewPanel | undefined;
+
+    private readonly disposables: vscode.Disposable[] = [];
+    private running = false;
+    private pending = false;
+
+    static show(document: vscode.TextDocument): void {
+        if (PreviewPanel.current) {
+            PreviewPanel.current.document = document;
+            PreviewPanel.current.panel.reveal(vscode.ViewColumn.Beside, true);
+            PreviewPanel.current.render();
+            return;
+        }
+        const panel = vscode.window.createWebviewPanel(
+            'mkmlPreview',
+            'Preview',
+            { viewColumn: vscode.ViewColumn.Beside, preserveFocus: true },
+            { enableScripts: false },
+        );
+        PreviewPanel.current = new PreviewPanel(panel, document);
+    }
+
+    private constructor(private readonly panel: vscode.WebviewPanel, private document: vscode.TextDocument) {
+        this.panel.onDidDispose(() => this.dispose(), null, this.disposables);
+        vscode.workspace.onDidSaveTextDocument(
+            (saved) => {
+                if (saved.languageId === 'mkml') this.render();
+            },
+            null,
+            this.disposables,
+        );
+        vscode.workspace.onDidChangeConfiguration(
+            (e) => {
+                if (e.affectsConfiguration('mkml.preview')) this.render();
+            },
+            null,
+            this.disposables,
+        );
+        this.render();
+    }
+
+    /** Renders now, or once more after the render in flight finishes. */
+    private render(): void {
+        if (this.running) {
+            this.pending = true;
+            return;
+        }
+        this.running = true;
+        this.panel.title = `Preview ${path.basename(this.document.fileName)}`;
+
+        const config = vscode.workspace.getConfiguration('mkml.preview');
+        const width = config.get<number>('width', 800);
+        const height = config.get<number>('height', 600);
+        const scale = config.get<number>('scale', 1);
+        const args = [
+            this.document.fileName,
+            '--size', `${width}x${height}`,
+            '--scale', String(scale),
+            ...config.get<string[]>('args', []),
+        ];
+        const [command, prefix] = previewCommand();
+        execFile(
+            command,
+            [...prefix, ...args],
+            { encoding: 'buffer', maxBuffer: 256 * 1024 * 1024 },
+            (error, stdout, stderr) => {
+                this.running = false;
+                if (error) {
+                    const message = stderr.toString().trim() || error.message;
+                    this.panel.webview.html = page(`<pre>${escape(message)}</pre>`);
+                } else {
+                    const src = `data:image/png;base64,${stdout.toString('base64')}`;
+                    this.panel.webview.html = page(
+                        `<img src="${src}" width="${width}" height="${height}" alt="">`,
+                    );
+                }
+                if (this.pending) {
+                    this.pending = false;
+                    this.render();
+                }
+            },
+        );
+    }
+
+    private dispose(): void {
+        PreviewPanel.current = undefined;
+        for (const d of this.disposables) d.dispose();
+    }
+}
+
+/** `[command, leading args]`: a pre-built binary, or `cargo run`. */
+function previewCommand(): [string, string[]] {
+    const configured = vscode.workspace.getConfiguration('mkml.preview').get<string>('path');
+    if (configured) return [configured, []];
+
+    const workspacePath = vscode.workspace.workspaceFolders?.[0]?.uri.fsPath;
+    const exe = process.platform === 'win32' ? 'marduk-preview.exe' : 'marduk-preview';
+    if (workspacePath) {
+        for (const sub of ['release', 'debug']) {
+            const bin = path.join(workspacePath, 'target', sub, exe);
+            if (fs.existsSync(bin)) return [bin, []];
+        }
+        return ['cargo', [
+            'run',
+            '--manifest-path', path.join(workspacePath, 'Cargo.toml'),
+            '--package', 'marduk-preview',
+            '--quiet',
+            '--',
+        ]];
+    }
+    return [exe, []];
+}
+
+function page(body: string): string {
+    return `<!DOCTYPE html>
+<html>
+<head>
+<meta http-equiv="Content-Security-Policy" content="default-src 'none'; img-src data:; style-src 'unsafe-inline';">
+<style>body { margin: 0; padding: 8px; } img { max-width: 100%; height: auto; } pre { white-space: pre-wrap; }</style>
+</head>
+<body>${body}</body>
+</html>`;
+}
+
+function escape(text: string): string {
+    return text.replace(/&/g, '&amp;').replace(/</g, '&lt;').replace(/>/g, '&gt;');
+}